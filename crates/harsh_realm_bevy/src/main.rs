@@ -1,9 +1,15 @@
+// Bevy system queries are routinely too nested for this lint
+#![allow(clippy::type_complexity)]
+
 use bevy::prelude::*;
 use bevy::prelude::Window;
 use bevy::window::PrimaryWindow;
 use bevy_egui::{egui, EguiContexts, EguiPlugin};
-use log::info;
+use log::{info, warn};
+use harsh_realm_sim::events::bevy_bridge::SimEventsPlugin;
+use harsh_realm_sim::events::game_event::GameEvent;
 use harsh_realm_sim::game_state;
+use harsh_realm_sim::simulation::real_time::{PauseTrigger, SimulationSpeed};
 use bevy::render::mesh::{Indices, PrimitiveTopology};
 use bevy::sprite::MaterialMesh2dBundle;
 
@@ -44,8 +50,12 @@ fn main() {
         }),
     )
     .add_plugins(EguiPlugin)
+    .add_plugins(SimEventsPlugin)
     .add_systems(Startup, setup_system)
     .add_systems(Update, ui_system)
+    .add_systems(Update, real_time_system)
+    .add_systems(Update, event_log_system)
+    .add_systems(Update, intel_ui_system)
     .add_systems(Update, solar_system_view_system)
     .add_systems(PostUpdate, planet_label_system)
     .add_systems(PostUpdate, update_visibility_system);
@@ -245,18 +255,61 @@ fn ui_system(mut contexts: EguiContexts, mut game_state: ResMut<game_state::Game
             game_state.simulation.current_turn,
             game_state.get_formatted_date()
         ));
-        if ui.button("End Turn").clicked() {
+        if let Some(outcome) = &game_state.game_over {
+            let winner = outcome
+                .rankings
+                .iter()
+                .find(|standing| Some(standing.faction_id) == outcome.winner)
+                .map_or("nobody", |standing| standing.name.as_str());
+            ui.colored_label(egui::Color32::YELLOW, format!("Game over: {} wins ({:?})", winner, outcome.reason));
+        }
+        if ui.add_enabled(!game_state.is_game_over(), egui::Button::new("End Turn")).clicked() {
             info!("=== Turn {} ===", game_state.simulation.current_turn + 1);
             game_state.process_turn();
             info!("Game date after turn: {}", game_state.get_formatted_date());
         }
 
+        let can_undo = game_state.can_undo();
+        if ui
+            .add_enabled(can_undo, egui::Button::new("Undo Turn"))
+            .clicked()
+        {
+            match game_state.undo_turn() {
+                Ok(()) => info!("Rewound to turn {} ({})", game_state.simulation.current_turn, game_state.get_formatted_date()),
+                Err(e) => warn!("Undo failed: {}", e),
+            }
+        }
+
         if ui.button("Exit").clicked() {
             std::process::exit(0);
         }
 
         ui.separator();
 
+        // Real-time controls
+        let pause_label = if game_state.clock.paused { "Run" } else { "Pause" };
+        if ui.button(pause_label).clicked() {
+            game_state.clock.toggle_pause();
+        }
+        ui.horizontal(|ui| {
+            let speeds = [
+                ("1 day/s", SimulationSpeed::DayPerSecond),
+                ("1 week/s", SimulationSpeed::WeekPerSecond),
+                ("1 month/s", SimulationSpeed::MonthPerSecond),
+            ];
+            for (label, speed) in speeds {
+                if ui.selectable_label(game_state.clock.speed == speed, label).clicked() {
+                    game_state.clock.speed = speed;
+                }
+            }
+        });
+        let mut pause_on_turn_end = game_state.clock.pause_on.contains(&PauseTrigger::TurnEnded);
+        if ui.checkbox(&mut pause_on_turn_end, "Pause at end of turn").changed() {
+            game_state.clock.set_pause_on(PauseTrigger::TurnEnded, pause_on_turn_end);
+        }
+
+        ui.separator();
+
         // View switch buttons
         let inner_enabled = *view_mode != ViewMode::Inner;
         if ui
@@ -284,6 +337,73 @@ fn ui_system(mut contexts: EguiContexts, mut game_state: ResMut<game_state::Game
     });
 }
 
+/// Advances the simulation while the real-time clock is running
+fn real_time_system(time: Res<Time>, mut game_state: ResMut<game_state::GameState>) {
+    if game_state.clock.paused {
+        return;
+    }
+    let tick = game_state.tick_real_time(time.delta_seconds_f64());
+    if let Some(trigger) = tick.paused_by {
+        info!("Auto-paused on {:?} at {}", trigger, game_state.get_formatted_date());
+    }
+}
+
+/// Shows what the chosen faction knows, read only from its knowledge model
+fn intel_ui_system(
+    mut contexts: EguiContexts,
+    game_state: Res<game_state::GameState>,
+    mut viewing_as: Local<Option<uuid::Uuid>>,
+) {
+    let ctx = contexts.ctx_mut();
+    egui::Window::new("Intel").default_open(false).show(ctx, |ui| {
+        let faction_name = |id: uuid::Uuid| {
+            game_state
+                .simulation
+                .factions
+                .get(&id)
+                .map_or("unknown", |faction| faction.name())
+        };
+
+        egui::ComboBox::from_label("Viewing as")
+            .selected_text(viewing_as.map_or("nobody", faction_name))
+            .show_ui(ui, |ui| {
+                for faction in game_state.simulation.factions.values() {
+                    ui.selectable_value(&mut *viewing_as, Some(faction.id()), faction.name());
+                }
+            });
+
+        let Some(knowledge) = viewing_as.and_then(|id| game_state.knowledge_of(id)) else {
+            ui.label("No intel yet");
+            return;
+        };
+        ui.label(format!(
+            "Surveyed bodies: {} | Reports in transit: {}",
+            knowledge.surveyed_bodies().len(),
+            knowledge.in_transit().len()
+        ));
+        ui.separator();
+
+        let today = game_state.get_game_date();
+        let mut contacts: Vec<_> = knowledge.contacts().collect();
+        contacts.sort_by_key(|contact| contact.observed_date);
+        for contact in contacts.into_iter().rev() {
+            ui.label(format!(
+                "{:?} {} ({}) - seen {} days ago",
+                contact.kind,
+                contact.name,
+                contact.owner.map_or("unowned", faction_name),
+                contact.age_days(today)
+            ));
+        }
+    });
+}
+
+fn event_log_system(mut events: EventReader<GameEvent>) {
+    for event in events.read() {
+        info!("[{}] {:?}", event.date, event.kind);
+    }
+}
+
 // Define colors
 const SUN_COLOR: Color = Color::YELLOW;
 const EARTH_COLOR: Color = Color::rgb(0.3, 0.3, 1.0);
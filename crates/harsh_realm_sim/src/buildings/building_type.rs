@@ -1,16 +1,47 @@
-use std::fmt::{Debug, Formatter};
+use std::collections::HashMap;
 use serde::{Deserialize, Serialize};
 
-#[derive(Clone,Serialize,Deserialize,Eq,Hash,PartialEq)]
+use crate::resources::resource_type::ResourceType;
+use crate::resources::storage::StorageType;
+
+#[derive(Debug,Clone,Serialize,Deserialize,Eq,Hash,PartialEq)]
 pub enum BuildingType {
     Mine,
     Refinery,
     Factory,
     Laboratory,
+    Warehouse,
+    TankFarm,
+    GasHolder,
+    PressureStore,
+    CryoStore,
 }
 
-impl Debug for BuildingType {
-    fn fmt(&self, _f: &mut Formatter<'_>) -> std::fmt::Result {
-        todo!()
+impl BuildingType {
+    /// Materials consumed to put up one building of this type
+    pub fn cost(&self) -> HashMap<ResourceType, u64> {
+        let (metal, non_metal) = match self {
+            BuildingType::Mine => (200, 100),
+            BuildingType::Refinery => (300, 150),
+            BuildingType::Factory => (400, 200),
+            BuildingType::Laboratory => (150, 250),
+            BuildingType::Warehouse => (150, 50),
+            BuildingType::TankFarm | BuildingType::GasHolder => (250, 50),
+            BuildingType::PressureStore => (300, 100),
+            BuildingType::CryoStore => (300, 200),
+        };
+        HashMap::from([(ResourceType::Metal, metal), (ResourceType::NonMetal, non_metal)])
+    }
+
+    /// The kind of storage the building provides, if it is a storage building
+    pub fn storage(&self) -> Option<StorageType> {
+        match self {
+            BuildingType::Warehouse => Some(StorageType::Bulk),
+            BuildingType::TankFarm => Some(StorageType::Liquid),
+            BuildingType::GasHolder => Some(StorageType::Gas),
+            BuildingType::PressureStore => Some(StorageType::Pressurized),
+            BuildingType::CryoStore => Some(StorageType::Cryogenic),
+            BuildingType::Mine | BuildingType::Refinery | BuildingType::Factory | BuildingType::Laboratory => None,
+        }
     }
 }
@@ -0,0 +1,28 @@
+use bevy::prelude::*;
+
+use crate::events::event_bus::{EventFilter, SubscriptionId};
+use crate::events::game_event::GameEvent;
+use crate::game_state::GameState;
+
+/// Forwards the simulation's domain events into Bevy as [`GameEvent`] events
+pub struct SimEventsPlugin;
+
+impl Plugin for SimEventsPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<GameEvent>()
+            .add_systems(Update, forward_sim_events);
+    }
+}
+
+fn forward_sim_events(
+    game_state: Option<ResMut<GameState>>,
+    mut subscription: Local<Option<SubscriptionId>>,
+    mut writer: EventWriter<GameEvent>,
+) {
+    let Some(mut game_state) = game_state else {
+        return;
+    };
+    let bus = &mut game_state.simulation.events;
+    let id = *subscription.get_or_insert_with(|| bus.subscribe(EventFilter::all()));
+    writer.send_batch(bus.poll(id));
+}
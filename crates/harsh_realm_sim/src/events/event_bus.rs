@@ -0,0 +1,239 @@
+use std::collections::{HashMap, HashSet};
+use chrono::NaiveDate;
+use uuid::Uuid;
+
+use crate::events::game_event::{EventCategory, GameEvent, GameEventKind};
+
+/// Selects which events a reader is interested in
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct EventFilter {
+    /// Only these categories; `None` accepts every category
+    pub categories: Option<HashSet<EventCategory>>,
+    /// Only events concerning this faction
+    pub faction: Option<Uuid>,
+}
+
+impl EventFilter {
+    /// A filter that accepts every event
+    pub fn all() -> Self {
+        Self::default()
+    }
+
+    pub fn categories(categories: impl IntoIterator<Item = EventCategory>) -> Self {
+        Self {
+            categories: Some(categories.into_iter().collect()),
+            faction: None,
+        }
+    }
+
+    pub fn for_faction(mut self, faction: Uuid) -> Self {
+        self.faction = Some(faction);
+        self
+    }
+
+    pub fn matches(&self, event: &GameEvent) -> bool {
+        self.categories.as_ref().is_none_or(|c| c.contains(&event.category()))
+            && self.faction.is_none_or(|f| event.faction == Some(f))
+    }
+}
+
+/// Handle for a subscription created with [`EventBus::subscribe`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SubscriptionId(u64);
+
+#[derive(Debug, Clone)]
+struct Subscription {
+    filter: EventFilter,
+    /// Sequence number of the next event this subscriber has not seen
+    cursor: u64,
+}
+
+/// Per-turn buffer of domain events emitted by the simulation.
+///
+/// Events of the turn in progress and of the previous turn are kept for anyone
+/// to read; older events are held only until every subscriber they match has
+/// polled them, so subscribers may skip turns without missing anything.
+#[derive(Debug, Clone, Default)]
+pub struct EventBus {
+    current_turn: Vec<GameEvent>,
+    previous_turn: Vec<GameEvent>,
+    /// Events from before the previous turn that a subscriber has yet to poll
+    unread: Vec<GameEvent>,
+    next_sequence: u64,
+    turn: u64,
+    date: NaiveDate,
+    subscriptions: HashMap<SubscriptionId, Subscription>,
+    next_subscription: u64,
+}
+
+impl EventBus {
+    /// Sets the turn and date stamped on subsequently emitted events
+    pub fn set_clock(&mut self, turn: u64, date: NaiveDate) {
+        self.turn = turn;
+        self.date = date;
+    }
+
+    /// Emits an event that does not concern a particular faction
+    pub fn emit(&mut self, kind: GameEventKind) {
+        self.emit_for(None, kind);
+    }
+
+    /// Emits an event concerning `faction`
+    pub fn emit_for(&mut self, faction: Option<Uuid>, kind: GameEventKind) {
+        self.current_turn.push(GameEvent {
+            sequence: self.next_sequence,
+            turn: self.turn,
+            date: self.date,
+            faction,
+            kind,
+        });
+        self.next_sequence += 1;
+    }
+
+    /// Closes the current turn's buffer; the turn before it is kept only for subscribers yet to poll it
+    pub fn end_turn(&mut self) {
+        let previous_turn = std::mem::replace(&mut self.previous_turn, std::mem::take(&mut self.current_turn));
+        self.unread.extend(previous_turn);
+        self.drop_read();
+    }
+
+    /// Forgets old events that no subscriber is still waiting for
+    fn drop_read(&mut self) {
+        let subscriptions = &self.subscriptions;
+        self.unread.retain(|event| {
+            subscriptions
+                .values()
+                .any(|subscription| event.sequence >= subscription.cursor && subscription.filter.matches(event))
+        });
+    }
+
+    /// Drops all buffered events, keeping subscriptions.
+    ///
+    /// Used when the game is rewound and the buffered events no longer happened.
+    pub fn clear(&mut self) {
+        self.current_turn.clear();
+        self.previous_turn.clear();
+        self.unread.clear();
+        for subscription in self.subscriptions.values_mut() {
+            subscription.cursor = self.next_sequence;
+        }
+    }
+
+    /// Sequence number the next emitted event will get
+    pub fn next_sequence(&self) -> u64 {
+        self.next_sequence
+    }
+
+    /// Events emitted so far in the turn in progress
+    pub fn current_turn(&self) -> &[GameEvent] {
+        &self.current_turn
+    }
+
+    /// Events emitted during the last completed turn
+    pub fn previous_turn(&self) -> &[GameEvent] {
+        &self.previous_turn
+    }
+
+    /// Buffered events from the last completed and current turns matching `filter`
+    pub fn events<'a>(&'a self, filter: &'a EventFilter) -> impl Iterator<Item = &'a GameEvent> + 'a {
+        self.previous_turn
+            .iter()
+            .chain(&self.current_turn)
+            .filter(move |event| filter.matches(event))
+    }
+
+    /// Registers a reader that will receive events emitted from now on
+    pub fn subscribe(&mut self, filter: EventFilter) -> SubscriptionId {
+        let id = SubscriptionId(self.next_subscription);
+        self.next_subscription += 1;
+        self.subscriptions.insert(id, Subscription { filter, cursor: self.next_sequence });
+        id
+    }
+
+    pub fn unsubscribe(&mut self, id: SubscriptionId) {
+        self.subscriptions.remove(&id);
+        self.drop_read();
+    }
+
+    /// Returns the matching events the subscriber has not seen yet.
+    ///
+    /// Unknown subscriptions receive nothing.
+    pub fn poll(&mut self, id: SubscriptionId) -> Vec<GameEvent> {
+        let Some(subscription) = self.subscriptions.get_mut(&id) else {
+            return Vec::new();
+        };
+        let cursor = subscription.cursor;
+        subscription.cursor = self.next_sequence;
+
+        let events = self
+            .unread
+            .iter()
+            .chain(&self.previous_turn)
+            .chain(&self.current_turn)
+            .filter(|event| event.sequence >= cursor && subscription.filter.matches(event))
+            .cloned()
+            .collect();
+        self.drop_read();
+        events
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn war() -> GameEventKind {
+        GameEventKind::WarDeclared { aggressor: Uuid::new_v4(), target: Uuid::new_v4() }
+    }
+
+    #[test]
+    fn subscribers_only_see_new_matching_events() {
+        let mut bus = EventBus::default();
+        bus.emit(GameEventKind::TurnEnded { turn: 0 });
+
+        let all = bus.subscribe(EventFilter::all());
+        let diplomacy = bus.subscribe(EventFilter::categories([EventCategory::Diplomacy]));
+        bus.emit(war());
+        bus.emit(GameEventKind::TurnEnded { turn: 1 });
+
+        assert_eq!(bus.poll(all).len(), 2);
+        assert_eq!(bus.poll(diplomacy).len(), 1);
+        assert!(bus.poll(all).is_empty());
+    }
+
+    #[test]
+    fn buffers_roll_over_each_turn() {
+        let mut bus = EventBus::default();
+        let id = bus.subscribe(EventFilter::all());
+        bus.emit(war());
+        bus.end_turn();
+        bus.emit(war());
+        assert_eq!(bus.previous_turn().len(), 1);
+        assert_eq!(bus.current_turn().len(), 1);
+
+        // Events more than a turn old leave the buffers but wait for the subscriber
+        bus.end_turn();
+        bus.end_turn();
+        assert!(bus.previous_turn().is_empty());
+        assert_eq!(bus.poll(id).len(), 2);
+        assert!(bus.unread.is_empty());
+
+        // With nobody left to read them, old events are dropped
+        bus.emit(war());
+        bus.unsubscribe(id);
+        bus.end_turn();
+        bus.end_turn();
+        assert!(bus.unread.is_empty());
+    }
+
+    #[test]
+    fn filters_by_faction() {
+        let mut bus = EventBus::default();
+        let faction = Uuid::new_v4();
+        bus.emit_for(Some(faction), war());
+        bus.emit(war());
+
+        let filter = EventFilter::all().for_faction(faction);
+        assert_eq!(bus.events(&filter).count(), 1);
+    }
+}
@@ -0,0 +1,101 @@
+#[cfg(feature = "bevy-integration")]
+use bevy::prelude::Event;
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::buildings::building_type::BuildingType;
+use crate::maps::location::Location;
+use crate::resources::resource_type::ResourceType;
+use crate::units::unit_type::UnitType;
+use crate::victory::victory_condition::VictoryCondition;
+
+/// Broad groups of events, used for filtering subscriptions
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum EventCategory {
+    Construction,
+    Logistics,
+    Population,
+    Astronomy,
+    Diplomacy,
+    Intelligence,
+    Game,
+}
+
+/// Something that happened in the simulation
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum GameEventKind {
+    BuildingCompleted { structure_id: Uuid, building: BuildingType },
+    UnitTrained { structure_id: Uuid, unit: UnitType },
+    /// A new ship left the structure that built it
+    SpacecraftLaunched { structure_id: Uuid, spacecraft_id: Uuid },
+    ShipArrived { spacecraft_id: Uuid, location: Location },
+    /// A ship could not reach its destination and its orders were cancelled
+    CourseBlocked { spacecraft_id: Uuid },
+    /// A fleet got where its order sent it; `location` is where its flagship ended up
+    FleetArrived { fleet_id: Uuid, location: Location },
+    /// A fleet could not carry out its order and now holds where it is
+    FleetOrderCancelled { fleet_id: Uuid },
+    DepositDepleted { structure_id: Uuid, resource: ResourceType },
+    /// A structure had nowhere to keep `amount` of `resource` and dumped it
+    StockpileOverflowed { structure_id: Uuid, resource: ResourceType, amount: u64 },
+    /// A structure could not feed `shortfall` of its people
+    PopulationStarving { structure_id: Uuid, shortfall: u64 },
+    /// A structure ran `shortfall` Energy short of what its life support needed
+    LifeSupportStrained { structure_id: Uuid, shortfall: u64 },
+    /// A settlement's shielding let through `dose_msv_per_day`, over the dose limit, and `deaths` died of it
+    RadiationSickness { structure_id: Uuid, dose_msv_per_day: f64, deaths: u32 },
+    /// An installation ran `shortfall` Energy short of what it needed to work flat out
+    PowerShortage { structure_id: Uuid, shortfall: u64 },
+    /// Two planets lined up as seen from the Sun
+    Conjunction { first: String, second: String, separation_degrees: f64 },
+    WarDeclared { aggressor: Uuid, target: Uuid },
+    /// A faction learned of a structure it did not know about
+    ContactReported { structure_id: Uuid },
+    TurnEnded { turn: u64 },
+    GameOver { winner: Option<Uuid>, reason: VictoryCondition },
+}
+
+impl GameEventKind {
+    pub fn category(&self) -> EventCategory {
+        match self {
+            GameEventKind::BuildingCompleted { .. }
+            | GameEventKind::UnitTrained { .. }
+            | GameEventKind::SpacecraftLaunched { .. } => EventCategory::Construction,
+            GameEventKind::ShipArrived { .. }
+            | GameEventKind::CourseBlocked { .. }
+            | GameEventKind::FleetArrived { .. }
+            | GameEventKind::FleetOrderCancelled { .. }
+            | GameEventKind::DepositDepleted { .. }
+            | GameEventKind::StockpileOverflowed { .. }
+            | GameEventKind::PowerShortage { .. } => EventCategory::Logistics,
+            GameEventKind::PopulationStarving { .. }
+            | GameEventKind::LifeSupportStrained { .. }
+            | GameEventKind::RadiationSickness { .. } => EventCategory::Population,
+            GameEventKind::Conjunction { .. } => EventCategory::Astronomy,
+            GameEventKind::WarDeclared { .. } => EventCategory::Diplomacy,
+            GameEventKind::ContactReported { .. } => EventCategory::Intelligence,
+            GameEventKind::TurnEnded { .. } | GameEventKind::GameOver { .. } => EventCategory::Game,
+        }
+    }
+}
+
+/// A domain event stamped with when it happened
+#[cfg_attr(feature = "bevy-integration", derive(Event))]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GameEvent {
+    /// Position in the stream of all events emitted this game
+    pub sequence: u64,
+    /// The turn in progress when the event happened
+    pub turn: u64,
+    pub date: NaiveDate,
+    /// The faction the event concerns, if any
+    pub faction: Option<Uuid>,
+    pub kind: GameEventKind,
+}
+
+impl GameEvent {
+    pub fn category(&self) -> EventCategory {
+        self.kind.category()
+    }
+}
@@ -1,2 +1,6 @@
 pub mod narrative_event;
-pub mod event_generator;
\ No newline at end of file
+pub mod event_generator;
+pub mod event_bus;
+pub mod game_event;
+#[cfg(feature = "bevy-integration")]
+pub mod bevy_bridge;
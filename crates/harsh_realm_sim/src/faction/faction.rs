@@ -1,3 +1,4 @@
+use std::collections::BTreeSet;
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
@@ -5,5 +6,54 @@ use uuid::Uuid;
 pub struct Faction {
     id: Uuid,
     name: String,
+    #[serde(default)]
+    treasury: f64,
+    #[serde(default)]
+    technologies: BTreeSet<String>,
 }
 
+impl Faction {
+    pub fn new(name: String) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            name,
+            treasury: 0.0,
+            technologies: BTreeSet::new(),
+        }
+    }
+
+    pub fn with_treasury(mut self, treasury: f64) -> Self {
+        self.treasury = treasury;
+        self
+    }
+
+    pub fn id(&self) -> Uuid {
+        self.id
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn treasury(&self) -> f64 {
+        self.treasury
+    }
+
+    /// Adds `amount` (negative to spend) to the treasury
+    pub fn adjust_treasury(&mut self, amount: f64) {
+        self.treasury += amount;
+    }
+
+    /// Marks a technology as researched; returns false if it already was
+    pub fn research(&mut self, technology: String) -> bool {
+        self.technologies.insert(technology)
+    }
+
+    pub fn has_technology(&self, technology: &str) -> bool {
+        self.technologies.contains(technology)
+    }
+
+    pub fn technologies(&self) -> &BTreeSet<String> {
+        &self.technologies
+    }
+}
@@ -0,0 +1,196 @@
+use std::collections::HashMap;
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::maps::location::Location;
+use crate::structures::structure_type::{StructureKind, StructureType};
+
+/// What a faction knows about a structure, as of when it was last observed
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct KnownStructure {
+    pub structure_id: Uuid,
+    pub name: String,
+    pub kind: StructureKind,
+    pub owner: Option<Uuid>,
+    /// Where the structure was when observed; it may have moved since
+    pub location: Location,
+    pub observed_turn: u64,
+    pub observed_date: NaiveDate,
+    /// When the report reached the faction, after communication delay
+    pub received_date: NaiveDate,
+}
+
+impl KnownStructure {
+    /// Builds a report of `structure` as observed on `date`, arriving on `received_date`
+    pub fn observe(structure: &StructureType, turn: u64, date: NaiveDate, received_date: NaiveDate) -> Self {
+        Self {
+            structure_id: structure.id(),
+            name: structure.name().to_string(),
+            kind: structure.kind(),
+            owner: structure.owner(),
+            location: structure.location().clone(),
+            observed_turn: turn,
+            observed_date: date,
+            received_date,
+        }
+    }
+
+    /// Days between the observation and `today`
+    pub fn age_days(&self, today: NaiveDate) -> i64 {
+        (today - self.observed_date).num_days()
+    }
+}
+
+/// A celestial body the faction has had a presence at
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BodySurvey {
+    pub body_id: Uuid,
+    pub first_surveyed: NaiveDate,
+    pub last_surveyed: NaiveDate,
+}
+
+/// One faction's view of the world.
+///
+/// The AI and the UI should read this rather than the full game state, which
+/// includes everything the faction has not seen.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct FactionKnowledge {
+    pub faction_id: Uuid,
+    structures: HashMap<Uuid, KnownStructure>,
+    surveyed_bodies: HashMap<Uuid, BodySurvey>,
+    /// Reports still travelling back to the faction
+    in_transit: Vec<KnownStructure>,
+}
+
+impl FactionKnowledge {
+    pub fn new(faction_id: Uuid) -> Self {
+        Self {
+            faction_id,
+            ..Default::default()
+        }
+    }
+
+    /// Queues a report, or records it at once if it has already arrived by `today`.
+    ///
+    /// Returns true if the report introduced a structure the faction did not know about.
+    pub fn report(&mut self, report: KnownStructure, today: NaiveDate) -> bool {
+        if report.received_date > today {
+            self.in_transit.push(report);
+            false
+        } else {
+            self.record(report)
+        }
+    }
+
+    /// Records every report that has arrived by `today` and returns the ids of newly
+    /// discovered structures
+    pub fn deliver(&mut self, today: NaiveDate) -> Vec<Uuid> {
+        let (arrived, in_transit): (Vec<_>, Vec<_>) =
+            std::mem::take(&mut self.in_transit).into_iter().partition(|r| r.received_date <= today);
+        self.in_transit = in_transit;
+
+        arrived
+            .into_iter()
+            .filter_map(|report| {
+                let id = report.structure_id;
+                self.record(report).then_some(id)
+            })
+            .collect()
+    }
+
+    /// Keeps the report unless a fresher observation is already known
+    fn record(&mut self, report: KnownStructure) -> bool {
+        match self.structures.get(&report.structure_id) {
+            Some(known) if known.observed_date > report.observed_date => false,
+            Some(_) => {
+                self.structures.insert(report.structure_id, report);
+                false
+            }
+            None => {
+                self.structures.insert(report.structure_id, report);
+                true
+            }
+        }
+    }
+
+    /// Drops everything known about a structure
+    pub fn forget(&mut self, structure_id: Uuid) {
+        self.structures.remove(&structure_id);
+        self.in_transit.retain(|r| r.structure_id != structure_id);
+    }
+
+    /// Marks a body as surveyed on `date`
+    pub fn survey(&mut self, body_id: Uuid, date: NaiveDate) {
+        self.surveyed_bodies
+            .entry(body_id)
+            .and_modify(|survey| survey.last_surveyed = date)
+            .or_insert(BodySurvey { body_id, first_surveyed: date, last_surveyed: date });
+    }
+
+    pub fn known_structures(&self) -> impl Iterator<Item = &KnownStructure> {
+        self.structures.values()
+    }
+
+    pub fn get_structure(&self, structure_id: Uuid) -> Option<&KnownStructure> {
+        self.structures.get(&structure_id)
+    }
+
+    /// Known structures belonging to anyone but this faction
+    pub fn contacts(&self) -> impl Iterator<Item = &KnownStructure> {
+        let faction_id = self.faction_id;
+        self.structures.values().filter(move |s| s.owner != Some(faction_id))
+    }
+
+    pub fn surveyed_bodies(&self) -> &HashMap<Uuid, BodySurvey> {
+        &self.surveyed_bodies
+    }
+
+    pub fn is_surveyed(&self, body_id: Uuid) -> bool {
+        self.surveyed_bodies.contains_key(&body_id)
+    }
+
+    /// Reports observed but not yet received
+    pub fn in_transit(&self) -> &[KnownStructure] {
+        &self.in_transit
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::structures::settlement::Settlement;
+
+    fn date(day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2070, 1, day).unwrap()
+    }
+
+    #[test]
+    fn test_reports_wait_for_their_arrival_date() {
+        let settlement = StructureType::Settlement(Settlement::new(
+            "Outpost".to_string(),
+            Location::DeepSpace { x: 0.0, y: 0.0 },
+        ));
+        let mut knowledge = FactionKnowledge::new(Uuid::new_v4());
+
+        assert!(!knowledge.report(KnownStructure::observe(&settlement, 1, date(1), date(3)), date(1)));
+        assert!(knowledge.get_structure(settlement.id()).is_none());
+        assert!(knowledge.deliver(date(2)).is_empty());
+        assert_eq!(knowledge.deliver(date(3)), vec![settlement.id()]);
+        assert_eq!(knowledge.contacts().count(), 1);
+    }
+
+    #[test]
+    fn test_older_reports_do_not_overwrite_newer_ones() {
+        let settlement = StructureType::Settlement(Settlement::new(
+            "Outpost".to_string(),
+            Location::DeepSpace { x: 0.0, y: 0.0 },
+        ));
+        let mut knowledge = FactionKnowledge::new(Uuid::new_v4());
+
+        knowledge.report(KnownStructure::observe(&settlement, 2, date(5), date(5)), date(5));
+        knowledge.report(KnownStructure::observe(&settlement, 1, date(2), date(6)), date(6));
+        assert_eq!(knowledge.get_structure(settlement.id()).unwrap().observed_date, date(5));
+        assert_eq!(knowledge.get_structure(settlement.id()).unwrap().age_days(date(9)), 4);
+    }
+}
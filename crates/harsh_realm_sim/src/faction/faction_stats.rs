@@ -0,0 +1,109 @@
+use std::collections::{HashMap, HashSet};
+use uuid::Uuid;
+
+use crate::maps::location::Location;
+use crate::population::person_type::PersonType;
+use crate::resources::resource_type::ResourceType;
+use crate::simulation::simulation::Simulation;
+use crate::structures::spacecraft::SpacecraftModuleType;
+use crate::structures::structure_type::StructureType;
+
+/// Aggregate totals for one faction, gathered from the structures it owns
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct FactionStats {
+    pub treasury: f64,
+    pub population: HashMap<PersonType, u64>,
+    pub stockpiles: HashMap<ResourceType, u64>,
+    pub settlements: u32,
+    pub installations: u32,
+    pub spacecraft: u32,
+    /// Military modules across the faction's spacecraft
+    pub fleet_strength: u32,
+    /// Bodies the faction has a structure on or in orbit of
+    pub present_on_bodies: HashSet<Uuid>,
+}
+
+impl FactionStats {
+    /// Gathers stats for every faction in the simulation
+    pub fn collect(simulation: &Simulation) -> HashMap<Uuid, FactionStats> {
+        let mut stats: HashMap<Uuid, FactionStats> = simulation
+            .factions
+            .values()
+            .map(|faction| {
+                let stats = FactionStats {
+                    treasury: faction.treasury(),
+                    ..Default::default()
+                };
+                (faction.id(), stats)
+            })
+            .collect();
+
+        for structure in simulation.structures.values() {
+            let Some(faction_stats) = structure.owner().and_then(|owner| stats.get_mut(&owner)) else {
+                continue;
+            };
+
+            match structure {
+                StructureType::Settlement(settlement) => {
+                    faction_stats.settlements += 1;
+                    faction_stats.add_population(settlement.population());
+                    for (resource, amount) in settlement.resources() {
+                        *faction_stats.stockpiles.entry(resource.clone()).or_default() += amount;
+                    }
+                }
+                StructureType::Installation(installation) => {
+                    faction_stats.installations += 1;
+                    for (resource, amount) in installation.resources() {
+                        *faction_stats.stockpiles.entry(resource.clone()).or_default() += amount;
+                    }
+                }
+                StructureType::Spacecraft(spacecraft) => {
+                    faction_stats.spacecraft += 1;
+                    faction_stats.fleet_strength += spacecraft
+                        .modules()
+                        .get(&SpacecraftModuleType::Military)
+                        .copied()
+                        .unwrap_or(0);
+                    if let Some(population) = spacecraft.population() {
+                        faction_stats.add_population(population);
+                    }
+                    for (resource, amount) in spacecraft.cargo() {
+                        *faction_stats.stockpiles.entry(resource.clone()).or_default() += *amount as u64;
+                    }
+                }
+            }
+
+            match structure.location() {
+                Location::Surface { body_id, .. } | Location::Orbit { body_id, .. } => {
+                    faction_stats.present_on_bodies.insert(*body_id);
+                }
+                Location::DeepSpace { .. } | Location::Docked { .. } => {}
+            }
+        }
+
+        stats
+    }
+
+    fn add_population(&mut self, population: &HashMap<PersonType, u32>) {
+        for (person_type, count) in population {
+            *self.population.entry(person_type.clone()).or_default() += *count as u64;
+        }
+    }
+
+    pub fn total_population(&self) -> u64 {
+        self.population.values().sum()
+    }
+
+    pub fn total_stockpile(&self) -> u64 {
+        self.stockpiles.values().sum()
+    }
+
+    pub fn structure_count(&self) -> u32 {
+        self.settlements + self.installations + self.spacecraft
+    }
+
+    /// Treasury plus every stockpiled resource unit, used to compare economies
+    pub fn wealth(&self) -> f64 {
+        self.treasury + self.total_stockpile() as f64
+    }
+}
@@ -1,3 +1,8 @@
+#[allow(clippy::module_inception)]
 pub mod faction;
 pub mod agent;
-mod faction_ai;
\ No newline at end of file
+pub mod faction_knowledge;
+pub mod faction_stats;
+pub mod sensors;
+pub mod territory;
+mod faction_ai;
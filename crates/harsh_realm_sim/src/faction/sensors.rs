@@ -0,0 +1,218 @@
+// src/faction/sensors.rs
+// Sensor sweep that feeds each faction's knowledge model.  A faction observes
+// every structure sharing a site (a body's surface and orbit, or a host
+// structure) with one of its own, and reports travel home at light speed.
+// Surveying a body also reveals its deposits, and those under the faction's
+// own structures on its surface, to the faction.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use chrono::{Duration, NaiveDate};
+use hex2d::Coordinate as HexCoord;
+use uuid::Uuid;
+
+use crate::faction::faction_knowledge::{FactionKnowledge, KnownStructure};
+use crate::maps::location::Location;
+use crate::resources::deposit::{Deposit, DepositStatus};
+use crate::simulation::simulation::Simulation;
+use crate::structures::structure_type::StructureType;
+use crate::universe::solar_system_manager::SolarSystemManager;
+
+const SPEED_OF_LIGHT_KM_PER_S: f64 = 299_792.458;
+const SECONDS_PER_DAY: f64 = 86_400.0;
+
+/// Where a structure can see and be seen from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum Site {
+    /// On or in orbit of a celestial body
+    Body(Uuid),
+    /// Docked to, or being, this structure
+    Structure(Uuid),
+}
+
+/// Observes the world for every faction and delivers reports that have arrived.
+///
+/// Returns `(faction_id, structure_id)` for each structure a faction learned of
+/// for the first time.
+pub fn sweep(
+    knowledge: &mut HashMap<Uuid, Arc<FactionKnowledge>>,
+    simulation: &Simulation,
+    solar_system: &mut SolarSystemManager,
+    turn: u64,
+    date: NaiveDate,
+) -> Vec<(Uuid, Uuid)> {
+    let body_positions: HashMap<Uuid, (f64, f64)> = solar_system
+        .get_all_bodies()
+        .values()
+        .map(|body| {
+            let position = solar_system.body_position(body, date);
+            (body.id, (position.x, position.y))
+        })
+        .collect();
+
+    // Everything present at each site
+    let mut occupants: HashMap<Site, Vec<&StructureType>> = HashMap::new();
+    for structure in simulation.structures.values() {
+        for site in sites(structure, simulation) {
+            occupants.entry(site).or_default().push(structure);
+        }
+    }
+
+    let mut discoveries = Vec::new();
+    for faction_id in simulation.factions.keys() {
+        let faction_knowledge = Arc::make_mut(
+            knowledge
+                .entry(*faction_id)
+                .or_insert_with(|| Arc::new(FactionKnowledge::new(*faction_id))),
+        );
+        let home = home_body(*faction_id, simulation).and_then(|id| body_positions.get(&id));
+
+        let own: Vec<&StructureType> = simulation
+            .structures
+            .values()
+            .filter(|s| s.owner() == Some(*faction_id))
+            .collect();
+
+        // Own structures that no longer exist are known to be gone
+        let lost: Vec<Uuid> = faction_knowledge
+            .known_structures()
+            .filter(|s| s.owner == Some(*faction_id) && !simulation.structures.contains_key(&s.structure_id))
+            .map(|s| s.structure_id)
+            .collect();
+        for structure_id in lost {
+            faction_knowledge.forget(structure_id);
+        }
+
+        let mut observed_sites = Vec::new();
+        let mut own_hexes: HashMap<Uuid, Vec<HexCoord>> = HashMap::new();
+        for structure in &own {
+            if let Location::Surface { body_id, hex_coord } = structure.location() {
+                own_hexes.entry(*body_id).or_default().push(*hex_coord);
+            }
+            // A faction always knows where its own structures are
+            faction_knowledge.report(KnownStructure::observe(structure, turn, date, date), date);
+            for site in sites(structure, simulation) {
+                if !observed_sites.contains(&site) {
+                    observed_sites.push(site);
+                }
+            }
+        }
+
+        for site in observed_sites {
+            let delay_days = match (site, home) {
+                (Site::Body(body_id), Some(home)) => {
+                    faction_knowledge.survey(body_id, date);
+                    survey_deposits(solar_system, *faction_id, body_id, own_hexes.get(&body_id));
+                    body_positions.get(&body_id).map_or(0.0, |position| light_delay_days(*position, *home))
+                }
+                (Site::Body(body_id), None) => {
+                    faction_knowledge.survey(body_id, date);
+                    survey_deposits(solar_system, *faction_id, body_id, own_hexes.get(&body_id));
+                    0.0
+                }
+                (Site::Structure(_), _) => 0.0,
+            };
+            // Only the delivery date is rounded, up to the day the light gets home
+            let received = date + Duration::days(delay_days.ceil() as i64);
+
+            for structure in occupants.get(&site).into_iter().flatten() {
+                if structure.owner() == Some(*faction_id) {
+                    continue;
+                }
+                if faction_knowledge.report(KnownStructure::observe(structure, turn, date, received), date) {
+                    discoveries.push((*faction_id, structure.id()));
+                }
+            }
+        }
+
+        discoveries.extend(faction_knowledge.deliver(date).into_iter().map(|id| (*faction_id, id)));
+    }
+    discoveries
+}
+
+/// Sites a structure occupies: its body or host, plus itself as a host for dockers
+fn sites(structure: &StructureType, simulation: &Simulation) -> Vec<Site> {
+    let mut sites = vec![Site::Structure(structure.id())];
+    match structure.location() {
+        Location::Surface { body_id, .. } | Location::Orbit { body_id, .. } => sites.push(Site::Body(*body_id)),
+        Location::Docked { structure_id } => {
+            sites.push(Site::Structure(*structure_id));
+            if let Some(host) = simulation.structures.get(structure_id) {
+                if let Location::Surface { body_id, .. } | Location::Orbit { body_id, .. } = host.location() {
+                    sites.push(Site::Body(*body_id));
+                }
+            }
+        }
+        Location::DeepSpace { .. } => {}
+    }
+    sites
+}
+
+/// Reveals a body's own deposits and those on `hexes` of its surface to `faction_id`.  Only
+/// what is still hidden is touched, so a surface map a snapshot shares is not copied for nothing.
+fn survey_deposits(solar_system: &mut SolarSystemManager, faction_id: Uuid, body_id: Uuid, hexes: Option<&Vec<HexCoord>>) {
+    let hidden = |deposits: &[Deposit]| deposits.iter().any(|d| d.status(faction_id) == DepositStatus::Hidden);
+    let Some(body) = solar_system.get_body_by_id_mut(body_id) else {
+        return;
+    };
+    for deposit in &mut body.deposits {
+        deposit.survey(faction_id);
+    }
+
+    let hexes: Vec<HexCoord> = match (body.surface_map(), hexes) {
+        (Some(map), Some(hexes)) => {
+            hexes.iter().copied().filter(|&hex| map.tile(hex).is_some_and(|tile| hidden(&tile.deposits))).collect()
+        }
+        _ => return,
+    };
+    if hexes.is_empty() {
+        return;
+    }
+    let Some(map) = body.surface_map_mut() else {
+        return;
+    };
+    for hex in hexes {
+        if let Some(tile) = map.tile_mut(hex) {
+            for deposit in &mut tile.deposits {
+                deposit.survey(faction_id);
+            }
+        }
+    }
+}
+
+/// The body of the faction's most populous settlement, where reports are sent
+fn home_body(faction_id: Uuid, simulation: &Simulation) -> Option<Uuid> {
+    simulation
+        .structures
+        .values()
+        .filter_map(|structure| match structure {
+            StructureType::Settlement(settlement) if settlement.owner() == Some(faction_id) => {
+                let population: u32 = settlement.population().values().sum();
+                match settlement.location() {
+                    Location::Surface { body_id, .. } | Location::Orbit { body_id, .. } => Some((population, *body_id)),
+                    _ => None,
+                }
+            }
+            _ => None,
+        })
+        .max_by_key(|(population, body_id)| (*population, *body_id))
+        .map(|(_, body_id)| body_id)
+}
+
+/// Light travel time between two heliocentric positions, in days
+fn light_delay_days(from: (f64, f64), to: (f64, f64)) -> f64 {
+    let distance_km = ((from.0 - to.0).powi(2) + (from.1 - to.1).powi(2)).sqrt();
+    distance_km / SPEED_OF_LIGHT_KM_PER_S / SECONDS_PER_DAY
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_light_delay_keeps_part_days() {
+        // One astronomical unit is a little over eight light minutes
+        let delay = light_delay_days((1.496e8, 0.0), (0.0, 0.0));
+        assert!((delay * 24.0 * 60.0 - 8.32).abs() < 0.01, "{} days", delay);
+    }
+}
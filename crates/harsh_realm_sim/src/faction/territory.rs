@@ -0,0 +1,239 @@
+// src/faction/territory.rs
+// Territorial control of surface hexes.  Every settlement and installation
+// projects influence over the hexes around it, fading with distance; each
+// turn every hex within reach of a structure goes to the faction with the
+// most influence there.  When the leader's margin is too thin the hex is
+// contested: its current holder keeps it if still in the running, and
+// nobody may extract from it until the contest is settled.
+
+use std::collections::{BTreeMap, HashMap};
+use hex2d::Coordinate as HexCoord;
+use uuid::Uuid;
+
+use crate::maps::hex_utils;
+use crate::maps::location::Location;
+use crate::simulation::simulation::Simulation;
+use crate::structures::structure_type::StructureType;
+use crate::universe::solar_system_manager::SolarSystemManager;
+
+/// Furthest a settlement's influence can reach, in hexes
+pub const MAX_INFLUENCE_RADIUS: u32 = 6;
+
+/// A hex is contested unless its leader has this many times the runner-up's influence
+pub const CONTEST_RATIO: f64 = 1.25;
+
+/// Extra weight a structure carries on its own hex
+const HOME_HEX_BONUS: f64 = 2.0;
+
+/// The reach of one structure on a body's surface
+#[derive(Debug, Clone, PartialEq)]
+pub struct Influence {
+    pub structure_id: Uuid,
+    pub faction_id: Uuid,
+    pub body_id: Uuid,
+    pub centre: HexCoord,
+    /// Hexes beyond the centre the influence reaches
+    pub radius: u32,
+    /// Influence at the centre, before the home hex bonus
+    pub strength: f64,
+}
+
+impl Influence {
+    /// Influence `steps` hexes from the centre; fades linearly to nothing past the radius
+    pub fn at(&self, steps: u32) -> f64 {
+        if steps > self.radius {
+            return 0.0;
+        }
+        let falloff = (self.radius + 1 - steps) as f64 / (self.radius + 1) as f64;
+        let bonus = if steps == 0 { HOME_HEX_BONUS } else { 1.0 };
+        self.strength * falloff * bonus
+    }
+}
+
+/// Works out the influence an owned surface structure projects; larger and
+/// better built settlements reach further and press harder
+pub fn influence_of(structure: &StructureType) -> Option<Influence> {
+    let faction_id = structure.owner()?;
+    let Location::Surface { body_id, hex_coord } = structure.location() else {
+        return None;
+    };
+    let (radius, strength) = match structure {
+        StructureType::Settlement(settlement) => {
+            let population: u64 = settlement.population().values().map(|n| *n as u64).sum();
+            let buildings: u32 = settlement.buildings().values().sum();
+            let radius = 1 + population.max(1).ilog10() + buildings / 4;
+            (radius.min(MAX_INFLUENCE_RADIUS), 1.0 + (population as f64).sqrt() / 10.0 + buildings as f64)
+        }
+        StructureType::Installation(_) => (1, 1.0),
+        StructureType::Spacecraft(_) => return None,
+    };
+    Some(Influence {
+        structure_id: structure.id(),
+        faction_id,
+        body_id: *body_id,
+        centre: *hex_coord,
+        radius,
+        strength,
+    })
+}
+
+/// What changed when territory was resolved
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TerritoryReport {
+    /// Hexes that changed hands, with their new holder
+    pub changed: Vec<(Uuid, HexCoord, Option<Uuid>)>,
+    /// Hexes left contested
+    pub contested: usize,
+}
+
+/// Reassigns every hex within reach of a structure, generating surface maps from `seed` as needed
+pub fn resolve(solar_system: &mut SolarSystemManager, simulation: &Simulation, seed: u64) -> TerritoryReport {
+    let mut by_body: BTreeMap<Uuid, Vec<Influence>> = BTreeMap::new();
+    for influence in simulation.structures.values().filter_map(influence_of) {
+        by_body.entry(influence.body_id).or_default().push(influence);
+    }
+
+    let mut report = TerritoryReport::default();
+    for (body_id, influences) in by_body {
+        // Work out what changes from the map as it is, so an unchanged map is never copied
+        let Some(map) = solar_system.surface_map(body_id, seed) else {
+            continue;
+        };
+
+        // Total influence of each faction on each hex in reach
+        let mut pressure: HashMap<HexCoord, BTreeMap<Uuid, f64>> = HashMap::new();
+        for influence in &influences {
+            for (coord, steps) in hex_utils::within_radius(map, influence.centre, influence.radius) {
+                *pressure.entry(coord).or_default().entry(influence.faction_id).or_default() += influence.at(steps);
+            }
+        }
+
+        // New (owner, contested) for each hex whose control changes
+        let mut updates: Vec<(HexCoord, Option<Uuid>, bool)> = Vec::new();
+
+        // Hexes nobody reaches any more fall out of control
+        for tile in map.tiles() {
+            if (tile.owner.is_some() || tile.contested) && !pressure.contains_key(&tile.coord) {
+                updates.push((tile.coord, None, false));
+            }
+        }
+
+        for (coord, factions) in pressure {
+            let Some(tile) = map.tile(coord) else {
+                continue;
+            };
+            let mut ranked: Vec<(Uuid, f64)> = factions.into_iter().collect();
+            ranked.sort_by(|a, b| b.1.total_cmp(&a.1).then(a.0.cmp(&b.0)));
+            let (leader, lead) = ranked[0];
+            let runner_up = ranked.get(1).map_or(0.0, |(_, pressure)| *pressure);
+
+            let contested = runner_up > 0.0 && lead < runner_up * CONTEST_RATIO;
+            let holder = if contested {
+                tile.owner.filter(|owner| ranked.iter().any(|(faction, _)| faction == owner))
+            } else {
+                Some(leader)
+            };
+            if contested {
+                report.contested += 1;
+            }
+            if tile.owner != holder || tile.contested != contested {
+                updates.push((coord, holder, contested));
+            }
+        }
+
+        if updates.is_empty() {
+            continue;
+        }
+        let Some(map) = solar_system.surface_map_mut(body_id, seed) else {
+            continue;
+        };
+        updates.sort_by_key(|(coord, _, _)| *coord);
+        for (coord, holder, contested) in updates {
+            let Some(tile) = map.tile_mut(coord) else {
+                continue;
+            };
+            tile.contested = contested;
+            if tile.owner != holder {
+                tile.owner = holder;
+                report.changed.push((body_id, coord, holder));
+            }
+        }
+    }
+    report
+}
+
+/// Returns true if `faction_id` holds `location`'s hex uncontested; off-surface locations
+/// are not subject to territory
+pub fn controls(solar_system: &SolarSystemManager, location: &Location, faction_id: Option<Uuid>) -> bool {
+    let Location::Surface { body_id, hex_coord } = location else {
+        return true;
+    };
+    let tile = solar_system
+        .get_body_by_id(*body_id)
+        .and_then(|body| body.surface_map())
+        .and_then(|map| map.tile(*hex_coord));
+    tile.is_some_and(|tile| !tile.contested && tile.owner.is_some() && tile.owner == faction_id)
+}
+
+/// Returns true if `faction_id` holds most of the claimed hexes on a body; a body nobody has
+/// claimed any of is held by no one
+pub fn holds_body(solar_system: &SolarSystemManager, body_id: Uuid, faction_id: Uuid) -> bool {
+    let Some(map) = solar_system.get_body_by_id(body_id).and_then(|body| body.surface_map()) else {
+        return false;
+    };
+    let (mut claimed, mut held) = (0usize, 0usize);
+    for owner in map.tiles().filter_map(|tile| tile.owner) {
+        claimed += 1;
+        if owner == faction_id {
+            held += 1;
+        }
+    }
+    held * 2 > claimed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::population::person_type::PersonType;
+    use crate::structures::installation::{Installation, InstallationPurpose};
+    use crate::structures::settlement::Settlement;
+
+    fn location() -> Location {
+        Location::Surface { body_id: Uuid::new_v4(), hex_coord: HexCoord::new(3, 2) }
+    }
+
+    #[test]
+    fn test_influence_grows_with_population() {
+        let owner = Uuid::new_v4();
+        let outpost = Settlement::new("Outpost".to_string(), location()).with_owner(owner);
+        let city = Settlement::new("City".to_string(), location())
+            .with_owner(owner)
+            .with_population([(PersonType::Colonist, 20_000)].into());
+        let outpost = influence_of(&StructureType::Settlement(outpost)).unwrap();
+        let city = influence_of(&StructureType::Settlement(city)).unwrap();
+        assert_eq!(outpost.radius, 1);
+        assert_eq!(city.radius, 5);
+        assert!(city.strength > outpost.strength);
+
+        let mine = Installation::new("Mine".to_string(), InstallationPurpose::Mine, location()).with_owner(owner);
+        assert_eq!(influence_of(&StructureType::Installation(mine)).unwrap().radius, 1);
+        let unowned = Installation::new("Derelict".to_string(), InstallationPurpose::Mine, location());
+        assert!(influence_of(&StructureType::Installation(unowned)).is_none());
+    }
+
+    #[test]
+    fn test_influence_fades_with_distance() {
+        let influence = Influence {
+            structure_id: Uuid::new_v4(),
+            faction_id: Uuid::new_v4(),
+            body_id: Uuid::new_v4(),
+            centre: HexCoord::new(0, 0),
+            radius: 3,
+            strength: 4.0,
+        };
+        assert_eq!(influence.at(0), 8.0);
+        assert_eq!(influence.at(1), 3.0);
+        assert_eq!(influence.at(3), 1.0);
+        assert_eq!(influence.at(4), 0.0);
+    }
+}
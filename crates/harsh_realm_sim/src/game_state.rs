@@ -1,25 +1,84 @@
 #[cfg(feature = "bevy-integration")]
 use bevy::prelude::Resource;
+use std::collections::HashMap;
+use std::sync::Arc;
 use serde::{Deserialize, Serialize};
-use chrono::NaiveDate;
+use uuid::Uuid;
+use chrono::{Datelike, NaiveDate};
+use crate::events::event_bus::EventFilter;
+use crate::events::game_event::GameEventKind;
+use crate::faction::faction_knowledge::FactionKnowledge;
+use crate::faction::sensors;
+use crate::faction::territory;
+use crate::maps::environment;
+use crate::maps::surface_map::SurfaceMap;
+use crate::metrics::metrics_recorder::MetricsRecorder;
+use crate::production::production_queue;
+use crate::resources::extraction;
+use crate::resources::storage;
+use crate::structures::fleet::{self, FleetReport};
+use crate::structures::installation_operations;
+use crate::structures::movement;
+use crate::simulation::calendar::{GameCalendar, TurnLength};
+use crate::simulation::real_time::{ClockTick, PauseTrigger, RealTimeClock};
 use crate::simulation::simulation::Simulation;
+use crate::simulation::turn_history::{RewindError, TurnHistory};
+use crate::simulation::turn_processor::{TurnPhase, TurnPipeline};
 use crate::universe::solar_system_manager::SolarSystemManager;
+use crate::victory::victory_condition::VictoryCondition;
+use crate::victory::victory_engine::{self, FactionStanding, GameOutcome};
+
+/// Planets closer than this, in heliocentric longitude, are in conjunction
+const CONJUNCTION_DEGREES: f64 = 1.0;
 
 #[cfg_attr(feature = "bevy-integration", derive(Resource))]
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GameState {
     pub simulation: Simulation,
     pub solar_system: SolarSystemManager,
+    #[serde(default)]
+    pub calendar: GameCalendar,
+    #[serde(default)]
+    pub victory_conditions: Vec<VictoryCondition>,
+    /// Set once a victory condition has decided the game
+    #[serde(default)]
+    pub game_over: Option<GameOutcome>,
+    /// What each faction knows of the world, keyed by faction id; shared with
+    /// turn snapshots until it next changes
+    #[serde(default)]
+    pub knowledge: HashMap<Uuid, Arc<FactionKnowledge>>,
+    /// Per-turn time series for balancing; kept out of turn snapshots
+    #[serde(default)]
+    pub metrics: MetricsRecorder,
+    #[serde(default)]
+    pub pipeline: TurnPipeline,
+    #[serde(default)]
+    pub clock: RealTimeClock,
+    /// Recent turn snapshots for rewind/undo; not persisted in saves
+    #[serde(skip)]
+    pub history: TurnHistory<GameState>,
     // Add other game-specific state here that is not part of the core simulation.
 }
 
 impl GameState {
     /// Creates a new game state with a fresh simulation and solar system.
     pub fn new() -> Self {
-        let start_date = NaiveDate::from_ymd_opt(2070, 1, 1).unwrap();
+        Self::with_calendar(GameCalendar::default())
+    }
+
+    /// Creates a new game state starting on the calendar's start date
+    pub fn with_calendar(calendar: GameCalendar) -> Self {
         Self {
             simulation: Simulation::new(),
-            solar_system: SolarSystemManager::new(start_date),
+            solar_system: SolarSystemManager::new(calendar.start_date),
+            calendar,
+            victory_conditions: Vec::new(),
+            game_over: None,
+            knowledge: HashMap::new(),
+            metrics: MetricsRecorder::default(),
+            pipeline: TurnPipeline::default(),
+            clock: RealTimeClock::default(),
+            history: TurnHistory::default(),
         }
     }
 
@@ -28,19 +87,262 @@ impl GameState {
         self.solar_system.load_from_csv(std::path::Path::new(csv_path))
     }
 
-    /// Updates the game world (called at the beginning of each turn)
-    pub fn update_world(&mut self) {
-        // Update orbital positions (30 days per turn)
-        self.solar_system.update_all_positions(30.0);
+    /// Advances the world by `days` days, running whichever phases are due
+    pub fn advance_time(&mut self, days: u32) {
+        let previous_date = self.get_game_date();
+        self.solar_system.advance_date(days as i64);
+        let date = self.get_game_date();
+        self.simulation.events.set_clock(self.simulation.current_turn + 1, date);
+
+        for (phase, phase_days) in self.pipeline.advance(days, previous_date, date) {
+            self.run_phase(phase, phase_days);
+        }
+    }
+
+    fn run_phase(&mut self, phase: TurnPhase, days: u32) {
+        match phase {
+            TurnPhase::Orbits => {
+                let before = self.solar_system.planetary_conjunctions(CONJUNCTION_DEGREES);
+                self.solar_system.update_positions(days as f64);
+                for (first, second, separation_degrees) in self.solar_system.planetary_conjunctions(CONJUNCTION_DEGREES) {
+                    if !before.iter().any(|(a, b, _)| *a == first && *b == second) {
+                        self.simulation.events.emit(GameEventKind::Conjunction { first, second, separation_degrees });
+                    }
+                }
+            }
+            TurnPhase::Movement => {
+                let dispatched = fleet::dispatch(&mut self.simulation, &self.solar_system);
+                let report = movement::advance(&mut self.simulation, &self.solar_system, days);
+                let settled = fleet::settle(&mut self.simulation, &report);
+                for FleetReport { arrived, blocked } in [dispatched, settled] {
+                    for (fleet_id, location) in arrived {
+                        let owner = self.simulation.fleets.get(&fleet_id).and_then(|f| f.owner());
+                        self.simulation.events.emit_for(owner, GameEventKind::FleetArrived { fleet_id, location });
+                    }
+                    for (fleet_id, _) in blocked {
+                        let owner = self.simulation.fleets.get(&fleet_id).and_then(|f| f.owner());
+                        self.simulation.events.emit_for(owner, GameEventKind::FleetOrderCancelled { fleet_id });
+                    }
+                }
+                for (spacecraft_id, location) in report.arrived {
+                    let owner = self.simulation.structures.get(&spacecraft_id).and_then(|s| s.owner());
+                    self.simulation.events.emit_for(owner, GameEventKind::ShipArrived { spacecraft_id, location });
+                }
+                for (spacecraft_id, _) in report.blocked {
+                    let owner = self.simulation.structures.get(&spacecraft_id).and_then(|s| s.owner());
+                    self.simulation.events.emit_for(owner, GameEventKind::CourseBlocked { spacecraft_id });
+                }
+            }
+            TurnPhase::Economy => {
+                let seed = self.simulation.seed;
+                // Power first, since it sets the pace of everything installations do
+                for (structure_id, shortfall) in installation_operations::power(&mut self.simulation, days) {
+                    let owner = self.simulation.structures.get(&structure_id).and_then(|s| s.owner());
+                    self.simulation.events.emit_for(owner, GameEventKind::PowerShortage { structure_id, shortfall });
+                }
+                for (structure_id, resource) in extraction::extract(&mut self.simulation, &mut self.solar_system, seed, days) {
+                    let owner = self.simulation.structures.get(&structure_id).and_then(|s| s.owner());
+                    self.simulation.events.emit_for(owner, GameEventKind::DepositDepleted { structure_id, resource });
+                }
+                let conditions = environment::apply(&mut self.simulation, &self.solar_system, days);
+                for (structure_id, shortfall) in conditions.strained {
+                    let owner = self.simulation.structures.get(&structure_id).and_then(|s| s.owner());
+                    self.simulation.events.emit_for(owner, GameEventKind::LifeSupportStrained { structure_id, shortfall });
+                }
+                for (structure_id, dose_msv_per_day, deaths) in conditions.irradiated {
+                    let owner = self.simulation.structures.get(&structure_id).and_then(|s| s.owner());
+                    let kind = GameEventKind::RadiationSickness { structure_id, dose_msv_per_day, deaths };
+                    self.simulation.events.emit_for(owner, kind);
+                }
+                installation_operations::operate(&mut self.simulation, days);
+                for (owner, kind) in production_queue::advance(&mut self.simulation, days) {
+                    self.simulation.events.emit_for(owner, kind);
+                }
+                for (structure_id, resource, amount) in storage::apply(&mut self.simulation, days) {
+                    let owner = self.simulation.structures.get(&structure_id).and_then(|s| s.owner());
+                    self.simulation.events.emit_for(owner, GameEventKind::StockpileOverflowed { structure_id, resource, amount });
+                }
+                self.simulation.process_economy(days);
+            }
+            TurnPhase::Population => self.simulation.process_population(days),
+            TurnPhase::Sensors => {
+                let turn = self.simulation.current_turn + 1;
+                let date = self.get_game_date();
+                for (faction_id, structure_id) in
+                    sensors::sweep(&mut self.knowledge, &self.simulation, &mut self.solar_system, turn, date)
+                {
+                    self.simulation
+                        .events
+                        .emit_for(Some(faction_id), GameEventKind::ContactReported { structure_id });
+                }
+            }
+        }
+    }
+
+    /// Changes how much time each turn covers, starting with the next turn
+    pub fn set_turn_length(&mut self, turn_length: TurnLength) {
+        self.calendar.set_turn_length(turn_length);
     }
 
     /// Advances the game state by processing a simulation turn.
+    ///
+    /// If real-time mode has already started the turn, only its remaining days are run.
     pub fn process_turn(&mut self) {
-        // Update world state (orbital positions, etc.)
-        self.update_world();
+        if !self.pipeline.turn_in_progress() {
+            self.begin_turn();
+        }
+
+        let days = self.pipeline.turn_days_remaining();
+        self.advance_time(days);
+        self.pipeline.consume_turn_days(days);
+
+        self.end_turn();
+    }
+
+    /// Feeds `real_seconds` of wall-clock time into real-time mode.
+    ///
+    /// Game time advances one day at a time at the clock's speed; turns end when
+    /// their calendar length has elapsed.  Does nothing while the clock is paused.
+    pub fn tick_real_time(&mut self, real_seconds: f64) -> ClockTick {
+        let mut tick = ClockTick::default();
+
+        for _ in 0..self.clock.accumulate(real_seconds) {
+            if !self.pipeline.turn_in_progress() {
+                self.begin_turn();
+            }
+
+            let previous_date = self.get_game_date();
+            let first_event = self.simulation.events.next_sequence();
+            self.advance_time(1);
+            tick.days_advanced += 1;
+            let date = self.get_game_date();
+
+            let mut triggers = Vec::new();
+            if self.pipeline.consume_turn_days(1) {
+                self.end_turn();
+                tick.turns_completed += 1;
+                triggers.push(PauseTrigger::TurnEnded);
+                if self.is_game_over() {
+                    self.clock.pause();
+                    break;
+                }
+            }
+            if date.month() != previous_date.month() {
+                triggers.push(PauseTrigger::NewMonth);
+            }
+            if date.year() != previous_date.year() {
+                triggers.push(PauseTrigger::NewYear);
+            }
+            triggers.extend(
+                self.simulation
+                    .events
+                    .events(&EventFilter::all())
+                    .filter(|event| event.sequence >= first_event)
+                    .map(|event| PauseTrigger::Event(event.category())),
+            );
+
+            if let Some(trigger) = triggers.into_iter().find(|t| self.clock.pause_on.contains(t)) {
+                self.clock.pause();
+                tick.paused_by = Some(trigger);
+                break;
+            }
+        }
+        tick
+    }
+
+    /// Starts a new turn sized by the calendar
+    fn begin_turn(&mut self) {
+        // Make sure the state we are leaving can be rewound to
+        if !self.history.contains(self.simulation.current_turn) {
+            self.record_snapshot();
+        }
 
-        // Process simulation turn
+        territory::resolve(&mut self.solar_system, &self.simulation, self.simulation.seed);
+        environment::update_weather(&mut self.solar_system, self.simulation.seed, self.simulation.current_turn + 1);
+
+        let days = self.calendar.advance_turn(self.get_game_date()).max(1);
+        self.pipeline.begin_turn(days);
+    }
+
+    fn end_turn(&mut self) {
         self.simulation.process_turn();
+        self.simulation.events.emit(GameEventKind::TurnEnded { turn: self.simulation.current_turn });
+        if self.game_over.is_none() && !self.victory_conditions.is_empty() {
+            self.game_over = victory_engine::evaluate(self).outcome;
+            if let Some(outcome) = &self.game_over {
+                let kind = GameEventKind::GameOver { winner: outcome.winner, reason: outcome.reason.clone() };
+                self.simulation.events.emit_for(outcome.winner, kind);
+            }
+        }
+        self.simulation.events.end_turn();
+
+        let mut metrics = std::mem::take(&mut self.metrics);
+        metrics.record(self);
+        self.metrics = metrics;
+
+        self.record_snapshot();
+    }
+
+    /// Gets a faction's view of the world; this is what its AI and UI should read
+    pub fn knowledge_of(&self, faction_id: Uuid) -> Option<&FactionKnowledge> {
+        self.knowledge.get(&faction_id).map(|knowledge| &**knowledge)
+    }
+
+    /// Gets a body's surface map, generating its terrain from the game seed on first use
+    pub fn surface_map_mut(&mut self, body_id: Uuid) -> Option<&mut SurfaceMap> {
+        self.solar_system.surface_map_mut(body_id, self.simulation.seed)
+    }
+
+    /// Whether a victory condition has ended the game
+    pub fn is_game_over(&self) -> bool {
+        self.game_over.is_some()
+    }
+
+    /// Every faction's progress towards the victory conditions, best first
+    pub fn victory_standings(&self) -> Vec<FactionStanding> {
+        victory_engine::evaluate(self).standings
+    }
+
+    /// Restores the state as it was at the end of `turn`, discarding later turns
+    pub fn rewind_to(&mut self, turn: u64) -> Result<(), RewindError> {
+        let snapshot = self.history.rewind_to(turn)?;
+        let history = std::mem::take(&mut self.history);
+        let mut metrics = std::mem::take(&mut self.metrics);
+        let mut events = std::mem::take(&mut self.simulation.events);
+        *self = GameState::clone(&snapshot);
+        self.history = history;
+        metrics.truncate_after(turn);
+        self.metrics = metrics;
+        // Subscribers keep listening, but the undone turns' events never happened
+        events.clear();
+        self.simulation.events = events;
+        Ok(())
+    }
+
+    /// Rewinds the most recently processed turn
+    pub fn undo_turn(&mut self) -> Result<(), RewindError> {
+        let previous_turn = self.simulation.current_turn.saturating_sub(1);
+        self.rewind_to(previous_turn)
+    }
+
+    /// Returns true if there is an earlier turn to rewind to
+    pub fn can_undo(&self) -> bool {
+        self.simulation.current_turn > 0 && self.history.contains(self.simulation.current_turn - 1)
+    }
+
+    /// Stores a snapshot of the current state in the turn history
+    fn record_snapshot(&mut self) {
+        if self.history.capacity() == 0 {
+            return;
+        }
+        // Surface maps and knowledge are shared with the live state until either side
+        // changes them; the history and metrics series stay out of the copy
+        let history = std::mem::replace(&mut self.history, TurnHistory::new(0));
+        let metrics = std::mem::take(&mut self.metrics);
+        let snapshot = self.clone();
+        self.history = history;
+        self.metrics = metrics;
+        self.history.record(self.simulation.current_turn, snapshot);
     }
 
     /// Gets the current game date
@@ -50,7 +352,7 @@ impl GameState {
 
     /// Gets formatted game date string
     pub fn get_formatted_date(&self) -> String {
-        self.solar_system.get_formatted_date()
+        self.calendar.format_date(self.get_game_date())
     }
 }
 
@@ -0,0 +1,6 @@
+//! Headless game runner used by the `harsh_realm_sim` binary.
+//!
+//! Runs a game without any rendering so it can be driven from CI or a server.
+
+pub mod runner;
+pub mod runner_options;
@@ -0,0 +1,193 @@
+use std::fmt::{Display, Formatter};
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::PathBuf;
+use log::{info, warn};
+
+use crate::game_state::GameState;
+use crate::victory::victory_engine::GameOutcome;
+use crate::headless::runner_options::{RunnerOptions, UNTIL_VICTORY_TURN_CAP};
+use crate::save::save_error::SaveError;
+use crate::scenario::scenario_error::ScenarioError;
+use crate::scenario::scenario_loader::{load_scenario, load_scenario_with_seed};
+use crate::save::save_file::{save_game, SaveOptions};
+
+/// Errors that stop a headless run
+#[derive(Debug)]
+pub enum RunnerError {
+    /// The solar system data could not be loaded
+    Data(Box<dyn std::error::Error>),
+    Scenario(ScenarioError),
+    Save(SaveError),
+    Io(std::io::Error),
+    Serialization(serde_json::Error),
+}
+
+impl Display for RunnerError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RunnerError::Data(e) => write!(f, "failed to load solar system data: {}", e),
+            RunnerError::Scenario(e) => write!(f, "failed to load scenario: {}", e),
+            RunnerError::Save(e) => write!(f, "failed to write save: {}", e),
+            RunnerError::Io(e) => write!(f, "I/O error: {}", e),
+            RunnerError::Serialization(e) => write!(f, "failed to serialize game state: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for RunnerError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            RunnerError::Data(e) => Some(e.as_ref()),
+            RunnerError::Scenario(e) => Some(e),
+            RunnerError::Save(e) => Some(e),
+            RunnerError::Io(e) => Some(e),
+            RunnerError::Serialization(e) => Some(e),
+        }
+    }
+}
+
+impl From<ScenarioError> for RunnerError {
+    fn from(e: ScenarioError) -> Self {
+        RunnerError::Scenario(e)
+    }
+}
+
+impl From<SaveError> for RunnerError {
+    fn from(e: SaveError) -> Self {
+        RunnerError::Save(e)
+    }
+}
+
+impl From<std::io::Error> for RunnerError {
+    fn from(e: std::io::Error) -> Self {
+        RunnerError::Io(e)
+    }
+}
+
+impl From<serde_json::Error> for RunnerError {
+    fn from(e: serde_json::Error) -> Self {
+        RunnerError::Serialization(e)
+    }
+}
+
+/// What a completed headless run produced
+#[derive(Debug, Clone, Default)]
+pub struct RunReport {
+    pub turns_run: u64,
+    pub saves_written: Vec<PathBuf>,
+    /// Set if a victory condition ended the game before the turn budget ran out
+    pub outcome: Option<GameOutcome>,
+}
+
+/// Builds the game described by `options`
+pub fn build_game(options: &RunnerOptions) -> Result<GameState, RunnerError> {
+    // The seed goes in before anything is built, since surface maps are generated from it
+    let mut state = match (&options.scenario_path, options.seed) {
+        (Some(path), Some(seed)) => load_scenario_with_seed(path, seed)?,
+        (Some(path), None) => load_scenario(path)?,
+        (None, seed) => {
+            let mut state = GameState::new();
+            if let Some(seed) = seed {
+                state.simulation.seed = seed;
+            }
+            state
+                .solar_system
+                .load_from_csv(&options.data_path)
+                .map_err(RunnerError::Data)?;
+            state
+        }
+    };
+    // Nobody can undo a headless run, so don't pay for snapshots
+    state.history.set_capacity(0);
+    Ok(state)
+}
+
+/// Runs a headless game, writing a one-line summary per turn to `out`
+pub fn run(options: &RunnerOptions, out: &mut dyn Write) -> Result<RunReport, RunnerError> {
+    let mut state = build_game(options)?;
+    run_game(&mut state, options, out)
+}
+
+/// Runs `options.turns` turns of an already built game, or until it is over with `options.until_victory`
+pub fn run_game(state: &mut GameState, options: &RunnerOptions, out: &mut dyn Write) -> Result<RunReport, RunnerError> {
+    let turns = if options.until_victory { UNTIL_VICTORY_TURN_CAP } else { options.turns };
+    info!("Starting headless run of {} turns (seed {})", turns, state.simulation.seed);
+    let mut report = RunReport::default();
+
+    if options.save_every.is_some() {
+        std::fs::create_dir_all(&options.save_dir)?;
+    }
+
+    for _ in 0..turns {
+        state.process_turn();
+        report.turns_run += 1;
+        writeln!(out, "{}", turn_summary(state))?;
+
+        let turn = state.simulation.current_turn;
+        if options.save_every.is_some_and(|every| turn.is_multiple_of(every)) {
+            let path = options.save_dir.join(format!("turn_{:05}.hrsave", turn));
+            save_game(state, &path, &SaveOptions::default())?;
+            report.saves_written.push(path);
+        }
+
+        if let Some(outcome) = &state.game_over {
+            writeln!(out, "{}", outcome_summary(state, outcome))?;
+            report.outcome = Some(outcome.clone());
+            break;
+        }
+    }
+
+    if options.until_victory && report.outcome.is_none() {
+        warn!("Stopped after {} turns without a winner", report.turns_run);
+    }
+
+    if let Some(path) = &options.dump_json {
+        let mut writer = BufWriter::new(File::create(path)?);
+        serde_json::to_writer_pretty(&mut writer, state)?;
+        writer.flush()?;
+    }
+
+    if let Some(path) = &options.metrics_path {
+        let mut writer = BufWriter::new(File::create(path)?);
+        if path.extension().is_some_and(|ext| ext == "json") {
+            state.metrics.write_json(&mut writer)?;
+        } else {
+            state.metrics.write_csv(&mut writer)?;
+        }
+        writer.flush()?;
+    }
+
+    Ok(report)
+}
+
+/// Formats the per-turn summary line
+pub fn turn_summary(state: &GameState) -> String {
+    format!(
+        "Turn {:>4} | {} | factions: {} | structures: {}",
+        state.simulation.current_turn,
+        state.get_formatted_date(),
+        state.simulation.factions.len(),
+        state.simulation.structures.len()
+    )
+}
+
+/// Formats the end-of-game result with the final rankings
+pub fn outcome_summary(state: &GameState, outcome: &GameOutcome) -> String {
+    let winner = outcome
+        .winner
+        .and_then(|id| state.simulation.factions.get(&id))
+        .map(|faction| faction.name().to_string())
+        .unwrap_or_else(|| "nobody".to_string());
+    let mut summary = format!("Game over on turn {}: {} wins ({:?})", outcome.turn, winner, outcome.reason);
+    for (rank, standing) in outcome.rankings.iter().enumerate() {
+        summary.push_str(&format!(
+            "\n  {}. {} | score {:.2}{}",
+            rank + 1,
+            standing.name,
+            standing.score,
+            if standing.eliminated { " | eliminated" } else { "" }
+        ));
+    }
+    summary
+}
@@ -0,0 +1,163 @@
+use std::fmt::{Display, Formatter};
+use std::path::PathBuf;
+
+/// Most turns `--until-victory` runs before giving up on a game that never ends
+pub const UNTIL_VICTORY_TURN_CAP: u64 = 1200;
+
+pub const USAGE: &str = "\
+Usage: harsh_realm_sim [OPTIONS]
+
+Options:
+  --scenario <PATH>     Scenario file to start from (overrides --data)
+  --data <PATH>         Solar system CSV (default: data/solar_system_data.csv)
+  --turns <N>           Number of turns to run (default: 12)
+  --until-victory       Run until the game is over instead, for at most 1200 turns
+  --seed <N>            Seed for deterministic randomness (default: scenario seed or 0)
+  --save-every <N>      Write a save every N turns (default: never)
+  --save-dir <DIR>      Directory for periodic saves (default: saves)
+  --dump-json <PATH>    Write the final game state as pretty-printed JSON
+  --metrics <PATH>      Export per-turn metrics; .json for JSON, anything else for CSV
+  -h, --help            Print this help";
+
+/// Command-line options for a headless run
+#[derive(Debug, Clone, PartialEq)]
+pub struct RunnerOptions {
+    pub scenario_path: Option<PathBuf>,
+    pub data_path: PathBuf,
+    pub turns: u64,
+    /// Ignores `turns` and runs until the game is over, up to `UNTIL_VICTORY_TURN_CAP` turns
+    pub until_victory: bool,
+    /// Overrides the scenario's seed when set
+    pub seed: Option<u64>,
+    pub save_every: Option<u64>,
+    pub save_dir: PathBuf,
+    pub dump_json: Option<PathBuf>,
+    pub metrics_path: Option<PathBuf>,
+}
+
+/// Errors produced while parsing command-line arguments
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ArgsError {
+    /// `--help` was requested
+    HelpRequested,
+    UnknownArgument(String),
+    MissingValue(String),
+    InvalidValue { flag: String, value: String },
+}
+
+impl Display for ArgsError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ArgsError::HelpRequested => write!(f, "{}", USAGE),
+            ArgsError::UnknownArgument(arg) => write!(f, "unknown argument '{}'", arg),
+            ArgsError::MissingValue(flag) => write!(f, "missing value for '{}'", flag),
+            ArgsError::InvalidValue { flag, value } => write!(f, "invalid value '{}' for '{}'", value, flag),
+        }
+    }
+}
+
+impl std::error::Error for ArgsError {}
+
+impl Default for RunnerOptions {
+    fn default() -> Self {
+        Self {
+            scenario_path: None,
+            data_path: PathBuf::from("data/solar_system_data.csv"),
+            turns: 12,
+            until_victory: false,
+            seed: None,
+            save_every: None,
+            save_dir: PathBuf::from("saves"),
+            dump_json: None,
+            metrics_path: None,
+        }
+    }
+}
+
+impl RunnerOptions {
+    /// Parses options from command-line arguments (excluding the program name)
+    pub fn parse<I, S>(args: I) -> Result<Self, ArgsError>
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        let mut options = Self::default();
+        let mut args = args.into_iter().map(Into::into);
+
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "-h" | "--help" => return Err(ArgsError::HelpRequested),
+                "--scenario" => options.scenario_path = Some(PathBuf::from(value(&arg, args.next())?)),
+                "--data" => options.data_path = PathBuf::from(value(&arg, args.next())?),
+                "--turns" => options.turns = number(&arg, args.next())?,
+                "--until-victory" => options.until_victory = true,
+                "--seed" => options.seed = Some(number(&arg, args.next())?),
+                "--save-every" => {
+                    let every = number(&arg, args.next())?;
+                    if every == 0 {
+                        return Err(ArgsError::InvalidValue { flag: arg, value: "0".to_string() });
+                    }
+                    options.save_every = Some(every);
+                }
+                "--save-dir" => options.save_dir = PathBuf::from(value(&arg, args.next())?),
+                "--dump-json" => options.dump_json = Some(PathBuf::from(value(&arg, args.next())?)),
+                "--metrics" => options.metrics_path = Some(PathBuf::from(value(&arg, args.next())?)),
+                _ => return Err(ArgsError::UnknownArgument(arg)),
+            }
+        }
+        Ok(options)
+    }
+}
+
+fn value(flag: &str, value: Option<String>) -> Result<String, ArgsError> {
+    value.ok_or_else(|| ArgsError::MissingValue(flag.to_string()))
+}
+
+fn number(flag: &str, raw: Option<String>) -> Result<u64, ArgsError> {
+    let raw = value(flag, raw)?;
+    raw.parse().map_err(|_| ArgsError::InvalidValue {
+        flag: flag.to_string(),
+        value: raw,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_defaults() {
+        let options = RunnerOptions::parse(Vec::<String>::new()).unwrap();
+        assert_eq!(options, RunnerOptions::default());
+    }
+
+    #[test]
+    fn test_parse_all_flags() {
+        let options = RunnerOptions::parse([
+            "--scenario", "sol.json", "--data", "other.csv", "--turns", "40", "--seed", "7", "--save-every", "10", "--save-dir", "out",
+            "--dump-json", "final.json", "--metrics", "metrics.csv", "--until-victory",
+        ])
+        .unwrap();
+
+        assert_eq!(options.scenario_path, Some(PathBuf::from("sol.json")));
+        assert_eq!(options.data_path, PathBuf::from("other.csv"));
+        assert_eq!(options.turns, 40);
+        assert!(options.until_victory);
+        assert_eq!(options.seed, Some(7));
+        assert_eq!(options.save_every, Some(10));
+        assert_eq!(options.save_dir, PathBuf::from("out"));
+        assert_eq!(options.dump_json, Some(PathBuf::from("final.json")));
+        assert_eq!(options.metrics_path, Some(PathBuf::from("metrics.csv")));
+    }
+
+    #[test]
+    fn test_rejects_bad_arguments() {
+        assert_eq!(
+            RunnerOptions::parse(["--turns", "many"]),
+            Err(ArgsError::InvalidValue { flag: "--turns".to_string(), value: "many".to_string() })
+        );
+        assert_eq!(RunnerOptions::parse(["--seed"]), Err(ArgsError::MissingValue("--seed".to_string())));
+        assert_eq!(RunnerOptions::parse(["--fast"]), Err(ArgsError::UnknownArgument("--fast".to_string())));
+        assert!(RunnerOptions::parse(["--save-every", "0"]).is_err());
+    }
+}
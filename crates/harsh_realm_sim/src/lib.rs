@@ -8,15 +8,20 @@ pub mod events;
 pub mod faction;
 pub mod game;
 pub mod game_state;
+pub mod headless;
 pub mod maps;
+pub mod metrics;
 pub mod population;
 pub mod procedural_generation;
 pub mod production;
 pub mod resources;
+pub mod save;
+pub mod scenario;
 pub mod simulation;
 pub mod structures;
 pub mod units;
 pub mod universe;
+pub mod victory;
 
 pub mod prelude {
     pub use crate::game_state::GameState;
@@ -0,0 +1,29 @@
+//! Headless Harsh Realm runner: `harsh_realm_sim --turns 100 --save-every 10`
+
+use std::process::ExitCode;
+use harsh_realm_sim::headless::runner::run;
+use harsh_realm_sim::headless::runner_options::{ArgsError, RunnerOptions, USAGE};
+
+fn main() -> ExitCode {
+    env_logger::Builder::from_env(env_logger::Env::default().default_filter_or("warn")).init();
+
+    let options = match RunnerOptions::parse(std::env::args().skip(1)) {
+        Ok(options) => options,
+        Err(ArgsError::HelpRequested) => {
+            println!("{}", USAGE);
+            return ExitCode::SUCCESS;
+        }
+        Err(e) => {
+            eprintln!("error: {}\n\n{}", e, USAGE);
+            return ExitCode::from(2);
+        }
+    };
+
+    match run(&options, &mut std::io::stdout().lock()) {
+        Ok(_) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("error: {}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
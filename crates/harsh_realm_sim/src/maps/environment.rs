@@ -0,0 +1,527 @@
+// src/maps/environment.rs
+// Surface conditions on each tile: air, temperature, radiation and weather.
+//
+// A body's climate is worked out from its physical data, much as its terrain
+// class is, and then varied per tile by elevation and latitude, so only the
+// weather needs storing.  Weather is rolled every turn from the game seed.
+// Conditions feed back on whatever stands on the tile: harsh ones load life
+// support, call for radiation shielding and wear equipment out.  Settlements
+// with less shielding than the surface calls for lose people to radiation
+// sickness.
+
+use std::collections::{BTreeMap, HashMap};
+use hex2d::Coordinate as HexCoord;
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::maps::hex_utils;
+use crate::maps::location::Location;
+use crate::maps::tile::{Terrain, Tile};
+use crate::population::person_type::PersonType;
+use crate::procedural_generation::terrain_generator::{SurfaceClass, TerrainProfile};
+use crate::resources::resource_type::ResourceType;
+use crate::simulation::simulation::Simulation;
+use crate::structures::structure_type::StructureType;
+use crate::universe::celestial_body::CelestialBody;
+use crate::universe::navigation;
+use crate::universe::solar_system_manager::SolarSystemManager;
+
+/// Pressure of Earth's air at sea level, in kPa
+pub const EARTH_PRESSURE_KPA: f64 = 101.3;
+
+/// Temperatures people live in without heating or cooling, in kelvin
+pub const COMFORT_TEMPERATURE_K: std::ops::Range<f64> = 273.0..303.0;
+
+/// Pressures people live in without pressurised habitats, in kPa
+pub const COMFORT_PRESSURE_KPA: std::ops::Range<f64> = 50.0..200.0;
+
+/// Occupational radiation limit of 50 mSv a year, per day
+pub const DOSE_LIMIT_MSV_PER_DAY: f64 = 50.0 / 365.0;
+
+/// Column of matter, in g/cm², that cuts a radiation dose by a factor of e
+pub const ATTENUATION_G_CM2: f64 = 222.0;
+
+/// Dose above the limit, in mSv, that kills on average one person in e exposed to it
+const LETHAL_DOSE_MSV: f64 = 5000.0;
+
+/// Cosmic ray dose on an unshielded surface, with the body blocking half the sky, in mSv/day
+const COSMIC_RAY_MSV_PER_DAY: f64 = 1.0;
+
+/// Trapped radiation at a giant of Jupiter's mass, extrapolated to its cloud tops, in mSv/day
+const BELT_DOSE_MSV_PER_DAY: f64 = 1.44e6;
+
+/// Planetary radii over which trapped radiation falls by a factor of e
+const BELT_FALLOFF_RADII: f64 = 1.6;
+
+const JUPITER_MASS_KG: f64 = 1.898e27;
+
+/// Worlds this hot under a thick carbon dioxide sky have a runaway greenhouse, like Venus
+const RUNAWAY_GREENHOUSE_K: f64 = 300.0;
+
+/// Equipment wear per day in benign conditions
+const BASE_WEAR_PER_DAY: f64 = 0.0002;
+
+/// Energy each person's life support draws per day for every unit of load above 1
+const LIFE_SUPPORT_ENERGY_PER_PERSON_DAY: f64 = 0.01;
+
+/// Chance a stormy tile settles one step each turn
+const STORM_EASING_CHANCE: f64 = 0.5;
+
+/// Main gas of an atmosphere
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Atmosphere {
+    #[default]
+    Vacuum,
+    /// Breathable air, like Earth's
+    NitrogenOxygen,
+    /// Like Mars and Venus
+    CarbonDioxide,
+    /// Nitrogen and methane, like Titan
+    Nitrogen,
+    /// Like the giants
+    Hydrogen,
+    /// Volcanic traces, like Io
+    SulphurDioxide,
+}
+
+impl Atmosphere {
+    /// Mean molar mass in g/mol
+    fn molar_mass(self) -> f64 {
+        match self {
+            Atmosphere::Vacuum | Atmosphere::NitrogenOxygen => 29.0,
+            Atmosphere::CarbonDioxide => 44.0,
+            Atmosphere::Nitrogen => 28.0,
+            Atmosphere::Hydrogen => 2.3,
+            Atmosphere::SulphurDioxide => 64.0,
+        }
+    }
+
+    /// Warming per kPa of surface pressure, in kelvin
+    fn greenhouse_k_per_kpa(self) -> f64 {
+        match self {
+            Atmosphere::CarbonDioxide => 0.045,
+            Atmosphere::NitrogenOxygen => 0.1,
+            Atmosphere::Nitrogen => 0.03,
+            Atmosphere::Vacuum | Atmosphere::Hydrogen | Atmosphere::SulphurDioxide => 0.0,
+        }
+    }
+
+    pub fn is_breathable(self) -> bool {
+        self == Atmosphere::NitrogenOxygen
+    }
+}
+
+/// Weather on a tile this turn
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Weather {
+    #[default]
+    Calm,
+    Windy,
+    /// Rain, snow or wind storms
+    Storm,
+    /// Dust lifted over the region, cutting sunlight and scouring equipment
+    DustStorm,
+}
+
+/// Conditions shared by a whole body, before elevation and latitude are accounted for
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Climate {
+    pub class: SurfaceClass,
+    pub atmosphere: Atmosphere,
+    /// Pressure at the reference level, in kPa
+    pub surface_pressure_kpa: f64,
+    /// Mean surface temperature in kelvin
+    pub mean_temperature_k: f64,
+    /// Temperature change from the day's mean to its peak, in kelvin
+    pub day_night_swing_k: f64,
+    /// Dose above the atmosphere, from cosmic rays and any radiation belts, in mSv/day
+    pub space_radiation_msv_per_day: f64,
+    /// Surface gravity in m/s²
+    pub surface_gravity: f64,
+    /// Chance of a storm breaking out over each tile each turn
+    pub storm_chance: f64,
+}
+
+impl Climate {
+    /// Works out a body's climate from its data and the body it orbits
+    pub fn for_body(body: &CelestialBody, solar_system: &SolarSystemManager) -> Self {
+        let profile = TerrainProfile::for_body(body, solar_system);
+        let (atmosphere, surface_pressure_kpa) = match profile.class {
+            SurfaceClass::Giant => (Atmosphere::Hydrogen, 100.0),
+            SurfaceClass::Temperate => (Atmosphere::NitrogenOxygen, EARTH_PRESSURE_KPA),
+            SurfaceClass::Arid if profile.temperature_k >= RUNAWAY_GREENHOUSE_K => (Atmosphere::CarbonDioxide, 9200.0),
+            SurfaceClass::Arid => (Atmosphere::CarbonDioxide, 0.6),
+            SurfaceClass::Frozen if profile.atmosphere => (Atmosphere::Nitrogen, 147.0),
+            SurfaceClass::Volcanic => (Atmosphere::SulphurDioxide, 1e-6),
+            SurfaceClass::Frozen | SurfaceClass::Barren => (Atmosphere::Vacuum, 0.0),
+        };
+        let mean_temperature_k = profile.temperature_k + surface_pressure_kpa * atmosphere.greenhouse_k_per_kpa();
+
+        // Air evens out day and night, and slow days leave longer to heat and cool
+        let damping = 0.5 / (1.0 + (surface_pressure_kpa / 0.3).sqrt());
+        let day = body.day_length().map_or(1.0, |days| (days / 30.0).min(1.0).powf(0.25));
+
+        let belts = solar_system.parent_of(body).filter(|parent| parent.is_giant()).map_or(0.0, |parent| {
+            let orbit_km = body.orbital_state.as_ref().map_or(f64::MAX, |state| state.parameters.semi_major_axis);
+            let radii = orbit_km / (parent.diameter / 2.0);
+            BELT_DOSE_MSV_PER_DAY * (parent.mass / JUPITER_MASS_KG).powi(4) * (-radii / BELT_FALLOFF_RADII).exp()
+        });
+
+        let storm_chance = match profile.class {
+            SurfaceClass::Giant => 0.01,
+            SurfaceClass::Temperate => 0.005,
+            SurfaceClass::Arid if surface_pressure_kpa < 10.0 => 0.002,
+            SurfaceClass::Frozen if profile.atmosphere => 0.001,
+            _ => 0.0,
+        };
+
+        Self {
+            class: profile.class,
+            atmosphere,
+            surface_pressure_kpa,
+            mean_temperature_k,
+            day_night_swing_k: mean_temperature_k * damping * day,
+            space_radiation_msv_per_day: COSMIC_RAY_MSV_PER_DAY + belts,
+            surface_gravity: profile.surface_gravity,
+            storm_chance,
+        }
+    }
+
+    /// The kind of storm the body's weather brews, if any
+    pub fn storm(&self) -> Option<Weather> {
+        match self.class {
+            _ if self.storm_chance <= 0.0 => None,
+            SurfaceClass::Arid => Some(Weather::DustStorm),
+            _ => Some(Weather::Storm),
+        }
+    }
+
+    /// Conditions at `latitude` on `tile`, or at the reference level in calm weather without one
+    pub fn at(&self, latitude: f64, tile: Option<&Tile>) -> Environment {
+        let mut elevation_m = tile.map_or(0.0, |tile| tile.elevation as f64);
+        if tile.is_some_and(|tile| tile.terrain == Terrain::Ocean) {
+            // Settlers live on the sea's surface, not its floor
+            elevation_m = elevation_m.max(0.0);
+        }
+        let gravity = self.surface_gravity.max(0.01);
+
+        // Poles are colder, less so where thick air carries heat to them
+        let transport = self.surface_pressure_kpa / (self.surface_pressure_kpa + 50.0);
+        let insolation = 1.05 * latitude.to_radians().cos().max(0.05).powf(0.25);
+        let mut temperature_k = self.mean_temperature_k * (transport + (1.0 - transport) * insolation);
+        let mut pressure_kpa = self.surface_pressure_kpa;
+        if pressure_kpa > 0.1 {
+            temperature_k -= gravity / 1.5 * elevation_m / 1000.0;
+        }
+        if pressure_kpa > 0.0 {
+            let scale_height_m = 8314.0 * self.mean_temperature_k / (self.atmosphere.molar_mass() * gravity);
+            pressure_kpa *= (-elevation_m / scale_height_m).exp();
+        }
+        let column_g_cm2 = pressure_kpa * 100.0 / gravity;
+
+        Environment {
+            atmosphere: self.atmosphere,
+            pressure_kpa,
+            temperature_k: temperature_k.max(3.0),
+            day_night_swing_k: self.day_night_swing_k,
+            radiation_msv_per_day: self.space_radiation_msv_per_day * (-column_g_cm2 / ATTENUATION_G_CM2).exp(),
+            loose_dust: matches!(self.class, SurfaceClass::Barren | SurfaceClass::Volcanic)
+                || (self.class == SurfaceClass::Arid && self.surface_pressure_kpa < 10.0),
+            weather: tile.map_or(Weather::Calm, |tile| tile.weather),
+        }
+    }
+}
+
+/// Conditions on one tile
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Environment {
+    pub atmosphere: Atmosphere,
+    pub pressure_kpa: f64,
+    /// Mean temperature over a day, in kelvin
+    pub temperature_k: f64,
+    /// Temperature change from the day's mean to its peak, in kelvin
+    pub day_night_swing_k: f64,
+    /// Dose to an unshielded person, in mSv/day
+    pub radiation_msv_per_day: f64,
+    /// Fine, abrasive regolith that gets into everything, as on the Moon and Mars
+    pub loose_dust: bool,
+    pub weather: Weather,
+}
+
+impl Environment {
+    /// Coldest temperature of the day, in kelvin
+    pub fn min_temperature_k(&self) -> f64 {
+        (self.temperature_k - self.day_night_swing_k).max(3.0)
+    }
+
+    /// Hottest temperature of the day, in kelvin
+    pub fn max_temperature_k(&self) -> f64 {
+        self.temperature_k + self.day_night_swing_k
+    }
+
+    /// Returns true if people can live in the open here
+    pub fn is_habitable(&self) -> bool {
+        self.atmosphere.is_breathable()
+            && COMFORT_PRESSURE_KPA.contains(&self.pressure_kpa)
+            && COMFORT_TEMPERATURE_K.contains(&self.temperature_k)
+            && self.radiation_msv_per_day <= DOSE_LIMIT_MSV_PER_DAY
+    }
+
+    /// What these conditions demand of anything built or parked here
+    pub fn effects(&self) -> EnvironmentEffects {
+        // Sealed habitats work harder the further the outside is from a shirt-sleeve room
+        let sealed = if self.atmosphere.is_breathable() { 0.0 } else { 0.25 };
+        let pressure_gap = if COMFORT_PRESSURE_KPA.contains(&self.pressure_kpa) {
+            0.0
+        } else {
+            ((self.pressure_kpa + 1.0) / (EARTH_PRESSURE_KPA + 1.0)).ln().abs() * 0.1
+        };
+        let thermal_gap = (COMFORT_TEMPERATURE_K.start - self.min_temperature_k())
+            .max(self.max_temperature_k() - COMFORT_TEMPERATURE_K.end)
+            .max(0.0)
+            / 200.0;
+        let (storm_load, storm_wear) = match self.weather {
+            Weather::Calm => (0.0, 0.0),
+            Weather::Windy => (0.0, 0.5),
+            Weather::Storm => (0.1, 2.0),
+            Weather::DustStorm => (0.2, 4.0),
+        };
+
+        let dust_wear = if self.loose_dust { 1.0 } else { 0.0 };
+        let radiation_wear = (1.0 + self.radiation_msv_per_day).log10();
+        let cycling_wear = self.day_night_swing_k / 100.0;
+
+        EnvironmentEffects {
+            life_support_load: 1.0 + sealed + pressure_gap + thermal_gap + storm_load,
+            shielding_g_cm2: ATTENUATION_G_CM2 * (self.radiation_msv_per_day / DOSE_LIMIT_MSV_PER_DAY).ln().max(0.0),
+            wear_per_day: BASE_WEAR_PER_DAY * (1.0 + storm_wear + dust_wear + radiation_wear + cycling_wear),
+        }
+    }
+}
+
+/// What the environment demands of the structures on a tile
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct EnvironmentEffects {
+    /// Life support work relative to a settlement in Earth's open air
+    pub life_support_load: f64,
+    /// Shielding needed to keep people under the dose limit, in g/cm² (about 1 m of regolith is 150)
+    pub shielding_g_cm2: f64,
+    /// Equipment wear per day, as a fraction of its working life
+    pub wear_per_day: f64,
+}
+
+/// Conditions at a location, following docking to the host; `None` off any surface
+pub fn environment_at(
+    solar_system: &SolarSystemManager,
+    simulation: &Simulation,
+    location: &Location,
+) -> Option<Environment> {
+    let Location::Surface { body_id, hex_coord } = navigation::resolve(simulation, location).ok()? else {
+        return None;
+    };
+    let body = solar_system.get_body_by_id(*body_id)?;
+    let (latitude, _) = body.surface_grid().lat_lon(*hex_coord)?;
+    let tile = body.surface_map().and_then(|map| map.tile(*hex_coord));
+    Some(Climate::for_body(body, solar_system).at(latitude, tile))
+}
+
+/// Rolls each surface map's weather for a new turn; returns the number of tiles under a storm.
+///
+/// Only maps that have been generated have weather; storms ease off a step at
+/// a time and new ones spread over a few hexes around where they break out.
+pub fn update_weather(solar_system: &mut SolarSystemManager, seed: u64, turn: u64) -> usize {
+    let mut bodies: Vec<(Uuid, Climate)> = solar_system
+        .get_all_bodies()
+        .values()
+        .filter(|body| body.surface_map().is_some())
+        .map(|body| (body.id, Climate::for_body(body, solar_system)))
+        .collect();
+    bodies.sort_by_key(|(body_id, _)| *body_id);
+
+    let mut stormy = 0;
+    for (body_id, climate) in bodies {
+        let Some(storm) = climate.storm() else {
+            continue;
+        };
+        // Roll the new weather against the map as it is, so a map whose weather holds is never copied
+        let Some(map) = solar_system.get_body_by_id(body_id).and_then(|body| body.surface_map()) else {
+            continue;
+        };
+        let mut rng = ChaCha8Rng::seed_from_u64(seed ^ (body_id.as_u128() as u64) ^ turn.wrapping_mul(0x9e37_79b9_7f4a_7c15));
+        let mut weather: BTreeMap<HexCoord, Weather> = map.tiles().map(|tile| (tile.coord, tile.weather)).collect();
+
+        let mut outbreaks = Vec::new();
+        for (coord, current) in weather.iter_mut() {
+            // Always draw the same numbers per tile so one tile's weather doesn't shift the rest
+            let (eases, breaks_out, reach) = (
+                rng.random_bool(STORM_EASING_CHANCE),
+                rng.random::<f64>() < climate.storm_chance,
+                rng.random_range(0..=2u32),
+            );
+            if eases {
+                *current = match *current {
+                    Weather::Storm | Weather::DustStorm => Weather::Windy,
+                    Weather::Windy | Weather::Calm => Weather::Calm,
+                };
+            }
+            if breaks_out {
+                outbreaks.push((*coord, reach));
+            }
+        }
+        for (centre, reach) in outbreaks {
+            for (coord, steps) in hex_utils::within_radius(map, centre, reach + 1) {
+                let current = weather.get_mut(&coord).expect("neighbours are on the map");
+                *current = if steps <= reach { storm } else { current.max_with(Weather::Windy) };
+            }
+        }
+        stormy += weather.values().filter(|weather| matches!(weather, Weather::Storm | Weather::DustStorm)).count();
+
+        let changed: Vec<(HexCoord, Weather)> = weather
+            .into_iter()
+            .filter(|(coord, weather)| map.tile(*coord).is_some_and(|tile| tile.weather != *weather))
+            .collect();
+        if changed.is_empty() {
+            continue;
+        }
+        let Some(map) = solar_system.get_body_by_id_mut(body_id).and_then(|body| body.surface_map_mut()) else {
+            continue;
+        };
+        for (coord, weather) in changed {
+            if let Some(tile) = map.tile_mut(coord) {
+                tile.weather = weather;
+            }
+        }
+    }
+    stormy
+}
+
+impl Weather {
+    /// The rougher of two kinds of weather
+    fn max_with(self, other: Weather) -> Weather {
+        let rank = |weather: Weather| match weather {
+            Weather::Calm => 0,
+            Weather::Windy => 1,
+            Weather::Storm => 2,
+            Weather::DustStorm => 3,
+        };
+        if rank(other) > rank(self) { other } else { self }
+    }
+}
+
+/// What surface conditions did to settlements over a turn
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct EnvironmentReport {
+    /// Settlements whose life support ran short, with the Energy shortfall
+    pub strained: Vec<(Uuid, u64)>,
+    /// Settlements shielded too thinly for their surface, with the dose their people
+    /// took in mSv/day and how many died of it
+    pub irradiated: Vec<(Uuid, f64, u32)>,
+}
+
+/// Applies `days` of surface conditions to every structure standing on a surface.
+///
+/// Equipment wears, settlements burn Energy to keep life support up with the
+/// load, and settlements with less shielding than the surface calls for take
+/// a dose over the limit that kills some of their people.
+pub fn apply(simulation: &mut Simulation, solar_system: &SolarSystemManager, days: u32) -> EnvironmentReport {
+    let effects: HashMap<Uuid, EnvironmentEffects> = simulation
+        .structures
+        .iter()
+        .filter_map(|(id, structure)| {
+            let environment = environment_at(solar_system, simulation, structure.location())?;
+            Some((*id, environment.effects()))
+        })
+        .collect();
+
+    let mut report = EnvironmentReport::default();
+    for (id, effects) in effects {
+        let Some(structure) = simulation.structures.get_mut(&id) else {
+            continue;
+        };
+        structure.add_wear((effects.wear_per_day * days as f64) as f32);
+
+        if let StructureType::Settlement(settlement) = structure {
+            let people: u64 = settlement.population().values().map(|n| *n as u64).sum();
+            let needed = (people as f64 * (effects.life_support_load - 1.0) * LIFE_SUPPORT_ENERGY_PER_PERSON_DAY * days as f64)
+                .ceil() as u64;
+            let shortfall = needed - settlement.remove_resource(&ResourceType::Energy, needed);
+            if shortfall > 0 {
+                // Overworked systems wear faster
+                settlement.add_wear((effects.wear_per_day * days as f64 * shortfall as f64 / needed as f64) as f32);
+                report.strained.push((id, shortfall));
+            }
+
+            // Every attenuation length of shielding missing multiplies the dose over the limit by e
+            let missing = effects.shielding_g_cm2 - settlement.shielding_g_cm2();
+            if missing > 0.0 {
+                let dose = DOSE_LIMIT_MSV_PER_DAY * (missing / ATTENUATION_G_CM2).exp();
+                let killed = 1.0 - (-(dose - DOSE_LIMIT_MSV_PER_DAY) * days as f64 / LETHAL_DOSE_MSV).exp();
+                let mut deaths = 0;
+                let mut people: Vec<(PersonType, u32)> = settlement.population().iter().map(|(p, n)| (p.clone(), *n)).collect();
+                people.sort();
+                for (person_type, count) in people {
+                    let dead = (count as f64 * killed).round() as u32;
+                    if dead > 0 && settlement.remove_population(person_type, dead).is_ok() {
+                        deaths += dead;
+                    }
+                }
+                report.irradiated.push((id, dose, deaths));
+            }
+        }
+    }
+    report.strained.sort();
+    report.irradiated.sort_by_key(|(id, _, _)| *id);
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn climate(class: SurfaceClass, atmosphere: Atmosphere, pressure_kpa: f64, temperature_k: f64) -> Climate {
+        Climate {
+            class,
+            atmosphere,
+            surface_pressure_kpa: pressure_kpa,
+            mean_temperature_k: temperature_k,
+            day_night_swing_k: 5.0,
+            space_radiation_msv_per_day: COSMIC_RAY_MSV_PER_DAY,
+            surface_gravity: 9.8,
+            storm_chance: 0.0,
+        }
+    }
+
+    #[test]
+    fn test_air_thins_and_cools_with_height() {
+        let earth = climate(SurfaceClass::Temperate, Atmosphere::NitrogenOxygen, EARTH_PRESSURE_KPA, 288.0);
+        let mut summit = Tile::new(HexCoord::new(0, 0), Terrain::Mountains);
+        summit.elevation = 8800.0;
+        let sea_level = earth.at(0.0, None);
+        let top = earth.at(0.0, Some(&summit));
+        assert!(sea_level.is_habitable());
+        assert!((top.pressure_kpa / sea_level.pressure_kpa - 0.35).abs() < 0.05, "{}", top.pressure_kpa);
+        assert!(top.temperature_k < sea_level.temperature_k - 50.0);
+        assert!(top.radiation_msv_per_day > sea_level.radiation_msv_per_day);
+        assert!(earth.at(80.0, None).temperature_k < sea_level.temperature_k);
+    }
+
+    #[test]
+    fn test_harsher_places_cost_more() {
+        let earth = climate(SurfaceClass::Temperate, Atmosphere::NitrogenOxygen, EARTH_PRESSURE_KPA, 288.0).at(0.0, None);
+        let mut moon = climate(SurfaceClass::Barren, Atmosphere::Vacuum, 0.0, 270.0);
+        moon.day_night_swing_k = 130.0;
+        let moon = moon.at(0.0, None);
+
+        let (home, away) = (earth.effects(), moon.effects());
+        assert!(home.life_support_load < 1.1);
+        assert!(away.life_support_load > home.life_support_load + 0.5);
+        assert_eq!(home.shielding_g_cm2, 0.0);
+        assert!(away.shielding_g_cm2 > 0.0);
+        assert!(away.wear_per_day > home.wear_per_day * 2.0);
+
+        let storm = Environment { weather: Weather::DustStorm, ..moon.clone() };
+        assert!(storm.effects().wear_per_day > away.wear_per_day);
+    }
+}
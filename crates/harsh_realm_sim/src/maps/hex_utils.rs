@@ -0,0 +1,608 @@
+// src/maps/hex_utils.rs
+// Axial hex helpers for the flat lattice `hex2d` works in, spatial queries
+// over any `HexTopology`, and pathfinding over a body's `SurfaceMap`.  The
+// plain `neighbors` and `distance` only hold within one face of the sphere;
+// the topology queries work the same on the plane and across a whole body.
+
+use std::cmp::Ordering;
+use std::collections::{BTreeSet, BinaryHeap, HashMap, HashSet, VecDeque};
+use std::f64::consts::TAU;
+use hex2d::Coordinate as HexCoord;
+use nalgebra::Vector3;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::maps::sphere_grid::{HEX_WIDTH_KM, MAX_STEP_STRETCH};
+use crate::maps::surface_map::SurfaceMap;
+use crate::maps::tile::{Terrain, Tile};
+use crate::units::unit_domain::UnitDomain;
+
+/// Water must be at least this deep, in metres, for submarines to pass
+pub const MIN_SUBMERGED_DEPTH_M: f32 = 100.0;
+
+/// Fuel an aerospace unit burns per hex, whatever it flies over
+pub const AEROSPACE_FUEL_COST: f64 = 1.0;
+
+/// Gets the six coordinates adjacent to `coord` on an unbounded plane
+pub fn neighbors(coord: HexCoord) -> [HexCoord; 6] {
+    coord.neighbors()
+}
+
+/// Number of steps between two coordinates on an unbounded plane
+pub fn distance(a: HexCoord, b: HexCoord) -> i32 {
+    a.distance(b)
+}
+
+/// A layout of hexes the spatial queries can walk: the flat plane or a body's sphere
+pub trait HexTopology {
+    fn contains(&self, coord: HexCoord) -> bool;
+
+    /// Hexes sharing an edge with `coord`
+    fn neighbors(&self, coord: HexCoord) -> Vec<HexCoord>;
+
+    /// Centre of a hex in km, from the plane's origin or the body's centre
+    fn centre_km(&self, coord: HexCoord) -> Option<Vector3<f64>>;
+
+    /// Radius of the surface's curvature in km, or `None` if it is flat
+    fn curvature_km(&self) -> Option<f64>;
+
+    /// Outward direction of the surface at `point`
+    fn up(&self, point: &Vector3<f64>) -> Vector3<f64> {
+        match self.curvature_km() {
+            Some(_) => point.normalize(),
+            None => Vector3::z(),
+        }
+    }
+}
+
+/// The unbounded flat lattice, with hexes `HEX_WIDTH_KM` across
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct FlatPlane;
+
+impl HexTopology for FlatPlane {
+    fn contains(&self, _coord: HexCoord) -> bool {
+        true
+    }
+
+    fn neighbors(&self, coord: HexCoord) -> Vec<HexCoord> {
+        coord.neighbors().to_vec()
+    }
+
+    fn centre_km(&self, coord: HexCoord) -> Option<Vector3<f64>> {
+        let (x, y) = (coord.x as f64, coord.y as f64);
+        Some(Vector3::new(x + y / 2.0, y * 3f64.sqrt() / 2.0, 0.0) * HEX_WIDTH_KM)
+    }
+
+    fn curvature_km(&self) -> Option<f64> {
+        None
+    }
+}
+
+impl HexTopology for SurfaceMap {
+    fn contains(&self, coord: HexCoord) -> bool {
+        SurfaceMap::contains(self, coord)
+    }
+
+    fn neighbors(&self, coord: HexCoord) -> Vec<HexCoord> {
+        SurfaceMap::neighbors(self, coord)
+    }
+
+    fn centre_km(&self, coord: HexCoord) -> Option<Vector3<f64>> {
+        self.grid().position(coord).map(|position| position * self.radius_km)
+    }
+
+    fn curvature_km(&self) -> Option<f64> {
+        Some(self.radius_km)
+    }
+}
+
+/// Every hex within `radius` steps of `centre`, with its distance in steps, nearest first
+pub fn within_radius(topology: &impl HexTopology, centre: HexCoord, radius: u32) -> Vec<(HexCoord, u32)> {
+    if !topology.contains(centre) {
+        return Vec::new();
+    }
+    let mut seen = HashSet::from([centre]);
+    let mut ring = vec![centre];
+    let mut found = vec![(centre, 0)];
+    for steps in 1..=radius {
+        let mut next_ring = Vec::new();
+        for coord in ring {
+            for next in topology.neighbors(coord) {
+                if topology.contains(next) && seen.insert(next) {
+                    next_ring.push(next);
+                    found.push((next, steps));
+                }
+            }
+        }
+        ring = next_ring;
+    }
+    found
+}
+
+/// Hexes exactly `radius` steps from `centre`, clockwise from north
+pub fn ring(topology: &impl HexTopology, centre: HexCoord, radius: u32) -> Vec<HexCoord> {
+    let mut ring: Vec<(f64, HexCoord)> = within_radius(topology, centre, radius)
+        .into_iter()
+        .filter(|(_, steps)| *steps == radius)
+        .filter_map(|(coord, _)| Some((bearing(topology, centre, coord)?, coord)))
+        .collect();
+    ring.sort_by(|a, b| a.0.total_cmp(&b.0).then(a.1.cmp(&b.1)));
+    ring.into_iter().map(|(_, coord)| coord).collect()
+}
+
+/// `centre` followed by each ring out to `radius` in turn
+pub fn spiral(topology: &impl HexTopology, centre: HexCoord, radius: u32) -> Vec<HexCoord> {
+    if !topology.contains(centre) {
+        return Vec::new();
+    }
+    let mut spiral = vec![centre];
+    for step in 1..=radius {
+        spiral.extend(ring(topology, centre, step));
+    }
+    spiral
+}
+
+/// Direction from one hex to another in radians clockwise from north; on the
+/// plane north is up the y axis
+pub fn bearing(topology: &impl HexTopology, from: HexCoord, to: HexCoord) -> Option<f64> {
+    let (origin, target) = (topology.centre_km(from)?, topology.centre_km(to)?);
+    let up = topology.up(&origin);
+    let mut north = Vector3::z() - up * up.z;
+    if north.norm() < 1e-9 {
+        // At a pole, or on the plane, take north along the y axis
+        north = Vector3::y() - up * up.y;
+    }
+    let north = north.normalize();
+    let east = north.cross(&up);
+    let heading = target - origin;
+    Some(heading.dot(&east).atan2(heading.dot(&north)).rem_euclid(TAU))
+}
+
+/// The hexes a straight line from `from` to `to` passes through, both ends included.
+///
+/// On a sphere the line follows the great circle.  Each step moves to the
+/// neighbour closer to `to` that strays least from the line.
+pub fn line(topology: &impl HexTopology, from: HexCoord, to: HexCoord) -> Vec<HexCoord> {
+    let (Some(start), Some(end)) = (topology.centre_km(from), topology.centre_km(to)) else {
+        return Vec::new();
+    };
+    // The plane holding the line and the surface normal where it starts
+    let across = (end - start).cross(&topology.up(&start));
+    let across = if across.norm() > 0.0 { across.normalize() } else { across };
+
+    let mut line = vec![from];
+    let mut current = from;
+    let mut remaining = (end - start).norm();
+    while current != to {
+        let next = topology
+            .neighbors(current)
+            .into_iter()
+            .filter(|coord| topology.contains(*coord))
+            .filter_map(|coord| {
+                let centre = topology.centre_km(coord)?;
+                let left = (end - centre).norm();
+                (left < remaining).then(|| ((centre - start).dot(&across).abs(), left, coord))
+            })
+            .min_by(|a, b| a.0.total_cmp(&b.0).then(a.1.total_cmp(&b.1)).then(a.2.cmp(&b.2)));
+        let Some((_, left, coord)) = next else {
+            break;
+        };
+        line.push(coord);
+        current = coord;
+        remaining = left;
+    }
+    line
+}
+
+/// Returns true if an observer `eye_height_m` above `from` can see the ground at `to`.
+///
+/// `elevation` gives each hex's height in metres; hexes it has no height for
+/// block the view.  On a sphere the ground curving away hides distant hexes
+/// behind the horizon.
+pub fn line_of_sight(
+    topology: &impl HexTopology,
+    from: HexCoord,
+    to: HexCoord,
+    eye_height_m: f32,
+    elevation: impl Fn(HexCoord) -> Option<f32>,
+) -> bool {
+    let (Some(eye), Some(target)) = (elevation(from), elevation(to)) else {
+        return false;
+    };
+    let eye = (eye + eye_height_m) as f64;
+    let path = line(topology, from, to);
+    if path.last() != Some(&to) {
+        return false;
+    }
+    let Some(total) = surface_km(topology, from, to) else {
+        return false;
+    };
+    path[1..path.len() - 1].iter().all(|coord| {
+        let (Some(ground), Some(along)) = (elevation(*coord), surface_km(topology, from, *coord)) else {
+            return false;
+        };
+        let t = if total > 0.0 { along / total } else { 0.0 };
+        // How far the ground bulges up into the sightline between the two ends
+        let bulge_m = topology.curvature_km().map_or(0.0, |radius| along * (total - along) / (2.0 * radius) * 1000.0);
+        ground as f64 + bulge_m < eye + (target as f64 - eye) * t
+    })
+}
+
+/// Distance over the surface between two hex centres in km
+fn surface_km(topology: &impl HexTopology, a: HexCoord, b: HexCoord) -> Option<f64> {
+    let (a, b) = (topology.centre_km(a)?, topology.centre_km(b)?);
+    Some(match topology.curvature_km() {
+        Some(radius) => a.angle(&b) * radius,
+        None => (a - b).norm(),
+    })
+}
+
+/// Every hex connected to `start` through hexes that satisfy `include`; empty if `start` doesn't
+pub fn flood_fill(
+    topology: &impl HexTopology,
+    start: HexCoord,
+    include: impl Fn(HexCoord) -> bool,
+) -> HashSet<HexCoord> {
+    let mut region = HashSet::new();
+    if !topology.contains(start) || !include(start) {
+        return region;
+    }
+    region.insert(start);
+    let mut queue = VecDeque::from([start]);
+    while let Some(coord) = queue.pop_front() {
+        for next in topology.neighbors(coord) {
+            if topology.contains(next) && !region.contains(&next) && include(next) {
+                region.insert(next);
+                queue.push_back(next);
+            }
+        }
+    }
+    region
+}
+
+/// Splits `hexes` into groups joined by shared edges, largest first; each group is sorted
+pub fn connected_components(
+    topology: &impl HexTopology,
+    hexes: impl IntoIterator<Item = HexCoord>,
+) -> Vec<Vec<HexCoord>> {
+    let mut remaining: BTreeSet<HexCoord> = hexes.into_iter().filter(|coord| topology.contains(*coord)).collect();
+    let mut components = Vec::new();
+    while let Some(start) = remaining.pop_first() {
+        let region = flood_fill(topology, start, |coord| coord == start || remaining.contains(&coord));
+        remaining.retain(|coord| !region.contains(coord));
+        let mut component: Vec<HexCoord> = region.into_iter().collect();
+        component.sort();
+        components.push(component);
+    }
+    components.sort_by(|a, b| b.len().cmp(&a.len()).then(a.cmp(b)));
+    components
+}
+
+/// The separate stretches of territory `faction_id` holds on a map, largest first
+pub fn territories(map: &SurfaceMap, faction_id: Uuid) -> Vec<Vec<HexCoord>> {
+    connected_components(map, map.owned_by(faction_id).map(|tile| tile.coord))
+}
+
+/// Cost for a unit of `domain` to enter `tile`, or `None` if it cannot
+pub fn movement_cost(tile: &Tile, domain: UnitDomain) -> Option<f64> {
+    match domain {
+        UnitDomain::SurfaceGround => match tile.terrain {
+            Terrain::Plains => Some(1.0),
+            Terrain::Highlands | Terrain::Craters | Terrain::Ice => Some(2.0),
+            Terrain::Volcanic => Some(2.5),
+            Terrain::Mountains | Terrain::Canyon => Some(3.0),
+            Terrain::Ocean | Terrain::Atmosphere => None,
+        },
+        UnitDomain::SurfaceAerospace => Some(AEROSPACE_FUEL_COST),
+        UnitDomain::SurfaceMaritimeSurface => (tile.terrain == Terrain::Ocean).then_some(1.0),
+        UnitDomain::SurfaceMaritimeSubsurface => {
+            (tile.terrain == Terrain::Ocean && -tile.elevation >= MIN_SUBMERGED_DEPTH_M).then_some(1.5)
+        }
+        UnitDomain::Space => None,
+    }
+}
+
+/// A route across a surface map
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SurfacePath {
+    /// Every hex on the route, starting with the origin and ending with the destination
+    pub tiles: Vec<HexCoord>,
+    /// Sum of the costs of entering each hex after the origin
+    pub cost: f64,
+    /// Turns needed to walk the route
+    pub turns: u32,
+}
+
+/// Finds the cheapest route from `from` to `to` with A*.
+///
+/// `movement_per_turn` is how much cost the unit can spend each turn; a step
+/// that doesn't fit in what is left of a turn waits for the next one.
+pub fn find_path(
+    map: &SurfaceMap,
+    from: HexCoord,
+    to: HexCoord,
+    domain: UnitDomain,
+    movement_per_turn: f64,
+) -> Option<SurfacePath> {
+    if map.tile(from).is_none() || map.tile(to).and_then(|tile| movement_cost(tile, domain)).is_none() {
+        return None;
+    }
+
+    // Every step costs at least the cheapest terrain and covers at most the
+    // widest neighbour spacing, so this never overestimates
+    let cheapest = min_step_cost(domain);
+    let widest_step = map.grid().min_step_angle() * MAX_STEP_STRETCH;
+    let estimate = |coord: HexCoord| {
+        map.grid().angular_distance(coord, to).map_or(0.0, |angle| angle / widest_step * cheapest)
+    };
+
+    let mut came_from: HashMap<HexCoord, HexCoord> = HashMap::new();
+    let mut costs = HashMap::from([(from, 0.0)]);
+    let mut frontier = BinaryHeap::from([Frontier { coord: from, priority: estimate(from) }]);
+
+    while let Some(Frontier { coord, priority }) = frontier.pop() {
+        let cost = costs[&coord];
+        if coord == to {
+            let mut tiles = vec![to];
+            while let Some(previous) = came_from.get(tiles.last().unwrap()) {
+                tiles.push(*previous);
+            }
+            tiles.reverse();
+            let turns = turns_needed(map, &tiles, domain, movement_per_turn);
+            return Some(SurfacePath { tiles, cost, turns });
+        }
+        if priority > cost + estimate(coord) {
+            // Stale entry; a cheaper route here was already expanded
+            continue;
+        }
+        for (next, next_cost) in steps(map, coord, cost, domain) {
+            if costs.get(&next).is_none_or(|known| next_cost < *known) {
+                costs.insert(next, next_cost);
+                came_from.insert(next, coord);
+                frontier.push(Frontier { coord: next, priority: next_cost + estimate(next) });
+            }
+        }
+    }
+    None
+}
+
+/// Finds every hex reachable from `from` for at most `budget`, with the cheapest cost to get there
+pub fn reachable(map: &SurfaceMap, from: HexCoord, domain: UnitDomain, budget: f64) -> HashMap<HexCoord, f64> {
+    let mut costs = HashMap::new();
+    if map.tile(from).is_none() {
+        return costs;
+    }
+    costs.insert(from, 0.0);
+    let mut frontier = BinaryHeap::from([Frontier { coord: from, priority: 0.0 }]);
+
+    while let Some(Frontier { coord, priority }) = frontier.pop() {
+        if priority > costs[&coord] {
+            continue;
+        }
+        for (next, next_cost) in steps(map, coord, priority, domain) {
+            if next_cost <= budget && costs.get(&next).is_none_or(|known| next_cost < *known) {
+                costs.insert(next, next_cost);
+                frontier.push(Frontier { coord: next, priority: next_cost });
+            }
+        }
+    }
+    costs
+}
+
+/// Neighbours of `coord` the unit can enter, with the total cost of getting there
+fn steps(map: &SurfaceMap, coord: HexCoord, cost: f64, domain: UnitDomain) -> impl Iterator<Item = (HexCoord, f64)> + '_ {
+    map.neighbors(coord).into_iter().filter_map(move |next| {
+        let step = movement_cost(map.tile(next)?, domain)?;
+        Some((next, cost + step))
+    })
+}
+
+fn min_step_cost(domain: UnitDomain) -> f64 {
+    match domain {
+        UnitDomain::SurfaceMaritimeSubsurface => 1.5,
+        _ => 1.0,
+    }
+}
+
+fn turns_needed(map: &SurfaceMap, tiles: &[HexCoord], domain: UnitDomain, movement_per_turn: f64) -> u32 {
+    let mut turns = 0;
+    let mut left = 0.0;
+    for step in tiles.iter().skip(1).filter_map(|coord| movement_cost(map.tile(*coord)?, domain)) {
+        if step > left {
+            // Start a new turn; a step dearer than a whole turn still takes just one
+            turns += 1;
+            left = movement_per_turn;
+        }
+        left -= step;
+    }
+    turns
+}
+
+/// Open-set entry ordered so the lowest priority pops first
+#[derive(Debug, Clone, Copy)]
+struct Frontier {
+    coord: HexCoord,
+    priority: f64,
+}
+
+impl PartialEq for Frontier {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Frontier {}
+
+impl PartialOrd for Frontier {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Frontier {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.priority.total_cmp(&self.priority).then_with(|| other.coord.cmp(&self.coord))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::maps::sphere_grid::SphereGrid;
+
+    fn map() -> SurfaceMap {
+        SurfaceMap::new(Uuid::new_v4(), SphereGrid::new(8), 1000.0, Terrain::Plains)
+    }
+
+    fn paint(map: &mut SurfaceMap, coords: &[HexCoord], terrain: Terrain, elevation: f32) {
+        for coord in coords {
+            let tile = map.tile_mut(*coord).unwrap();
+            tile.terrain = terrain;
+            tile.elevation = elevation;
+        }
+    }
+
+    #[test]
+    fn test_path_matches_dijkstra_cost() {
+        let mut map = map();
+        let from = map.grid().tile_at(0.0, 0.0);
+        let to = map.grid().tile_at(20.0, 60.0);
+        let ridge: Vec<HexCoord> = map.grid().tiles().filter(|c| map.lat_lon(*c).unwrap().1.abs() < 5.0).collect();
+        paint(&mut map, &ridge, Terrain::Mountains, 2000.0);
+
+        let path = find_path(&map, from, to, UnitDomain::SurfaceGround, 4.0).unwrap();
+        assert_eq!(path.tiles.first(), Some(&from));
+        assert_eq!(path.tiles.last(), Some(&to));
+        for pair in path.tiles.windows(2) {
+            assert!(map.neighbors(pair[0]).contains(&pair[1]));
+        }
+        let costs = reachable(&map, from, UnitDomain::SurfaceGround, f64::MAX);
+        assert!((path.cost - costs[&to]).abs() < 1e-9);
+        assert!(path.turns >= (path.cost / 4.0).ceil() as u32);
+    }
+
+    #[test]
+    fn test_domains_respect_water() {
+        let mut map = map();
+        let start = map.grid().tile_at(0.0, 0.0);
+        let mut sea = map.neighbors(start);
+        sea.push(start);
+        paint(&mut map, &sea, Terrain::Ocean, -50.0);
+
+        // Ground units are stranded on water, boats can't leave it
+        assert!(find_path(&map, start, map.grid().tile_at(30.0, 30.0), UnitDomain::SurfaceGround, 3.0).is_none());
+        let boat = reachable(&map, start, UnitDomain::SurfaceMaritimeSurface, 100.0);
+        assert_eq!(boat.len(), sea.len());
+
+        // The sea is too shallow for submarines until it is deepened
+        assert_eq!(reachable(&map, start, UnitDomain::SurfaceMaritimeSubsurface, 100.0).len(), 1);
+        paint(&mut map, &sea, Terrain::Ocean, -500.0);
+        assert_eq!(reachable(&map, start, UnitDomain::SurfaceMaritimeSubsurface, 100.0).len(), sea.len());
+
+        // Aircraft fly over everything for fuel alone
+        let flight = find_path(&map, start, map.grid().tile_at(30.0, 30.0), UnitDomain::SurfaceAerospace, 5.0).unwrap();
+        assert_eq!(flight.cost, (flight.tiles.len() - 1) as f64 * AEROSPACE_FUEL_COST);
+        assert_eq!(reachable(&map, start, UnitDomain::Space, 100.0).len(), 1);
+    }
+
+    #[test]
+    fn test_turns_needed() {
+        let mut map = map();
+        let from = HexCoord::new(0, 0);
+        let to = map.grid().tile_at(0.0, 36.0);
+        let path = find_path(&map, from, to, UnitDomain::SurfaceGround, 2.0).unwrap();
+        assert_eq!(path.turns as usize, (path.tiles.len() - 1).div_ceil(2));
+
+        // A mountain costing more than a whole turn still takes just one
+        let everywhere: Vec<HexCoord> = map.grid().tiles().collect();
+        paint(&mut map, &everywhere, Terrain::Mountains, 0.0);
+        let path = find_path(&map, from, to, UnitDomain::SurfaceGround, 2.0).unwrap();
+        assert_eq!(path.turns as usize, path.tiles.len() - 1);
+    }
+
+    #[test]
+    fn test_flat_areas_and_lines() {
+        let centre = HexCoord::new(2, -1);
+        assert_eq!(within_radius(&FlatPlane, centre, 2).len(), 19);
+        for radius in 1..=3 {
+            let ring = ring(&FlatPlane, centre, radius);
+            assert_eq!(ring.len(), 6 * radius as usize);
+            assert!(ring.iter().all(|coord| distance(centre, *coord) == radius as i32));
+            let bearings: Vec<f64> = ring.iter().map(|coord| bearing(&FlatPlane, centre, *coord).unwrap()).collect();
+            assert!(bearings.windows(2).all(|pair| pair[0] <= pair[1]));
+        }
+        let spiral = spiral(&FlatPlane, centre, 2);
+        assert_eq!(spiral.len(), 19);
+        assert_eq!(spiral[0], centre);
+        assert_eq!(spiral[1..7], ring(&FlatPlane, centre, 1)[..]);
+
+        for target in [HexCoord::new(7, -3), HexCoord::new(5, 3), HexCoord::new(-4, 0), HexCoord::new(2, 6)] {
+            let line = line(&FlatPlane, centre, target);
+            assert_eq!(line.len() as i32, distance(centre, target) + 1);
+            assert_eq!(line.last(), Some(&target));
+            assert!(line.windows(2).all(|pair| distance(pair[0], pair[1]) == 1));
+        }
+    }
+
+    #[test]
+    fn test_sphere_areas_and_lines() {
+        let map = map();
+        let from = map.grid().tile_at(10.0, 20.0);
+        let ring = ring(&map, from, 1);
+        assert_eq!(ring.len(), map.neighbors(from).len());
+        assert_eq!(spiral(&map, from, 2).len(), within_radius(&map, from, 2).len());
+
+        // Lines run across faces, and even over the pole
+        for to in [map.grid().tile_at(-30.0, 140.0), map.grid().tile_at(70.0, -160.0)] {
+            let line = line(&map, from, to);
+            assert_eq!(line.last(), Some(&to));
+            assert!(line.windows(2).all(|pair| map.neighbors(pair[0]).contains(&pair[1])));
+            let cheapest = find_path(&map, from, to, UnitDomain::SurfaceAerospace, 100.0).unwrap();
+            assert!(line.len() <= cheapest.tiles.len() + 2, "{} vs {}", line.len(), cheapest.tiles.len());
+        }
+    }
+
+    #[test]
+    fn test_line_of_sight() {
+        // A ridge on the plane hides what lies behind it from the ground, not from a tower
+        let from = HexCoord::new(0, 0);
+        let to = HexCoord::new(4, 0);
+        let ridge = |coord: HexCoord| Some(if coord.x == 2 { 500.0 } else { 0.0 });
+        assert!(line_of_sight(&FlatPlane, from, to, 2.0, |_| Some(0.0)));
+        assert!(!line_of_sight(&FlatPlane, from, to, 2.0, ridge));
+        assert!(line_of_sight(&FlatPlane, from, to, 2000.0, ridge));
+
+        // On a sphere the horizon hides hexes two steps away unless the eye is high
+        let map = map();
+        let from = map.grid().tile_at(0.0, 0.0);
+        let beyond = ring(&map, from, 2)[0];
+        let ground = |coord: HexCoord| map.tile(coord).map(|tile| tile.elevation);
+        assert!(line_of_sight(&map, from, map.neighbors(from)[0], 2.0, ground));
+        assert!(!line_of_sight(&map, from, beyond, 2.0, ground));
+        assert!(line_of_sight(&map, from, beyond, 30_000.0, ground));
+    }
+
+    #[test]
+    fn test_territory_components() {
+        let mut map = map();
+        let faction = Uuid::new_v4();
+        let home = map.grid().tile_at(0.0, 0.0);
+        let colony = map.grid().tile_at(0.0, 90.0);
+        let outpost = map.grid().tile_at(-60.0, -90.0);
+        let mut claimed = spiral(&map, home, 1);
+        claimed.extend(spiral(&map, colony, 1));
+        claimed.push(outpost);
+        for coord in &claimed {
+            map.tile_mut(*coord).unwrap().owner = Some(faction);
+        }
+
+        let territories = territories(&map, faction);
+        let sizes: Vec<usize> = territories.iter().map(|t| t.len()).collect();
+        assert_eq!(sizes, [spiral(&map, home, 1).len(), spiral(&map, colony, 1).len(), 1]);
+        assert_eq!(territories[2], [outpost]);
+
+        let region = flood_fill(&map, home, |coord| map.tile(coord).unwrap().owner == Some(faction));
+        assert_eq!(region.len(), spiral(&map, home, 1).len());
+        assert!(flood_fill(&map, map.grid().tile_at(45.0, 45.0), |coord| claimed.contains(&coord)).is_empty());
+    }
+}
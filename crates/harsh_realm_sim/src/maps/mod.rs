@@ -1,4 +1,6 @@
+pub mod environment;
 pub mod hex_utils;
 pub mod location;
+pub mod sphere_grid;
 pub mod surface_map;
 pub mod tile;
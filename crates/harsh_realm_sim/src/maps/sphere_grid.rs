@@ -0,0 +1,371 @@
+// src/maps/sphere_grid.rs
+// Goldberg-polyhedron hex topology used by `SurfaceMap`.
+//
+// The icosahedron is unfolded into ten rhombi, five around the north pole and
+// five around the south pole, each covered by an n x n patch of an axial hex
+// lattice.  Together with the two poles that gives the 10n² + 2 tiles of a
+// Goldberg polyhedron; the twelve icosahedron vertices (the poles and the
+// corner each rhombus owns) are the pentagons.
+//
+// Tiles are keyed by `hex2d` coordinates in the unfolded net: rhombus k of the
+// northern ring has its lattice origin at (kn, kn) and the southern rhombus
+// below it at (kn, (k + 1)n).  Inside a rhombus `hex2d` neighbours are exact;
+// across rhombus edges coordinates are re-expressed in the adjacent rhombus.
+
+use hex2d::Coordinate as HexCoord;
+use nalgebra::Vector3;
+use serde::{Deserialize, Serialize};
+
+use crate::maps::hex_utils;
+
+/// Nominal width of one surface hex; Earth's 160-hex equator gives 250 km
+pub const HEX_WIDTH_KM: f64 = 250.0;
+
+/// Upper bound on how much further apart neighbours are than [`SphereGrid::min_step_angle`];
+/// hexes stretch most midway between pentagons
+pub const MAX_STEP_STRETCH: f64 = 1.5;
+
+/// Smallest possible grid: the 12 pentagons of a dodecahedron
+const MIN_TILE_COUNT: u32 = 12;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Rhombus {
+    south: bool,
+    /// 0..5, increasing eastwards
+    k: i32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Cell {
+    NorthPole,
+    SouthPole,
+    /// Lattice point (i, j) of a rhombus, owned when 1 <= i <= n and 0 <= j < n
+    Face { rhombus: Rhombus, i: i32, j: i32 },
+}
+
+/// Spherical hex grid with `10 * frequency² + 2` tiles
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SphereGrid {
+    /// Hexes along each edge of the underlying icosahedron
+    pub frequency: u32,
+}
+
+impl SphereGrid {
+    pub fn new(frequency: u32) -> Self {
+        Self { frequency: frequency.max(1) }
+    }
+
+    /// Picks the grid whose tile count is closest to `tile_count`, such as the TH data column
+    pub fn from_tile_count(tile_count: u32) -> Self {
+        let tile_count = tile_count.max(MIN_TILE_COUNT);
+        Self::new(((tile_count - 2) as f64 / 10.0).sqrt().round() as u32)
+    }
+
+    /// Sizes the grid so each tile covers about one [`HEX_WIDTH_KM`]-wide hex
+    pub fn from_diameter(diameter_km: f64) -> Self {
+        let surface_area = std::f64::consts::PI * diameter_km * diameter_km;
+        let hex_area = 3f64.sqrt() / 2.0 * HEX_WIDTH_KM * HEX_WIDTH_KM;
+        Self::from_tile_count((surface_area / hex_area).round().min(u32::MAX as f64) as u32)
+    }
+
+    pub fn tile_count(&self) -> usize {
+        let n = self.frequency as usize;
+        10 * n * n + 2
+    }
+
+    /// Every tile coordinate on the grid
+    pub fn tiles(&self) -> impl Iterator<Item = HexCoord> + '_ {
+        let n = self.n();
+        let faces = rhombi().flat_map(move |rhombus| {
+            (1..=n).flat_map(move |i| (0..n).map(move |j| Cell::Face { rhombus, i, j }))
+        });
+        [Cell::NorthPole, Cell::SouthPole]
+            .into_iter()
+            .chain(faces)
+            .map(move |cell| self.encode(cell))
+    }
+
+    pub fn contains(&self, coord: HexCoord) -> bool {
+        self.decode(coord).is_some()
+    }
+
+    /// Gets the tiles sharing an edge with `coord`: five for pentagons, six otherwise
+    pub fn neighbors(&self, coord: HexCoord) -> Vec<HexCoord> {
+        let n = self.n();
+        let cells: Vec<Cell> = match self.decode(coord) {
+            None => return Vec::new(),
+            Some(Cell::NorthPole) => rhombi()
+                .filter(|r| !r.south)
+                .map(|rhombus| Cell::Face { rhombus, i: 1, j: 0 })
+                .collect(),
+            Some(Cell::SouthPole) => rhombi()
+                .filter(|r| r.south)
+                .map(|rhombus| Cell::Face { rhombus, i: n, j: n - 1 })
+                .collect(),
+            Some(Cell::Face { rhombus, i, j }) => hex_utils::neighbors(HexCoord::new(i, j))
+                .iter()
+                .map(|next| self.canonical(rhombus, next.x, next.y))
+                .collect(),
+        };
+
+        let mut neighbors = Vec::with_capacity(6);
+        for cell in cells {
+            let next = self.encode(cell);
+            if next != coord && !neighbors.contains(&next) {
+                neighbors.push(next);
+            }
+        }
+        neighbors
+    }
+
+    pub fn is_pentagon(&self, coord: HexCoord) -> bool {
+        self.neighbors(coord).len() == 5
+    }
+
+    /// Unit vector from the body's centre through the centre of a tile
+    pub fn position(&self, coord: HexCoord) -> Option<Vector3<f64>> {
+        let n = self.n() as f64;
+        let position = match self.decode(coord)? {
+            Cell::NorthPole => Vector3::z(),
+            Cell::SouthPole => -Vector3::z(),
+            Cell::Face { rhombus, i, j } => {
+                let [top, left, bottom, right] = corners(rhombus);
+                let (s, t) = (i as f64 / n, j as f64 / n);
+                let point = if s + t <= 1.0 {
+                    top + (left - top) * s + (right - top) * t
+                } else {
+                    bottom + (right - bottom) * (1.0 - s) + (left - bottom) * (1.0 - t)
+                };
+                point.normalize()
+            }
+        };
+        Some(position)
+    }
+
+    /// Latitude and longitude of a tile centre in degrees; longitude is in (-180, 180]
+    pub fn lat_lon(&self, coord: HexCoord) -> Option<(f64, f64)> {
+        let p = self.position(coord)?;
+        Some((p.z.clamp(-1.0, 1.0).asin().to_degrees(), p.y.atan2(p.x).to_degrees()))
+    }
+
+    /// Gets the tile containing the point at `latitude`, `longitude` (degrees)
+    pub fn tile_at(&self, latitude: f64, longitude: f64) -> HexCoord {
+        let target = unit_vector(latitude, longitude);
+        let n = self.n() as f64;
+
+        // The containing icosahedron face is the one whose centre is nearest
+        let (rhombus, lower, [a, b, c]) = rhombi()
+            .flat_map(|rhombus| {
+                let [top, left, bottom, right] = corners(rhombus);
+                [(rhombus, false, [top, left, right]), (rhombus, true, [bottom, right, left])]
+            })
+            .max_by(|x, y| {
+                let centre = |f: &[Vector3<f64>; 3]| (f[0] + f[1] + f[2]).dot(&target);
+                centre(&x.2).total_cmp(&centre(&y.2))
+            })
+            .expect("an icosahedron has faces");
+
+        // Project onto the flat face and read off lattice coordinates
+        let normal = (a + b + c).normalize();
+        let projected = target * (a.dot(&normal) / target.dot(&normal));
+        let (u, v) = solve_plane(projected - a, b - a, c - a);
+        let (s, t) = if lower { (1.0 - v, 1.0 - u) } else { (u, v) };
+        let guess = HexCoord::<i32>::nearest(s * n, t * n);
+        let mut best = self.encode(self.canonical(rhombus, guess.x, guess.y));
+
+        // Rounding near rhombus edges can land one tile off; walk to the true nearest
+        let closeness = |coord: HexCoord| self.position(coord).map_or(f64::MIN, |p| p.dot(&target));
+        loop {
+            let next = self
+                .neighbors(best)
+                .into_iter()
+                .max_by(|x, y| closeness(*x).total_cmp(&closeness(*y)));
+            match next {
+                Some(next) if closeness(next) > closeness(best) => best = next,
+                _ => return best,
+            }
+        }
+    }
+
+    /// Smallest angle between neighbouring tile centres, found around the poles.
+    /// Neighbours elsewhere are up to [`MAX_STEP_STRETCH`] times further apart.
+    pub fn min_step_angle(&self) -> f64 {
+        self.angular_distance(HexCoord::new(0, 0), self.encode(Cell::Face { rhombus: north(0), i: 1, j: 0 }))
+            .expect("the pole and its neighbour are on the grid")
+    }
+
+    /// Angle between two tile centres as seen from the body's centre, in radians
+    pub fn angular_distance(&self, a: HexCoord, b: HexCoord) -> Option<f64> {
+        let (a, b) = (self.position(a)?, self.position(b)?);
+        Some(a.dot(&b).clamp(-1.0, 1.0).acos())
+    }
+
+    /// Great-circle distance between two tile centres on a body of `radius_km`
+    pub fn distance_km(&self, a: HexCoord, b: HexCoord, radius_km: f64) -> Option<f64> {
+        self.angular_distance(a, b).map(|angle| angle * radius_km)
+    }
+
+    fn n(&self) -> i32 {
+        self.frequency as i32
+    }
+
+    fn encode(&self, cell: Cell) -> HexCoord {
+        let n = self.n();
+        match cell {
+            Cell::NorthPole => HexCoord::new(0, 0),
+            Cell::SouthPole => HexCoord::new(n, 2 * n),
+            Cell::Face { rhombus, i, j } => {
+                let row_offset = if rhombus.south { (rhombus.k + 1) * n } else { rhombus.k * n };
+                HexCoord::new(i + rhombus.k * n, j + row_offset)
+            }
+        }
+    }
+
+    fn decode(&self, coord: HexCoord) -> Option<Cell> {
+        let n = self.n();
+        if coord == HexCoord::new(0, 0) {
+            return Some(Cell::NorthPole);
+        }
+        if coord == HexCoord::new(n, 2 * n) {
+            return Some(Cell::SouthPole);
+        }
+        if coord.x < 1 || coord.x > 5 * n {
+            return None;
+        }
+        let k = (coord.x - 1) / n;
+        let i = coord.x - k * n;
+        let dy = coord.y - k * n;
+        match dy {
+            _ if (0..n).contains(&dy) => Some(Cell::Face { rhombus: Rhombus { south: false, k }, i, j: dy }),
+            _ if (n..2 * n).contains(&dy) => Some(Cell::Face { rhombus: Rhombus { south: true, k }, i, j: dy - n }),
+            _ => None,
+        }
+    }
+
+    /// Resolves a lattice point given relative to `rhombus`, possibly on or just
+    /// past its edges, to the cell that owns it
+    fn canonical(&self, mut rhombus: Rhombus, mut i: i32, mut j: i32) -> Cell {
+        let n = self.n();
+        // Crossing from a pentagon corner can take a few hops to settle
+        for _ in 0..8 {
+            if !rhombus.south && i == 0 && j == 0 {
+                return Cell::NorthPole;
+            }
+            if rhombus.south && i == n && j == n {
+                return Cell::SouthPole;
+            }
+            if (1..=n).contains(&i) && (0..n).contains(&j) {
+                return Cell::Face { rhombus, i, j };
+            }
+
+            let k = rhombus.k;
+            (rhombus, i, j) = match (rhombus.south, i, j) {
+                // Northern rhombi meet at the north pole, rotated 72° apart
+                (false, i, j) if i <= 0 => (north(k + 1), i + j, -i),
+                (false, i, j) if j < 0 => (north(k - 1), -j, i + j),
+                // and sit on the southern ring, shifted along the unfolded band
+                (false, i, j) if i > n => (south(k - 1), i - n, j),
+                (false, i, j) => (south(k), i, j - n),
+                (true, i, j) if i <= 0 => (north(k + 1), i + n, j),
+                (true, i, j) if j < 0 => (north(k), i, j + n),
+                // Southern rhombi meet at the south pole
+                (true, i, j) if j >= n => (south(k + 1), 2 * n - j, i + j - n),
+                (true, i, j) => (south(k - 1), i + j - n, 2 * n - i),
+            };
+        }
+        unreachable!("lattice point ({}, {}) did not settle on a tile", i, j)
+    }
+}
+
+fn north(k: i32) -> Rhombus {
+    Rhombus { south: false, k: k.rem_euclid(5) }
+}
+
+fn south(k: i32) -> Rhombus {
+    Rhombus { south: true, k: k.rem_euclid(5) }
+}
+
+fn rhombi() -> impl Iterator<Item = Rhombus> {
+    (0..5).map(north).chain((0..5).map(south))
+}
+
+fn unit_vector(latitude: f64, longitude: f64) -> Vector3<f64> {
+    let (lat, lon) = (latitude.to_radians(), longitude.to_radians());
+    Vector3::new(lat.cos() * lon.cos(), lat.cos() * lon.sin(), lat.sin())
+}
+
+/// Top, left, bottom and right icosahedron vertices of a rhombus
+fn corners(rhombus: Rhombus) -> [Vector3<f64>; 4] {
+    // The two rings of five vertices sit at ±atan(1/2) latitude, offset by 36°
+    let ring_latitude = 0.5f64.atan().to_degrees();
+    let upper = |k: i32| unit_vector(ring_latitude, 72.0 * k as f64);
+    let lower = |k: i32| unit_vector(-ring_latitude, 72.0 * k as f64 + 36.0);
+    let k = rhombus.k;
+    if rhombus.south {
+        [upper(k + 1), lower(k), -Vector3::z(), lower(k + 1)]
+    } else {
+        [Vector3::z(), upper(k), lower(k), upper(k + 1)]
+    }
+}
+
+/// Solves `d = x * e1 + y * e2` for a point `d` in the plane spanned by `e1` and `e2`
+fn solve_plane(d: Vector3<f64>, e1: Vector3<f64>, e2: Vector3<f64>) -> (f64, f64) {
+    let (a, b, c) = (e1.dot(&e1), e1.dot(&e2), e2.dot(&e2));
+    let (p, q) = (d.dot(&e1), d.dot(&e2));
+    let det = a * c - b * b;
+    ((p * c - q * b) / det, (q * a - p * b) / det)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    #[test]
+    fn test_tile_count_and_pentagons() {
+        for frequency in 1..6 {
+            let grid = SphereGrid::new(frequency);
+            let tiles: HashSet<HexCoord> = grid.tiles().collect();
+            assert_eq!(tiles.len(), grid.tile_count());
+            assert_eq!(tiles.iter().filter(|t| grid.is_pentagon(**t)).count(), 12);
+            assert!(tiles.iter().all(|t| (5..=6).contains(&grid.neighbors(*t).len())));
+        }
+    }
+
+    #[test]
+    fn test_neighbors_are_symmetric_and_close() {
+        let grid = SphereGrid::new(7);
+        let spacing = grid.min_step_angle();
+        for tile in grid.tiles() {
+            for next in grid.neighbors(tile) {
+                assert!(grid.contains(next));
+                assert!(grid.neighbors(next).contains(&tile), "{:?} -> {:?} is one-way", tile, next);
+                let angle = grid.angular_distance(tile, next).unwrap();
+                assert!(angle < spacing * MAX_STEP_STRETCH && angle > spacing * 0.999, "{:?} -> {:?}", tile, next);
+            }
+        }
+    }
+
+    #[test]
+    fn test_lat_lon_round_trip() {
+        let grid = SphereGrid::new(9);
+        for tile in grid.tiles() {
+            let (lat, lon) = grid.lat_lon(tile).unwrap();
+            assert_eq!(grid.tile_at(lat, lon), tile);
+        }
+        assert_eq!(grid.tile_at(90.0, 0.0), HexCoord::new(0, 0));
+        assert_eq!(grid.tile_at(-90.0, 123.0), HexCoord::new(9, 18));
+    }
+
+    #[test]
+    fn test_sizing() {
+        // Earth's TH column is 8828 tiles
+        assert_eq!(SphereGrid::from_tile_count(8828).frequency, 30);
+        assert_eq!(SphereGrid::from_diameter(12742.0).frequency, 31);
+        assert_eq!(SphereGrid::from_diameter(22.0).tile_count(), 12);
+
+        let grid = SphereGrid::new(30);
+        let quarter = grid.distance_km(grid.tile_at(0.0, 0.0), grid.tile_at(0.0, 90.0), 6371.0).unwrap();
+        assert!((quarter - 10_007.0).abs() < 300.0, "{}", quarter);
+    }
+}
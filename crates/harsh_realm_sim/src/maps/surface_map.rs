@@ -1,6 +1,137 @@
-use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use hex2d::Coordinate as HexCoord;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use uuid::Uuid;
 
-#[derive(Debug,Clone,Serialize,Deserialize)]
+use crate::maps::sphere_grid::SphereGrid;
+use crate::maps::tile::{Terrain, Tile};
+
+/// Hex grid of tiles covering the surface of one celestial body
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct SurfaceMap {
-    
-}
\ No newline at end of file
+    pub body_id: Uuid,
+    grid: SphereGrid,
+    /// Mean radius of the body, used for surface distances
+    pub radius_km: f64,
+    #[serde(serialize_with = "serialize_tiles", deserialize_with = "deserialize_tiles")]
+    tiles: HashMap<HexCoord, Tile>,
+}
+
+impl SurfaceMap {
+    /// Creates a map on `grid` with every tile set to `terrain`
+    pub fn new(body_id: Uuid, grid: SphereGrid, radius_km: f64, terrain: Terrain) -> Self {
+        let tiles = grid.tiles().map(|coord| (coord, Tile::new(coord, terrain))).collect();
+        Self { body_id, grid, radius_km, tiles }
+    }
+
+    pub fn grid(&self) -> SphereGrid {
+        self.grid
+    }
+
+    /// Latitude and longitude of a hex centre in degrees
+    pub fn lat_lon(&self, coord: HexCoord) -> Option<(f64, f64)> {
+        self.grid.lat_lon(coord)
+    }
+
+    /// Gets the tile containing the point at `latitude`, `longitude` (degrees)
+    pub fn tile_at(&self, latitude: f64, longitude: f64) -> Option<&Tile> {
+        self.tiles.get(&self.grid.tile_at(latitude, longitude))
+    }
+
+    /// Coordinates of the tiles bordering `coord`, including across faces of the sphere
+    pub fn neighbors(&self, coord: HexCoord) -> Vec<HexCoord> {
+        self.grid.neighbors(coord)
+    }
+
+    /// Great-circle distance between two hex centres in km
+    pub fn distance_km(&self, a: HexCoord, b: HexCoord) -> Option<f64> {
+        self.grid.distance_km(a, b, self.radius_km)
+    }
+
+    pub fn contains(&self, coord: HexCoord) -> bool {
+        self.tiles.contains_key(&coord)
+    }
+
+    pub fn tile(&self, coord: HexCoord) -> Option<&Tile> {
+        self.tiles.get(&coord)
+    }
+
+    pub fn tile_mut(&mut self, coord: HexCoord) -> Option<&mut Tile> {
+        self.tiles.get_mut(&coord)
+    }
+
+    pub fn tiles(&self) -> impl Iterator<Item = &Tile> {
+        self.tiles.values()
+    }
+
+    pub fn tiles_mut(&mut self) -> impl Iterator<Item = &mut Tile> {
+        self.tiles.values_mut()
+    }
+
+    pub fn len(&self) -> usize {
+        self.tiles.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.tiles.is_empty()
+    }
+
+    /// Tiles controlled by `faction_id`
+    pub fn owned_by(&self, faction_id: Uuid) -> impl Iterator<Item = &Tile> {
+        self.tiles.values().filter(move |tile| tile.owner == Some(faction_id))
+    }
+}
+
+// JSON object keys must be strings, so tiles are stored as a list and re-keyed on load
+fn serialize_tiles<S: Serializer>(tiles: &HashMap<HexCoord, Tile>, serializer: S) -> Result<S::Ok, S::Error> {
+    let mut tiles: Vec<&Tile> = tiles.values().collect();
+    tiles.sort_by_key(|tile| (tile.coord.y, tile.coord.x));
+    serializer.collect_seq(tiles)
+}
+
+fn deserialize_tiles<'de, D: Deserializer<'de>>(deserializer: D) -> Result<HashMap<HexCoord, Tile>, D::Error> {
+    let tiles = Vec::<Tile>::deserialize(deserializer)?;
+    Ok(tiles.into_iter().map(|tile| (tile.coord, tile)).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn mars() -> SurfaceMap {
+        SurfaceMap::new(Uuid::new_v4(), SphereGrid::new(16), 3389.5, Terrain::Plains)
+    }
+
+    #[test]
+    fn test_map_covers_the_grid() {
+        let map = mars();
+        assert_eq!(map.len(), map.grid().tile_count());
+        assert!(map.contains(HexCoord::new(0, 0)));
+        assert!(!map.contains(HexCoord::new(0, 60)));
+    }
+
+    #[test]
+    fn test_neighbors_are_about_a_hex_apart() {
+        let map = mars();
+        let jezero = map.tile_at(18.4, 77.5).unwrap().coord;
+        let (lat, lon) = map.lat_lon(jezero).unwrap();
+        assert!((lat - 18.4).abs() < 5.0 && (lon - 77.5).abs() < 5.0);
+        for next in map.neighbors(jezero) {
+            let km = map.distance_km(jezero, next).unwrap();
+            assert!((200.0..=450.0).contains(&km), "{}", km);
+        }
+    }
+
+    #[test]
+    fn test_serializes_tiles_as_a_list() {
+        let mut map = SurfaceMap::new(Uuid::new_v4(), SphereGrid::from_diameter(1000.0), 500.0, Terrain::Craters);
+        let owner = Uuid::new_v4();
+        map.tile_mut(HexCoord::new(0, 0)).unwrap().owner = Some(owner);
+
+        let json = serde_json::to_value(&map).unwrap();
+        assert!(json["tiles"].is_array());
+        let loaded: SurfaceMap = serde_json::from_value(json).unwrap();
+        assert_eq!(loaded, map);
+        assert_eq!(loaded.owned_by(owner).count(), 1);
+    }
+}
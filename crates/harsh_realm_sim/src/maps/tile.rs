@@ -1,7 +1,60 @@
+use hex2d::Coordinate as HexCoord;
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
-#[derive(Default,Debug,Serialize,Deserialize)]
+use crate::maps::environment::Weather;
+use crate::resources::deposit::Deposit;
+
+/// Broad surface type of a hex
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Terrain {
+    #[default]
+    Plains,
+    Highlands,
+    Mountains,
+    Craters,
+    Canyon,
+    Volcanic,
+    Ice,
+    Ocean,
+    /// No solid surface, as on a gas giant
+    Atmosphere,
+}
+
+/// One hex of a body's surface
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Tile {
     pub id: Uuid,
+    pub coord: HexCoord,
+    pub terrain: Terrain,
+    /// Height above the body's reference level, in metres
+    pub elevation: f32,
+    /// The faction controlling this hex
+    pub owner: Option<Uuid>,
+    /// Set while no faction has a clear hold on the hex; nobody extracts from contested hexes
+    #[serde(default)]
+    pub contested: bool,
+    /// The settlement or installation built here; one per hex
+    pub structure_id: Option<Uuid>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub deposits: Vec<Deposit>,
+    /// This turn's weather; the rest of the environment follows from the body and the tile's height
+    #[serde(default)]
+    pub weather: Weather,
+}
+
+impl Tile {
+    pub fn new(coord: HexCoord, terrain: Terrain) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            coord,
+            terrain,
+            elevation: 0.0,
+            owner: None,
+            contested: false,
+            structure_id: None,
+            deposits: Vec::new(),
+            weather: Weather::Calm,
+        }
+    }
 }
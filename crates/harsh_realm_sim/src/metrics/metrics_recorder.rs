@@ -0,0 +1,118 @@
+use std::collections::BTreeMap;
+use std::io::Write;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::faction::faction_stats::FactionStats;
+use crate::game_state::GameState;
+use crate::metrics::turn_metrics::{FactionMetrics, TurnMetrics};
+
+/// Keeps a time series of per-turn metrics for balancing and charting
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct MetricsRecorder {
+    series: Vec<TurnMetrics>,
+}
+
+impl MetricsRecorder {
+    /// Measures the state at the end of a turn and appends it to the series.
+    ///
+    /// Recording a turn that is already in the series (e.g. after a rewind) replaces
+    /// it and everything after it.
+    pub fn record(&mut self, state: &GameState) {
+        let turn = state.simulation.current_turn;
+        if let Some(index) = self.series.iter().position(|m| m.turn >= turn) {
+            self.series.truncate(index);
+        }
+        let previous = self.series.last();
+
+        let mut factions: Vec<FactionMetrics> = FactionStats::collect(&state.simulation)
+            .into_iter()
+            .map(|(faction_id, stats)| {
+                let previous_stocks = previous
+                    .and_then(|m| m.factions.iter().find(|f| f.faction_id == faction_id))
+                    .map(|f| &f.stocks);
+                let stocks: BTreeMap<_, _> = stats.stockpiles.into_iter().collect();
+
+                // With no earlier turn to compare against there is no flow to report
+                let mut flows = BTreeMap::new();
+                if let Some(previous_stocks) = previous_stocks {
+                    for resource in stocks.keys().chain(previous_stocks.keys()) {
+                        let now = stocks.get(resource).copied().unwrap_or(0) as i64;
+                        let before = previous_stocks.get(resource).copied().unwrap_or(0) as i64;
+                        flows.insert(resource.clone(), now - before);
+                    }
+                }
+
+                FactionMetrics {
+                    faction_id,
+                    name: state.simulation.factions[&faction_id].name().to_string(),
+                    treasury: stats.treasury,
+                    population: stats.population.into_iter().collect(),
+                    stocks,
+                    flows,
+                    settlements: stats.settlements,
+                    installations: stats.installations,
+                    spacecraft: stats.spacecraft,
+                    fleet_strength: stats.fleet_strength,
+                }
+            })
+            .collect();
+        factions.sort_by(|a, b| a.name.cmp(&b.name).then(a.faction_id.cmp(&b.faction_id)));
+
+        self.series.push(TurnMetrics { turn, date: state.get_game_date(), factions });
+    }
+
+    /// Drops every turn after `turn`, e.g. once those turns have been rewound
+    pub fn truncate_after(&mut self, turn: u64) {
+        self.series.retain(|metrics| metrics.turn <= turn);
+    }
+
+    pub fn series(&self) -> &[TurnMetrics] {
+        &self.series
+    }
+
+    pub fn latest(&self) -> Option<&TurnMetrics> {
+        self.series.last()
+    }
+
+    /// One faction's metrics over time, as `(turn, metrics)` pairs
+    pub fn faction_series(&self, faction_id: Uuid) -> impl Iterator<Item = (u64, &FactionMetrics)> {
+        self.series.iter().filter_map(move |m| {
+            m.factions
+                .iter()
+                .find(|f| f.faction_id == faction_id)
+                .map(|f| (m.turn, f))
+        })
+    }
+
+    pub fn clear(&mut self) {
+        self.series.clear();
+    }
+
+    /// Writes the series as a JSON array of turns
+    pub fn write_json<W: Write>(&self, writer: W) -> std::io::Result<()> {
+        serde_json::to_writer_pretty(writer, &self.series)?;
+        Ok(())
+    }
+
+    /// Writes the series as long-format CSV with one row per turn, faction and metric
+    pub fn write_csv<W: Write>(&self, writer: W) -> std::io::Result<()> {
+        let mut csv_writer = csv::Writer::from_writer(writer);
+        csv_writer.write_record(["turn", "date", "faction_id", "faction", "metric", "value"])?;
+        for turn_metrics in &self.series {
+            for faction in &turn_metrics.factions {
+                for (metric, value) in faction.values() {
+                    csv_writer.write_record([
+                        turn_metrics.turn.to_string(),
+                        turn_metrics.date.to_string(),
+                        faction.faction_id.to_string(),
+                        faction.name.clone(),
+                        metric,
+                        value.to_string(),
+                    ])?;
+                }
+            }
+        }
+        csv_writer.flush()
+    }
+}
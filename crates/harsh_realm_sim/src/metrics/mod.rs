@@ -0,0 +1,2 @@
+pub mod metrics_recorder;
+pub mod turn_metrics;
@@ -0,0 +1,50 @@
+use std::collections::BTreeMap;
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::population::person_type::PersonType;
+use crate::resources::resource_type::ResourceType;
+
+/// One faction's measurements at the end of a turn
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FactionMetrics {
+    pub faction_id: Uuid,
+    pub name: String,
+    pub treasury: f64,
+    pub population: BTreeMap<PersonType, u64>,
+    /// Stockpiled resources across settlements, installations and cargo holds
+    pub stocks: BTreeMap<ResourceType, u64>,
+    /// Change in each stock since the previous recorded turn; empty on the faction's first one
+    pub flows: BTreeMap<ResourceType, i64>,
+    pub settlements: u32,
+    pub installations: u32,
+    pub spacecraft: u32,
+    pub fleet_strength: u32,
+}
+
+impl FactionMetrics {
+    /// Flattens the metrics into `(name, value)` pairs, e.g. `("stock.Water", 12.0)`
+    pub fn values(&self) -> Vec<(String, f64)> {
+        let mut values = vec![
+            ("treasury".to_string(), self.treasury),
+            ("settlements".to_string(), self.settlements as f64),
+            ("installations".to_string(), self.installations as f64),
+            ("spacecraft".to_string(), self.spacecraft as f64),
+            ("fleet_strength".to_string(), self.fleet_strength as f64),
+        ];
+        values.extend(self.population.iter().map(|(p, n)| (format!("population.{:?}", p), *n as f64)));
+        values.extend(self.stocks.iter().map(|(r, n)| (format!("stock.{:?}", r), *n as f64)));
+        values.extend(self.flows.iter().map(|(r, n)| (format!("flow.{:?}", r), *n as f64)));
+        values
+    }
+}
+
+/// Everything recorded at the end of one turn
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TurnMetrics {
+    pub turn: u64,
+    pub date: NaiveDate,
+    /// Sorted by faction name
+    pub factions: Vec<FactionMetrics>,
+}
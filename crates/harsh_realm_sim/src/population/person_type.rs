@@ -1,6 +1,6 @@
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug,Clone,Serialize,Deserialize,Eq,PartialEq,Hash)]
+#[derive(Debug,Clone,Serialize,Deserialize,Eq,PartialEq,Hash,PartialOrd,Ord)]
 pub enum PersonType {
     Colonist,
     Worker,
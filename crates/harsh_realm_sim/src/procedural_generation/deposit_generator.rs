@@ -0,0 +1,126 @@
+// src/procedural_generation/deposit_generator.rs
+// Places resource deposits on tiles and small bodies.
+//
+// Tile deposits are rolled per tile from odds that depend on the world's
+// surface class, the tile's terrain and latitude, and the body's bulk
+// composition.  Small bodies also get bulk deposits mined from the body as a
+// whole, sized by surface area and split by composition; those are fixed by
+// the data, so they need no seed.
+
+use rand::Rng;
+use rand_chacha::ChaCha8Rng;
+
+use crate::maps::tile::Terrain;
+use crate::procedural_generation::terrain_generator::{SurfaceClass, TerrainProfile};
+use crate::resources::deposit::Deposit;
+use crate::resources::resource_type::ResourceType;
+use crate::universe::celestial_body::{CelestialBody, Composition};
+
+/// Typical size of a tile deposit
+const TILE_DEPOSIT_UNITS: f64 = 20_000.0;
+
+/// Units of bulk reserves per km² of a small body's surface
+const BULK_UNITS_PER_KM2: f64 = 10.0;
+
+/// Share of a small body's bulk reserves and the richness of each resource
+fn bulk_shares(composition: Composition) -> &'static [(ResourceType, f64, f32)] {
+    match composition {
+        Composition::Metallic => &[(ResourceType::Minerals, 0.9, 2.0), (ResourceType::Ice, 0.02, 0.3)],
+        Composition::Stony => &[(ResourceType::Minerals, 0.7, 1.2), (ResourceType::Ice, 0.05, 0.5)],
+        Composition::Carbonaceous => &[
+            (ResourceType::Minerals, 0.3, 0.8),
+            (ResourceType::Ice, 0.3, 1.0),
+            (ResourceType::Hydrocarbons, 0.1, 0.6),
+        ],
+        Composition::Icy => &[
+            (ResourceType::Ice, 0.7, 1.5),
+            (ResourceType::Gases, 0.1, 0.8),
+            (ResourceType::Minerals, 0.1, 0.4),
+        ],
+    }
+}
+
+/// Deposits mined from a small body as a whole; empty for larger bodies
+pub fn bulk_deposits(body: &CelestialBody) -> Vec<Deposit> {
+    if !body.is_small_body() {
+        return Vec::new();
+    }
+    let reserves = std::f64::consts::PI * body.diameter * body.diameter * BULK_UNITS_PER_KM2;
+    bulk_shares(body.composition())
+        .iter()
+        .map(|(resource, share, richness)| Deposit::new(resource.clone(), (reserves * share).round() as u64, *richness))
+        .collect()
+}
+
+/// Chance of each resource being found on a tile
+fn tile_odds(profile: &TerrainProfile, terrain: Terrain, latitude: f64) -> Vec<(ResourceType, f64)> {
+    let composition = profile.composition;
+    let mut odds = Vec::new();
+    let ore_bonus = if composition == Composition::Metallic { 2.0 } else { 1.0 };
+    match terrain {
+        Terrain::Mountains | Terrain::Highlands | Terrain::Craters | Terrain::Canyon => {
+            odds.push((ResourceType::Minerals, 0.2 * ore_bonus));
+        }
+        Terrain::Volcanic => {
+            odds.push((ResourceType::Minerals, 0.3 * ore_bonus));
+            odds.push((ResourceType::Gases, 0.2));
+        }
+        Terrain::Ice => odds.push((ResourceType::Ice, 0.5)),
+        Terrain::Atmosphere => odds.push((ResourceType::Gases, 0.3)),
+        Terrain::Ocean => {
+            odds.push((ResourceType::Organics, 0.1));
+            odds.push((ResourceType::Hydrocarbons, 0.05));
+        }
+        Terrain::Plains => odds.push((ResourceType::Minerals, 0.05 * ore_bonus)),
+    }
+
+    match profile.class {
+        SurfaceClass::Temperate if terrain != Terrain::Ocean && terrain != Terrain::Ice => {
+            odds.push((ResourceType::Organics, 0.3));
+            odds.push((ResourceType::Hydrocarbons, 0.05));
+        }
+        // Permanently shadowed polar craters trap ice, as at Shackleton
+        SurfaceClass::Barren if latitude.abs() >= 80.0 => odds.push((ResourceType::Ice, 0.3)),
+        // Hydrocarbon lakes and dunes, as on Titan
+        SurfaceClass::Frozen if profile.atmosphere => odds.push((ResourceType::Hydrocarbons, 0.1)),
+        _ => {}
+    }
+    if composition == Composition::Icy && terrain != Terrain::Ice && terrain != Terrain::Atmosphere {
+        odds.push((ResourceType::Ice, 0.2));
+    }
+    odds
+}
+
+/// Rolls the deposits found on one tile
+pub fn tile_deposits(profile: &TerrainProfile, terrain: Terrain, latitude: f64, rng: &mut ChaCha8Rng) -> Vec<Deposit> {
+    tile_odds(profile, terrain, latitude)
+        .into_iter()
+        .filter_map(|(resource, chance)| {
+            // Always draw all three so one tile's odds don't shift every later tile
+            let (roll, size, richness) = (rng.random::<f64>(), rng.random_range(0.2..2.0), rng.random_range(0.5f32..2.0));
+            (roll < chance).then(|| Deposit::new(resource, (TILE_DEPOSIT_UNITS * size).round() as u64, richness))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::universe::celestial_body::CelestialBodyType;
+
+    #[test]
+    fn test_bulk_deposits_follow_composition() {
+        // Masses and diameters of 16 Psyche and Enceladus
+        let psyche = CelestialBody::new("16 Psyche".to_string(), CelestialBodyType::Asteroid, String::new(), 2.29e19, 223.0);
+        let enceladus = CelestialBody::new("Enceladus".to_string(), CelestialBodyType::Moon, String::new(), 1.1e20, 504.0);
+        assert_eq!(psyche.composition(), Composition::Metallic);
+        assert_eq!(enceladus.composition(), Composition::Icy);
+
+        let largest = |deposits: Vec<Deposit>| deposits.into_iter().max_by_key(|d| d.quantity).unwrap().resource;
+        assert_eq!(largest(bulk_deposits(&psyche)), ResourceType::Minerals);
+        assert_eq!(largest(bulk_deposits(&enceladus)), ResourceType::Ice);
+
+        let earth = CelestialBody::new("Earth".to_string(), CelestialBodyType::Planet, String::new(), 5.97e24, 12742.0);
+        assert!(bulk_deposits(&earth).is_empty());
+    }
+}
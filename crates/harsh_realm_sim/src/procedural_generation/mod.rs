@@ -0,0 +1,4 @@
+//! Seeded generation of game content from the solar system data.
+
+pub mod deposit_generator;
+pub mod terrain_generator;
@@ -0,0 +1,360 @@
+// src/procedural_generation/terrain_generator.rs
+// Seeded terrain for surface maps.
+//
+// A body is first classified from its physical data (size, gravity, escape
+// velocity, equilibrium temperature and what it orbits), then painted: fractal
+// value noise gives elevation, which is cut into lowlands, highlands and
+// mountains by quantile so every world gets a similar spread of terrain.
+// Class-specific features (oceans, ice caps, craters, volcanic provinces) are
+// laid over the top.  Everything is driven by a ChaCha stream and a hash of
+// the game seed with the body's name, so a body always regenerates the same.
+
+use hex2d::Coordinate as HexCoord;
+use nalgebra::Vector3;
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::maps::surface_map::SurfaceMap;
+use crate::maps::tile::Terrain;
+use crate::procedural_generation::deposit_generator;
+use crate::universe::celestial_body::{CelestialBody, Composition};
+use crate::universe::solar_system_manager::SolarSystemManager;
+
+/// Black-body temperature at 1 AU for a fast-rotating body with no albedo, in kelvin
+const EQUILIBRIUM_TEMPERATURE_1AU_K: f64 = 278.6;
+
+const AU_KM: f64 = 149_597_870.7;
+
+/// Bodies with `escape velocity² / temperature` below this (km²/s²/K) cannot hold
+/// an atmosphere; Titan and Mars are just above, Mercury and the Moon below
+const ATMOSPHERE_RETENTION: f64 = 0.07;
+
+/// Moons closer than this many planetary radii to a giant are tidally heated, like Io
+const TIDAL_HEATING_RADII: f64 = 7.0;
+
+/// Moons smaller than this cannot sustain tidal volcanism
+const TIDAL_HEATING_MIN_DIAMETER_KM: f64 = 1000.0;
+
+/// Broad kind of world, which decides the features painted onto its surface
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum SurfaceClass {
+    /// No solid surface
+    Giant,
+    /// Liquid water oceans and ice caps, like Earth
+    Temperate,
+    /// Tidally heated moon with active volcanism, like Io
+    Volcanic,
+    /// Ice-covered, like Europa
+    Frozen,
+    /// Airless and heavily cratered, like the Moon or Mercury
+    Barren,
+    /// Rocky with an atmosphere but no liquid water, like Mars or Venus
+    Arid,
+}
+
+/// The physical facts terrain generation is driven by
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TerrainProfile {
+    pub class: SurfaceClass,
+    /// Surface gravity in m/s²
+    pub surface_gravity: f64,
+    /// Equilibrium temperature in kelvin
+    pub temperature_k: f64,
+    /// Whether the body is heavy and cold enough to hold an atmosphere
+    pub atmosphere: bool,
+    pub composition: Composition,
+    /// Region from the solar system data, e.g. "Jupiter Orbit"
+    pub region: String,
+}
+
+impl TerrainProfile {
+    /// Works out a body's profile from its data and the body it orbits
+    pub fn for_body(body: &CelestialBody, solar_system: &SolarSystemManager) -> Self {
+        let temperature_k = solar_system
+            .solar_distance(body)
+            .filter(|distance| *distance > 0.0)
+            .map_or(EQUILIBRIUM_TEMPERATURE_1AU_K, |distance| {
+                EQUILIBRIUM_TEMPERATURE_1AU_K / (distance / AU_KM).sqrt()
+            });
+        let tidally_heated = solar_system.parent_of(body).is_some_and(|parent| {
+            let distance = body.orbital_state.as_ref().map_or(f64::MAX, |state| state.parameters.semi_major_axis);
+            parent.is_giant()
+                && body.diameter >= TIDAL_HEATING_MIN_DIAMETER_KM
+                && distance < TIDAL_HEATING_RADII * parent.diameter / 2.0
+        });
+        let holds_atmosphere = body.escape_velocity().powi(2) / temperature_k >= ATMOSPHERE_RETENTION;
+
+        let class = if body.is_giant() {
+            SurfaceClass::Giant
+        } else if tidally_heated {
+            SurfaceClass::Volcanic
+        } else if temperature_k < 150.0 {
+            SurfaceClass::Frozen
+        } else if !holds_atmosphere {
+            SurfaceClass::Barren
+        } else if (250.0..300.0).contains(&temperature_k) && body.surface_gravity() >= 5.0 {
+            SurfaceClass::Temperate
+        } else {
+            SurfaceClass::Arid
+        };
+
+        Self {
+            class,
+            surface_gravity: body.surface_gravity(),
+            temperature_k,
+            atmosphere: holds_atmosphere,
+            composition: body.composition(),
+            region: body.region.clone(),
+        }
+    }
+
+    /// Typical height of the tallest relief in metres; weaker gravity allows taller mountains
+    pub fn relief_m(&self) -> f64 {
+        (10_000.0 / self.surface_gravity.max(0.25).sqrt()).min(20_000.0)
+    }
+
+    /// Latitude beyond which the surface is ice, if it has ice caps
+    pub fn ice_cap_latitude(&self) -> Option<f64> {
+        match self.class {
+            SurfaceClass::Temperate => Some(70.0),
+            SurfaceClass::Arid if self.temperature_k < 250.0 => Some(80.0),
+            _ => None,
+        }
+    }
+
+    /// Craters per tile
+    fn crater_density(&self) -> f64 {
+        match self.class {
+            SurfaceClass::Barren => 0.03,
+            SurfaceClass::Frozen => 0.01,
+            SurfaceClass::Arid => 0.004,
+            SurfaceClass::Giant | SurfaceClass::Temperate | SurfaceClass::Volcanic => 0.0,
+        }
+    }
+}
+
+/// Generates a body's surface map; the same body, profile and seed always give the same map
+pub fn generate(body: &CelestialBody, profile: &TerrainProfile, seed: u64) -> SurfaceMap {
+    let seed = body_seed(seed, &body.name);
+    let mut rng = ChaCha8Rng::seed_from_u64(seed);
+    let grid = body.surface_grid();
+    let base = if profile.class == SurfaceClass::Giant { Terrain::Atmosphere } else { Terrain::Plains };
+    let mut map = SurfaceMap::new(body.id, grid, body.diameter / 2.0, base);
+
+    // Tiles in grid order, so nothing depends on hash map iteration
+    let coords: Vec<HexCoord> = grid.tiles().collect();
+    for coord in &coords {
+        map.tile_mut(*coord).expect("grid tiles are on the map").id = Uuid::from_u128(rng.random());
+    }
+    if profile.class == SurfaceClass::Giant {
+        add_deposits(&mut map, &coords, profile, &mut rng);
+        return map;
+    }
+
+    let positions: Vec<Vector3<f64>> =
+        coords.iter().map(|coord| grid.position(*coord).expect("grid tiles have positions")).collect();
+    let heights: Vec<f64> = positions.iter().map(|p| fractal_noise(seed, p * 3.0, 5)).collect();
+    let relief = profile.relief_m();
+    let sea_level = match profile.class {
+        SurfaceClass::Temperate => quantile(&heights, 0.7),
+        _ => quantile(&heights, 0.5),
+    };
+    let highlands = quantile(&heights, 0.8);
+    let mountains = quantile(&heights, 0.95);
+    let canyons = quantile(&heights, 0.03);
+    let hotspot_seed = seed.wrapping_add(0x9e37_79b9_7f4a_7c15);
+    let hotspots: Vec<f64> = positions.iter().map(|p| fractal_noise(hotspot_seed, p * 4.0, 3)).collect();
+    let volcanic = quantile(&hotspots, 0.7);
+
+    for (index, coord) in coords.iter().enumerate() {
+        let height = heights[index];
+        let latitude = grid.lat_lon(*coord).map_or(0.0, |(lat, _)| lat);
+        let tile = map.tile_mut(*coord).expect("grid tiles are on the map");
+        tile.elevation = ((height - sea_level) * relief) as f32;
+        tile.terrain = if height >= mountains {
+            Terrain::Mountains
+        } else if height >= highlands {
+            Terrain::Highlands
+        } else {
+            Terrain::Plains
+        };
+
+        match profile.class {
+            SurfaceClass::Temperate if height < sea_level => tile.terrain = Terrain::Ocean,
+            SurfaceClass::Volcanic if hotspots[index] >= volcanic => tile.terrain = Terrain::Volcanic,
+            SurfaceClass::Frozen if tile.terrain != Terrain::Mountains => tile.terrain = Terrain::Ice,
+            SurfaceClass::Arid if height <= canyons => tile.terrain = Terrain::Canyon,
+            _ => {}
+        }
+        if profile.ice_cap_latitude().is_some_and(|cap| latitude.abs() >= cap) {
+            tile.terrain = Terrain::Ice;
+        }
+    }
+
+    add_craters(&mut map, &coords, profile, &mut rng);
+    add_deposits(&mut map, &coords, profile, &mut rng);
+    map
+}
+
+fn add_deposits(map: &mut SurfaceMap, coords: &[HexCoord], profile: &TerrainProfile, rng: &mut ChaCha8Rng) {
+    for coord in coords {
+        let latitude = map.lat_lon(*coord).map_or(0.0, |(lat, _)| lat);
+        let tile = map.tile_mut(*coord).expect("grid tiles are on the map");
+        tile.deposits = deposit_generator::tile_deposits(profile, tile.terrain, latitude, rng);
+    }
+}
+
+/// Punches bowl-shaped craters into the surface
+fn add_craters(map: &mut SurfaceMap, coords: &[HexCoord], profile: &TerrainProfile, rng: &mut ChaCha8Rng) {
+    let count = (coords.len() as f64 * profile.crater_density()).round() as usize;
+    let step = map.grid().min_step_angle();
+    let depth_m = profile.relief_m() * 0.3;
+
+    for _ in 0..count {
+        let centre = coords[rng.random_range(0..coords.len())];
+        let radius = step * rng.random_range(0.5..3.0);
+        let mut frontier = vec![centre];
+        let mut visited = vec![centre];
+        while let Some(coord) = frontier.pop() {
+            let angle = map.grid().angular_distance(centre, coord).unwrap_or(f64::MAX);
+            let tile = map.tile_mut(coord).expect("craters stay on the map");
+            tile.terrain = Terrain::Craters;
+            tile.elevation -= (depth_m * (1.0 - (angle / radius).powi(2)).max(0.0)) as f32;
+
+            for next in map.neighbors(coord) {
+                let inside = map.grid().angular_distance(centre, next).is_some_and(|a| a <= radius);
+                if inside && !visited.contains(&next) {
+                    visited.push(next);
+                    frontier.push(next);
+                }
+            }
+        }
+    }
+}
+
+/// Mixes the game seed with a body's name so each body gets its own stream
+fn body_seed(seed: u64, name: &str) -> u64 {
+    // FNV-1a, which unlike the std hasher is stable across Rust releases
+    let hash = name.bytes().fold(0xcbf2_9ce4_8422_2325u64, |hash, byte| {
+        (hash ^ byte as u64).wrapping_mul(0x0100_0000_01b3)
+    });
+    mix(seed ^ hash)
+}
+
+/// SplitMix64 finaliser
+fn mix(mut x: u64) -> u64 {
+    x = (x ^ (x >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    x = (x ^ (x >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    x ^ (x >> 31)
+}
+
+/// Pseudo-random value in [-1, 1] for a lattice point
+fn lattice_value(seed: u64, x: i64, y: i64, z: i64) -> f64 {
+    let hash = mix(seed ^ mix(x as u64 ^ mix(y as u64 ^ mix(z as u64))));
+    (hash >> 11) as f64 / (1u64 << 53) as f64 * 2.0 - 1.0
+}
+
+/// Smoothly interpolated 3D value noise in [-1, 1]
+fn value_noise(seed: u64, p: Vector3<f64>) -> f64 {
+    let base = p.map(f64::floor);
+    let t = (p - base).map(|t| t * t * (3.0 - 2.0 * t));
+    let (x, y, z) = (base.x as i64, base.y as i64, base.z as i64);
+    let lerp = |a: f64, b: f64, t: f64| a + (b - a) * t;
+    let corner = |dx, dy, dz| lattice_value(seed, x + dx, y + dy, z + dz);
+
+    let near = lerp(lerp(corner(0, 0, 0), corner(1, 0, 0), t.x), lerp(corner(0, 1, 0), corner(1, 1, 0), t.x), t.y);
+    let far = lerp(lerp(corner(0, 0, 1), corner(1, 0, 1), t.x), lerp(corner(0, 1, 1), corner(1, 1, 1), t.x), t.y);
+    lerp(near, far, t.z)
+}
+
+/// Sums octaves of value noise, each twice the frequency and half the weight of the last
+fn fractal_noise(seed: u64, p: Vector3<f64>, octaves: u32) -> f64 {
+    let (mut total, mut weight, mut scale) = (0.0, 1.0, 1.0);
+    for octave in 0..octaves {
+        total += value_noise(seed.wrapping_add(octave as u64), p * scale) * weight;
+        weight *= 0.5;
+        scale *= 2.0;
+    }
+    total
+}
+
+/// Value below which `fraction` of `values` lie
+fn quantile(values: &[f64], fraction: f64) -> f64 {
+    let mut sorted = values.to_vec();
+    sorted.sort_by(f64::total_cmp);
+    let index = ((sorted.len() - 1) as f64 * fraction).round() as usize;
+    sorted[index]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::resources::resource_type::ResourceType;
+    use crate::universe::celestial_body::CelestialBodyType;
+
+    fn body(name: &str) -> CelestialBody {
+        CelestialBody::new(name.to_string(), CelestialBodyType::Planet, String::new(), 6.4e23, 6779.0)
+    }
+
+    fn profile(class: SurfaceClass) -> TerrainProfile {
+        TerrainProfile {
+            class,
+            surface_gravity: 3.7,
+            temperature_k: 210.0,
+            atmosphere: true,
+            composition: Composition::Stony,
+            region: String::new(),
+        }
+    }
+
+    fn count(map: &SurfaceMap, terrain: Terrain) -> usize {
+        map.tiles().filter(|tile| tile.terrain == terrain).count()
+    }
+
+    #[test]
+    fn test_same_seed_same_map() {
+        let mars = body("Mars");
+        let profile = profile(SurfaceClass::Arid);
+        assert_eq!(generate(&mars, &profile, 7), generate(&mars, &profile, 7));
+
+        let other_seed = generate(&mars, &profile, 8);
+        let other_body = generate(&body("Not Mars"), &profile, 7);
+        let elevations = |map: &SurfaceMap| map.tiles().map(|t| (t.coord, t.elevation)).collect::<Vec<_>>();
+        assert_ne!(elevations(&generate(&mars, &profile, 7)), elevations(&other_seed));
+        assert_ne!(elevations(&generate(&mars, &profile, 7)), elevations(&other_body));
+    }
+
+    #[test]
+    fn test_classes_paint_their_features() {
+        let mars = body("Mars");
+        let temperate = generate(&mars, &profile(SurfaceClass::Temperate), 1);
+        let oceans = count(&temperate, Terrain::Ocean) as f64 / temperate.len() as f64;
+        assert!((0.5..0.75).contains(&oceans), "{}", oceans);
+        assert!(count(&temperate, Terrain::Ice) > 0);
+        assert_eq!(count(&temperate, Terrain::Craters), 0);
+
+        let barren = generate(&mars, &profile(SurfaceClass::Barren), 1);
+        assert!(count(&barren, Terrain::Craters) > 0);
+        assert_eq!(count(&barren, Terrain::Ocean), 0);
+
+        let volcanic = generate(&mars, &profile(SurfaceClass::Volcanic), 1);
+        assert!(count(&volcanic, Terrain::Volcanic) > volcanic.len() / 5);
+
+        let frozen = generate(&mars, &profile(SurfaceClass::Frozen), 1);
+        assert!(count(&frozen, Terrain::Ice) > frozen.len() / 2);
+
+        let giant = generate(&mars, &profile(SurfaceClass::Giant), 1);
+        assert_eq!(count(&giant, Terrain::Atmosphere), giant.len());
+        assert!(giant.tiles().flat_map(|t| &t.deposits).all(|d| d.resource == ResourceType::Gases));
+
+        // Mountains hold ore, ice caps hold ice
+        let has = |map: &SurfaceMap, terrain: Terrain, resource: ResourceType| {
+            map.tiles().filter(|t| t.terrain == terrain).flat_map(|t| &t.deposits).any(|d| d.resource == resource)
+        };
+        assert!(has(&temperate, Terrain::Mountains, ResourceType::Minerals));
+        assert!(has(&temperate, Terrain::Ice, ResourceType::Ice));
+        assert!(has(&temperate, Terrain::Plains, ResourceType::Organics));
+    }
+}
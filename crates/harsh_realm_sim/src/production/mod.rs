@@ -2,4 +2,5 @@ mod process;
 pub mod product;
 pub mod product_input;
 pub mod product_output;
+pub mod production_queue;
 pub mod production_chain;
@@ -1,6 +1,7 @@
 use serde::{Deserialize, Serialize};
 use crate::production::product::Product;
 
+#[allow(dead_code)]
 #[derive(Debug,Clone,Serialize,Deserialize)]
 pub struct Process {
     name: String,
@@ -0,0 +1,479 @@
+// src/production/production_queue.rs
+// Ordered production queues shared by settlements, installations and ships.
+// Each turn a structure's labor is handed out down its queue in order; an
+// item reserves its inputs from the stockpile as soon as it gets workers, and
+// can only get as far along as the share of its inputs it holds.  Cancelled
+// items give back everything they reserved.
+
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::buildings::building_type::BuildingType;
+use crate::events::game_event::GameEventKind;
+use crate::maps::location::Location;
+use crate::resources::resource_type::ResourceType;
+use crate::resources::storage::{self, Storehouse};
+use crate::simulation::simulation::Simulation;
+use crate::structures::spacecraft::{Spacecraft, SpacecraftModuleType};
+use crate::structures::structure_type::StructureType;
+use crate::units::unit_type::UnitType;
+
+/// Something a structure can make
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum BuildItem {
+    Building(BuildingType),
+    Unit(UnitType),
+    Spacecraft { name: String, modules: HashMap<SpacecraftModuleType, u32> },
+}
+
+impl BuildItem {
+    /// Materials consumed over the course of making the item
+    pub fn cost(&self) -> HashMap<ResourceType, u64> {
+        match self {
+            BuildItem::Building(building) => building.cost(),
+            BuildItem::Unit(unit) => {
+                let (metal, food) = match unit {
+                    UnitType::Infantry => (50, 100),
+                    UnitType::Engineers => (80, 100),
+                    UnitType::Marines => (100, 100),
+                    UnitType::Crew => (20, 150),
+                };
+                HashMap::from([(ResourceType::Metal, metal), (ResourceType::Food, food)])
+            }
+            BuildItem::Spacecraft { modules, .. } => {
+                let count: u64 = modules.values().map(|&n| n as u64).sum();
+                HashMap::from([(ResourceType::Metal, 500 + 200 * count), (ResourceType::NonMetal, 200 + 100 * count)])
+            }
+        }
+    }
+
+    /// Workers needed to make the item at full speed
+    pub fn labor(&self) -> u32 {
+        match self {
+            BuildItem::Building(BuildingType::Mine) => 50,
+            BuildItem::Building(BuildingType::Refinery) => 80,
+            BuildItem::Building(BuildingType::Factory) => 100,
+            BuildItem::Building(BuildingType::Laboratory) => 40,
+            BuildItem::Building(building) if building.storage().is_some() => 30,
+            BuildItem::Building(_) => 50,
+            BuildItem::Unit(_) => 20,
+            BuildItem::Spacecraft { .. } => 200,
+        }
+    }
+
+    /// Days the item takes with full labor and every input on hand
+    pub fn build_days(&self) -> u32 {
+        match self {
+            BuildItem::Building(BuildingType::Mine) => 60,
+            BuildItem::Building(BuildingType::Refinery) => 90,
+            BuildItem::Building(BuildingType::Factory) => 120,
+            BuildItem::Building(BuildingType::Laboratory) => 45,
+            BuildItem::Building(building) if building.storage().is_some() => 30,
+            BuildItem::Building(_) => 60,
+            BuildItem::Unit(UnitType::Crew) => 45,
+            BuildItem::Unit(_) => 30,
+            BuildItem::Spacecraft { modules, .. } => 180 + 30 * modules.values().sum::<u32>(),
+        }
+    }
+}
+
+/// Reasons a production order was refused
+#[derive(Debug, Clone, PartialEq)]
+pub enum ProductionError {
+    UnknownStructure(Uuid),
+    /// The structure lacks the facilities to make the item
+    CannotProduce { structure_id: Uuid, item: BuildItem },
+    /// Nothing with this id is in the queue
+    UnknownItem(Uuid),
+}
+
+impl Display for ProductionError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ProductionError::UnknownStructure(id) => write!(f, "no structure with id {}", id),
+            ProductionError::CannotProduce { structure_id, item } => write!(f, "{} cannot produce {:?}", structure_id, item),
+            ProductionError::UnknownItem(id) => write!(f, "no queued item with id {}", id),
+        }
+    }
+}
+
+impl std::error::Error for ProductionError {}
+
+/// Somewhere inputs are drawn from and refunds go back to
+pub trait Stockpile {
+    fn available(&self, resource: &ResourceType) -> u64;
+    /// Takes up to `amount`, returning how much was taken
+    fn take(&mut self, resource: &ResourceType, amount: u64) -> u64;
+    fn put(&mut self, resource: ResourceType, amount: u64);
+    /// Everything held, in resource order
+    fn contents(&self) -> Vec<(ResourceType, u64)>;
+}
+
+impl Stockpile for HashMap<ResourceType, u64> {
+    fn available(&self, resource: &ResourceType) -> u64 {
+        self.get(resource).copied().unwrap_or(0)
+    }
+
+    fn take(&mut self, resource: &ResourceType, amount: u64) -> u64 {
+        let Some(stock) = self.get_mut(resource) else {
+            return 0;
+        };
+        let taken = amount.min(*stock);
+        *stock -= taken;
+        taken
+    }
+
+    fn put(&mut self, resource: ResourceType, amount: u64) {
+        *self.entry(resource).or_default() += amount;
+    }
+
+    fn contents(&self) -> Vec<(ResourceType, u64)> {
+        let mut contents: Vec<(ResourceType, u64)> = self.iter().map(|(resource, &amount)| (resource.clone(), amount)).collect();
+        contents.sort();
+        contents
+    }
+}
+
+/// Ship cargo holds are counted in `u32`
+impl Stockpile for HashMap<ResourceType, u32> {
+    fn available(&self, resource: &ResourceType) -> u64 {
+        self.get(resource).copied().unwrap_or(0) as u64
+    }
+
+    fn take(&mut self, resource: &ResourceType, amount: u64) -> u64 {
+        let Some(stock) = self.get_mut(resource) else {
+            return 0;
+        };
+        let taken = amount.min(*stock as u64) as u32;
+        *stock -= taken;
+        taken as u64
+    }
+
+    fn put(&mut self, resource: ResourceType, amount: u64) {
+        let stock = self.entry(resource).or_default();
+        *stock = stock.saturating_add(amount.min(u32::MAX as u64) as u32);
+    }
+
+    fn contents(&self) -> Vec<(ResourceType, u64)> {
+        let mut contents: Vec<(ResourceType, u64)> =
+            self.iter().map(|(resource, &amount)| (resource.clone(), amount as u64)).collect();
+        contents.sort();
+        contents
+    }
+}
+
+/// An item in a queue and how far along it is
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct QueuedItem {
+    id: Uuid,
+    item: BuildItem,
+    /// Inputs taken from the stockpile so far
+    reserved: HashMap<ResourceType, u64>,
+    /// Share of the work done, from 0 to 1
+    progress: f64,
+}
+
+impl QueuedItem {
+    pub fn id(&self) -> Uuid {
+        self.id
+    }
+
+    pub fn item(&self) -> &BuildItem {
+        &self.item
+    }
+
+    pub fn reserved(&self) -> &HashMap<ResourceType, u64> {
+        &self.reserved
+    }
+
+    pub fn progress(&self) -> f64 {
+        self.progress
+    }
+
+    /// Returns true once the item has been given workers and started drawing inputs
+    pub fn is_started(&self) -> bool {
+        !self.reserved.is_empty() || self.progress > 0.0
+    }
+
+    /// Tops the reservation up towards the full cost from whatever `stockpile` holds
+    fn reserve(&mut self, stockpile: &mut dyn Stockpile) {
+        for (resource, needed) in self.item.cost() {
+            let held = self.reserved.get(&resource).copied().unwrap_or(0);
+            if held < needed {
+                let taken = stockpile.take(&resource, needed - held);
+                if taken > 0 {
+                    *self.reserved.entry(resource).or_default() += taken;
+                }
+            }
+        }
+    }
+
+    /// Share of the inputs on hand; the work cannot get further than this
+    fn supplied(&self) -> f64 {
+        self.item
+            .cost()
+            .iter()
+            .filter(|(_, &needed)| needed > 0)
+            .map(|(resource, &needed)| self.reserved.get(resource).copied().unwrap_or(0) as f64 / needed as f64)
+            .fold(1.0, f64::min)
+    }
+}
+
+/// An ordered list of things being made
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ProductionQueue {
+    items: Vec<QueuedItem>,
+}
+
+impl ProductionQueue {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn items(&self) -> &[QueuedItem] {
+        &self.items
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    /// Adds `item` to the back of the queue, returning its queue id
+    pub fn enqueue(&mut self, item: BuildItem) -> Uuid {
+        let id = Uuid::new_v4();
+        self.items.push(QueuedItem { id, item, reserved: HashMap::new(), progress: 0.0 });
+        id
+    }
+
+    /// Adds every item of `other` to the back of this queue, progress and reservations included
+    pub fn append(&mut self, other: ProductionQueue) {
+        self.items.extend(other.items);
+    }
+
+    /// Moves an item to `position`, or the back if that is past the end
+    pub fn move_to(&mut self, item_id: Uuid, position: usize) -> Result<(), ProductionError> {
+        let index = self.index_of(item_id)?;
+        let item = self.items.remove(index);
+        self.items.insert(position.min(self.items.len()), item);
+        Ok(())
+    }
+
+    /// Drops an item from the queue and refunds everything it reserved into `stockpile`
+    pub fn cancel(&mut self, item_id: Uuid, stockpile: &mut dyn Stockpile) -> Result<BuildItem, ProductionError> {
+        let index = self.index_of(item_id)?;
+        let QueuedItem { item, reserved, .. } = self.items.remove(index);
+        for (resource, amount) in reserved {
+            stockpile.put(resource, amount);
+        }
+        Ok(item)
+    }
+
+    /// Works the queue for `days` days with `labor` workers, returning what was finished
+    pub fn advance(&mut self, stockpile: &mut dyn Stockpile, labor: u32, days: u32) -> Vec<BuildItem> {
+        let mut completed = Vec::new();
+        let mut labor_left = labor;
+        let mut index = 0;
+        while index < self.items.len() && labor_left > 0 {
+            let queued = &mut self.items[index];
+            let needed = queued.item.labor().max(1);
+            let assigned = needed.min(labor_left);
+            labor_left -= assigned;
+
+            queued.reserve(stockpile);
+            let pace = assigned as f64 / needed as f64;
+            let work = days as f64 / queued.item.build_days().max(1) as f64 * pace;
+            queued.progress = (queued.progress + work).min(queued.supplied());
+
+            if queued.progress >= 1.0 - 1e-9 {
+                completed.push(self.items.remove(index).item);
+            } else {
+                index += 1;
+            }
+        }
+        completed
+    }
+
+    fn index_of(&self, item_id: Uuid) -> Result<usize, ProductionError> {
+        self.items.iter().position(|queued| queued.id == item_id).ok_or(ProductionError::UnknownItem(item_id))
+    }
+}
+
+/// A structure with a production queue
+pub trait Workshop {
+    fn production(&self) -> &ProductionQueue;
+    /// The stockpile the queue draws on
+    fn stockpile(&self) -> &dyn Stockpile;
+    /// The queue and the stockpile it draws on, borrowed together
+    fn workshop_mut(&mut self) -> (&mut ProductionQueue, &mut dyn Stockpile);
+    /// The stockpile the queue draws on, borrowed on its own
+    fn stockpile_mut(&mut self) -> &mut dyn Stockpile {
+        self.workshop_mut().1
+    }
+    /// Workers available to the queue
+    fn labor(&self) -> u32;
+    /// Returns true if the structure has the facilities to make `item`
+    fn can_produce(&self, item: &BuildItem) -> bool;
+}
+
+fn workshop_mut(simulation: &mut Simulation, structure_id: Uuid) -> Result<&mut dyn Workshop, ProductionError> {
+    simulation
+        .structures
+        .get_mut(&structure_id)
+        .map(StructureType::workshop_mut)
+        .ok_or(ProductionError::UnknownStructure(structure_id))
+}
+
+/// Queues `item` at a structure that can make it
+pub fn enqueue(simulation: &mut Simulation, structure_id: Uuid, item: BuildItem) -> Result<Uuid, ProductionError> {
+    let workshop = workshop_mut(simulation, structure_id)?;
+    if !workshop.can_produce(&item) {
+        return Err(ProductionError::CannotProduce { structure_id, item });
+    }
+    Ok(workshop.workshop_mut().0.enqueue(item))
+}
+
+/// Moves a queued item to `position` in its structure's queue
+pub fn reorder(simulation: &mut Simulation, structure_id: Uuid, item_id: Uuid, position: usize) -> Result<(), ProductionError> {
+    workshop_mut(simulation, structure_id)?.workshop_mut().0.move_to(item_id, position)
+}
+
+/// Cancels a queued item, refunding its reserved inputs to the structure's stockpile;
+/// whatever no longer fits there is lost
+pub fn cancel(simulation: &mut Simulation, structure_id: Uuid, item_id: Uuid) -> Result<BuildItem, ProductionError> {
+    let structure = simulation.structures.get_mut(&structure_id).ok_or(ProductionError::UnknownStructure(structure_id))?;
+    let capacity = storage::capacity(structure);
+    let (queue, stockpile) = structure.workshop_mut().workshop_mut();
+    queue.cancel(item_id, &mut Storehouse::new(stockpile, capacity))
+}
+
+/// Works every structure's queue for `days` days and delivers what was finished:
+/// buildings and units join the structure, ships launch docked to it.
+///
+/// Returns an event for everything completed, with the faction that owns the structure.
+pub fn advance(simulation: &mut Simulation, days: u32) -> Vec<(Option<Uuid>, GameEventKind)> {
+    let mut ids: Vec<Uuid> = simulation.structures.keys().copied().collect();
+    ids.sort();
+
+    let mut completed = Vec::new();
+    for structure_id in ids {
+        let Some(structure) = simulation.structures.get_mut(&structure_id) else {
+            continue;
+        };
+        let owner = structure.owner();
+        let workshop = structure.workshop_mut();
+        if workshop.production().is_empty() {
+            continue;
+        }
+        let labor = workshop.labor();
+        let (queue, stockpile) = workshop.workshop_mut();
+        for item in queue.advance(stockpile, labor, days) {
+            completed.push((owner, deliver(simulation, structure_id, owner, item)));
+        }
+    }
+    completed
+}
+
+fn deliver(simulation: &mut Simulation, structure_id: Uuid, owner: Option<Uuid>, item: BuildItem) -> GameEventKind {
+    match item {
+        BuildItem::Building(building) => {
+            if let Some(StructureType::Settlement(settlement)) = simulation.structures.get_mut(&structure_id) {
+                settlement.add_building(building.clone());
+            }
+            GameEventKind::BuildingCompleted { structure_id, building }
+        }
+        BuildItem::Unit(unit) => {
+            match simulation.structures.get_mut(&structure_id) {
+                Some(StructureType::Settlement(settlement)) => settlement.add_units(unit.clone(), 1),
+                Some(StructureType::Installation(installation)) => installation.add_units(unit.clone(), 1),
+                Some(StructureType::Spacecraft(spacecraft)) => spacecraft.add_units(unit.clone(), 1),
+                None => {}
+            }
+            GameEventKind::UnitTrained { structure_id, unit }
+        }
+        BuildItem::Spacecraft { name, modules } => {
+            let mut spacecraft = Spacecraft::new(name, Location::Docked { structure_id }).with_modules(modules);
+            if let Some(owner) = owner {
+                spacecraft = spacecraft.with_owner(owner);
+            }
+            let spacecraft_id = simulation.add_structure(StructureType::Spacecraft(spacecraft));
+            GameEventKind::SpacecraftLaunched { structure_id, spacecraft_id }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stock(metal: u64, non_metal: u64) -> HashMap<ResourceType, u64> {
+        HashMap::from([(ResourceType::Metal, metal), (ResourceType::NonMetal, non_metal)])
+    }
+
+    #[test]
+    fn test_items_reserve_inputs_when_started() {
+        let mut queue = ProductionQueue::new();
+        let mut stockpile = stock(1000, 1000);
+        let mine = queue.enqueue(BuildItem::Building(BuildingType::Mine));
+        queue.enqueue(BuildItem::Building(BuildingType::Factory));
+
+        // Only enough labor for the mine, so the factory does not start
+        assert!(queue.advance(&mut stockpile, 50, 30).is_empty());
+        assert_eq!(queue.items()[0].id(), mine);
+        assert_eq!(queue.items()[0].reserved(), &BuildingType::Mine.cost());
+        assert!((queue.items()[0].progress() - 0.5).abs() < 1e-9);
+        assert!(!queue.items()[1].is_started());
+        assert_eq!(stockpile, stock(800, 900));
+
+        let done = queue.advance(&mut stockpile, 50, 30);
+        assert_eq!(done, vec![BuildItem::Building(BuildingType::Mine)]);
+        assert_eq!(queue.items().len(), 1);
+    }
+
+    #[test]
+    fn test_shortages_and_short_staffing_slow_work() {
+        let mut queue = ProductionQueue::new();
+        let mut stockpile = stock(100, 100);
+        queue.enqueue(BuildItem::Building(BuildingType::Mine));
+
+        // Half the metal on hand caps progress at half, however long the work goes on
+        queue.advance(&mut stockpile, 50, 600);
+        assert!((queue.items()[0].progress() - 0.5).abs() < 1e-9);
+        assert_eq!(stockpile, stock(0, 0));
+
+        // Deliveries top the reservation up; half the crew works at half pace
+        stockpile = stock(100, 0);
+        queue.advance(&mut stockpile, 25, 30);
+        assert!((queue.items()[0].progress() - 0.75).abs() < 1e-9);
+        assert_eq!(queue.advance(&mut stockpile, 50, 15).len(), 1);
+    }
+
+    #[test]
+    fn test_reorder_and_cancel_with_refund() {
+        let mut queue = ProductionQueue::new();
+        let mut stockpile = stock(1000, 1000);
+        let first = queue.enqueue(BuildItem::Unit(UnitType::Infantry));
+        let second = queue.enqueue(BuildItem::Building(BuildingType::Laboratory));
+        queue.move_to(second, 0).unwrap();
+        assert_eq!(queue.items()[0].id(), second);
+        queue.move_to(second, 10).unwrap();
+        assert_eq!(queue.items()[1].id(), second);
+
+        queue.advance(&mut stockpile, 1000, 1);
+        assert!(queue.items().iter().all(QueuedItem::is_started));
+        assert_eq!(queue.cancel(second, &mut stockpile), Ok(BuildItem::Building(BuildingType::Laboratory)));
+        assert_eq!(stockpile[&ResourceType::NonMetal], 1000);
+        assert_eq!(stockpile[&ResourceType::Metal], 950);
+        assert_eq!(queue.cancel(second, &mut stockpile), Err(ProductionError::UnknownItem(second)));
+        assert_eq!(queue.items()[0].id(), first);
+    }
+
+    #[test]
+    fn test_cargo_holds_serve_as_stockpiles() {
+        let mut cargo: HashMap<ResourceType, u32> = HashMap::from([(ResourceType::Metal, 60)]);
+        assert_eq!(cargo.take(&ResourceType::Metal, 100), 60);
+        cargo.put(ResourceType::Metal, u64::MAX);
+        assert_eq!(cargo.available(&ResourceType::Metal), u32::MAX as u64);
+    }
+}
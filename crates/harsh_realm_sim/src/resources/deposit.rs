@@ -0,0 +1,94 @@
+use std::collections::BTreeSet;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::resources::resource_type::ResourceType;
+
+/// Whether a faction knows about a deposit
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum DepositStatus {
+    Hidden,
+    Surveyed,
+}
+
+/// A finite body of an extractable resource on a tile or small body
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Deposit {
+    pub resource: ResourceType,
+    /// Units left to extract
+    pub quantity: u64,
+    /// Extraction rate multiplier; 1.0 is an average deposit
+    pub richness: f32,
+    /// Factions that have surveyed this deposit
+    #[serde(default)]
+    surveyed_by: BTreeSet<Uuid>,
+}
+
+impl Deposit {
+    pub fn new(resource: ResourceType, quantity: u64, richness: f32) -> Self {
+        Self {
+            resource,
+            quantity,
+            richness: richness.max(0.0),
+            surveyed_by: BTreeSet::new(),
+        }
+    }
+
+    /// Gets what `faction_id` knows about this deposit
+    pub fn status(&self, faction_id: Uuid) -> DepositStatus {
+        if self.surveyed_by.contains(&faction_id) {
+            DepositStatus::Surveyed
+        } else {
+            DepositStatus::Hidden
+        }
+    }
+
+    /// Reveals the deposit to `faction_id`; returns true if it was hidden from them
+    pub fn survey(&mut self, faction_id: Uuid) -> bool {
+        self.surveyed_by.insert(faction_id)
+    }
+
+    pub fn is_depleted(&self) -> bool {
+        self.quantity == 0
+    }
+
+    /// Units an extractor with `base_rate` units a day gets from this deposit over `days`
+    pub fn yield_over(&self, base_rate: u64, days: u32) -> u64 {
+        let output = (base_rate as f64 * self.richness as f64 * days as f64).round() as u64;
+        output.min(self.quantity)
+    }
+
+    /// Removes up to `amount` units and returns how many were taken
+    pub fn extract(&mut self, amount: u64) -> u64 {
+        let taken = amount.min(self.quantity);
+        self.quantity -= taken;
+        taken
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extraction_depletes() {
+        let mut deposit = Deposit::new(ResourceType::Minerals, 100, 1.5);
+        assert_eq!(deposit.yield_over(10, 3), 45);
+        assert_eq!(deposit.extract(45), 45);
+        assert_eq!(deposit.yield_over(10, 30), 55);
+        assert_eq!(deposit.extract(500), 55);
+        assert!(deposit.is_depleted());
+        assert_eq!(deposit.extract(1), 0);
+    }
+
+    #[test]
+    fn test_survey_is_per_faction() {
+        let (us, them) = (Uuid::new_v4(), Uuid::new_v4());
+        let mut deposit = Deposit::new(ResourceType::Ice, 10, 1.0);
+        assert_eq!(deposit.status(us), DepositStatus::Hidden);
+        assert!(deposit.survey(us));
+        assert!(!deposit.survey(us));
+        assert_eq!(deposit.status(us), DepositStatus::Surveyed);
+        assert_eq!(deposit.status(them), DepositStatus::Hidden);
+    }
+}
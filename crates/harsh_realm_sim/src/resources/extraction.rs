@@ -0,0 +1,94 @@
+// src/resources/extraction.rs
+// Mining: settlements with mine buildings and mining installations draw down
+// the deposits where they stand.  Surface extractors mine their hex; orbital
+// ones mine small bodies as a whole.  Output goes to the extractor's own
+// stockpile, as much as it has room for, and operating a deposit reveals it
+// to the owner.  Extraction on the surface needs the owner to control the
+// hex (see `faction::territory`).
+
+use uuid::Uuid;
+
+use crate::buildings::building_type::BuildingType;
+use crate::faction::territory;
+use crate::maps::location::Location;
+use crate::resources::deposit::Deposit;
+use crate::resources::resource_type::ResourceType;
+use crate::resources::storage;
+use crate::simulation::simulation::Simulation;
+use crate::structures::installation::InstallationPurpose;
+use crate::structures::structure_type::StructureType;
+use crate::universe::solar_system_manager::SolarSystemManager;
+
+/// Units a day one mine building in a settlement takes from each deposit of average richness
+pub const SETTLEMENT_MINE_RATE: u64 = 10;
+
+/// Units a day a crewed tier 1 mining installation takes from each deposit of average richness
+pub const INSTALLATION_MINE_RATE: u64 = 25;
+
+/// Runs every extractor for `days` days.
+///
+/// Returns `(structure_id, resource)` for each deposit an extractor exhausted.
+pub fn extract(simulation: &mut Simulation, solar_system: &mut SolarSystemManager, seed: u64, days: u32) -> Vec<(Uuid, ResourceType)> {
+    // Orbital mines can share a body, so go in a fixed order
+    let mut ids: Vec<Uuid> = simulation.structures.keys().copied().collect();
+    ids.sort();
+
+    let mut depleted = Vec::new();
+    for id in ids {
+        let Some(structure) = simulation.structures.get_mut(&id) else {
+            continue;
+        };
+        let rate = extraction_rate(structure);
+        if rate == 0 {
+            continue;
+        }
+        // Surface extractors need an uncontested hold on their hex
+        let owner = structure.owner();
+        if !territory::controls(solar_system, structure.location(), owner) {
+            continue;
+        }
+        let Some(deposits) = deposits_at(structure.location(), solar_system, seed) else {
+            continue;
+        };
+
+        for deposit in deposits.iter_mut().filter(|d| !d.is_depleted()) {
+            if let Some(owner) = owner {
+                deposit.survey(owner);
+            }
+            // Whatever the extractor has no room for stays in the ground
+            let room = storage::free_space(structure, &deposit.resource);
+            let amount = deposit.extract(deposit.yield_over(rate, days).min(room));
+            match structure {
+                StructureType::Settlement(settlement) => settlement.add_resource(deposit.resource.clone(), amount),
+                StructureType::Installation(installation) => installation.add_resource(deposit.resource.clone(), amount),
+                StructureType::Spacecraft(_) => {}
+            }
+            if deposit.is_depleted() {
+                depleted.push((id, deposit.resource.clone()));
+            }
+        }
+    }
+    depleted
+}
+
+/// Base units a day the structure takes from each deposit it works
+pub fn extraction_rate(structure: &StructureType) -> u64 {
+    match structure {
+        StructureType::Settlement(settlement) => {
+            settlement.buildings().get(&BuildingType::Mine).copied().unwrap_or(0) as u64 * SETTLEMENT_MINE_RATE
+        }
+        StructureType::Installation(installation) if *installation.purpose() == InstallationPurpose::Mine => {
+            (INSTALLATION_MINE_RATE as f64 * installation.effective_output()).round() as u64
+        }
+        _ => 0,
+    }
+}
+
+/// Deposits an extractor at `location` can work: its hex on a surface, or a small body it orbits
+fn deposits_at<'a>(location: &Location, solar_system: &'a mut SolarSystemManager, seed: u64) -> Option<&'a mut Vec<Deposit>> {
+    match location {
+        Location::Surface { .. } => solar_system.tile_mut(location, seed).map(|tile| &mut tile.deposits),
+        Location::Orbit { body_id, .. } => solar_system.get_body_by_id_mut(*body_id).map(|body| &mut body.deposits),
+        Location::DeepSpace { .. } | Location::Docked { .. } => None,
+    }
+}
@@ -1,2 +1,5 @@
+pub mod deposit;
+pub mod extraction;
 pub mod resource_type;
-pub mod resource_manager;
\ No newline at end of file
+pub mod resource_manager;
+pub mod storage;
@@ -1,6 +1,8 @@
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug,Clone, Serialize,Deserialize,Eq, PartialEq,Hash)]
+use crate::resources::storage::StorageType;
+
+#[derive(Debug,Clone, Serialize,Deserialize,Eq, PartialEq,Hash,PartialOrd,Ord)]
 pub enum ResourceType {
     // Extracted
     Ice,
@@ -19,3 +21,31 @@ pub enum ResourceType {
     // Future intermediates
     Waste,
 }
+
+impl ResourceType {
+    /// The kind of storage the resource is kept in; Energy is held in the grid and needs none
+    pub fn storage_type(&self) -> Option<StorageType> {
+        match self {
+            ResourceType::Minerals
+            | ResourceType::Organics
+            | ResourceType::Metal
+            | ResourceType::NonMetal
+            | ResourceType::Food
+            | ResourceType::Waste => Some(StorageType::Bulk),
+            ResourceType::Hydrocarbons | ResourceType::Water | ResourceType::BioMatter => Some(StorageType::Liquid),
+            ResourceType::Gases => Some(StorageType::Gas),
+            ResourceType::Air => Some(StorageType::Pressurized),
+            ResourceType::Ice => Some(StorageType::Cryogenic),
+            ResourceType::Energy => None,
+        }
+    }
+
+    /// Share of a stock that goes off each day
+    pub fn spoilage_per_day(&self) -> f64 {
+        match self {
+            ResourceType::Food => 0.005,
+            ResourceType::BioMatter => 0.02,
+            _ => 0.0,
+        }
+    }
+}
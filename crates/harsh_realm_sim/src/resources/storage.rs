@@ -0,0 +1,270 @@
+// src/resources/storage.rs
+// Warehousing: every resource but Energy needs a particular kind of storage,
+// and each structure has only so much of each kind.  Settlements start with
+// some and add more with storage buildings; ships carry only what their hull
+// and cargo modules hold.  Nothing is put into a full store: mines leave ore
+// in the ground and refineries stop for want of room.  Each turn whatever no
+// longer fits because storage was lost (a warehouse torn down, a cargo module
+// removed) is dumped, and perishables spoil into Waste.
+
+use std::collections::HashMap;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::production::production_queue::Stockpile;
+use crate::resources::resource_type::ResourceType;
+use crate::simulation::simulation::Simulation;
+use crate::structures::structure_type::StructureType;
+
+/// Storage every settlement has of each kind before any storage buildings
+pub const SETTLEMENT_BASE_CAPACITY: u64 = 10_000;
+
+/// Storage each storage building adds for its kind
+pub const STORAGE_BUILDING_CAPACITY: u64 = 50_000;
+
+/// Storage an installation has of each kind
+pub const INSTALLATION_CAPACITY: u64 = 5_000;
+
+/// Storage a hull has of each kind before any cargo modules
+pub const SPACECRAFT_BASE_CAPACITY: u64 = 500;
+
+/// Storage each cargo module adds for its kind
+pub const CARGO_MODULE_CAPACITY: u64 = 5_000;
+
+/// How a resource has to be kept
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum StorageType {
+    /// Dry solids in sacks, bins or open yards
+    Bulk,
+    Liquid,
+    Gas,
+    /// Held above ambient pressure, like breathable air
+    Pressurized,
+    /// Kept frozen or liquefied by refrigeration
+    Cryogenic,
+}
+
+impl StorageType {
+    pub const ALL: [StorageType; 5] =
+        [StorageType::Bulk, StorageType::Liquid, StorageType::Gas, StorageType::Pressurized, StorageType::Cryogenic];
+}
+
+/// Storage a structure has of each kind
+pub fn capacity(structure: &StructureType) -> HashMap<StorageType, u64> {
+    let (base, extra) = match structure {
+        StructureType::Settlement(settlement) => (
+            SETTLEMENT_BASE_CAPACITY,
+            settlement
+                .buildings()
+                .iter()
+                .filter_map(|(building, &count)| Some((building.storage()?, count as u64 * STORAGE_BUILDING_CAPACITY)))
+                .collect(),
+        ),
+        StructureType::Installation(_) => (INSTALLATION_CAPACITY, Vec::new()),
+        StructureType::Spacecraft(spacecraft) => (
+            SPACECRAFT_BASE_CAPACITY,
+            spacecraft
+                .modules()
+                .iter()
+                .filter_map(|(module, &count)| Some((module.storage()?, count as u64 * CARGO_MODULE_CAPACITY)))
+                .collect(),
+        ),
+    };
+    let mut capacity: HashMap<StorageType, u64> = StorageType::ALL.iter().map(|&kind| (kind, base)).collect();
+    for (kind, amount) in extra {
+        *capacity.entry(kind).or_default() += amount;
+    }
+    capacity
+}
+
+/// How much of each kind of storage a structure is using
+pub fn used(structure: &StructureType) -> HashMap<StorageType, u64> {
+    used_by(structure.stockpile())
+}
+
+fn used_by(stockpile: &dyn Stockpile) -> HashMap<StorageType, u64> {
+    let mut used = HashMap::new();
+    for (resource, amount) in stockpile.contents() {
+        if let Some(kind) = resource.storage_type() {
+            *used.entry(kind).or_default() += amount;
+        }
+    }
+    used
+}
+
+/// Room left for `resource` at a structure; unlimited for resources that need no storage
+pub fn free_space(structure: &StructureType, resource: &ResourceType) -> u64 {
+    let Some(kind) = resource.storage_type() else {
+        return u64::MAX;
+    };
+    let capacity = capacity(structure).get(&kind).copied().unwrap_or(0);
+    capacity.saturating_sub(used(structure).get(&kind).copied().unwrap_or(0))
+}
+
+/// A stockpile held to its structure's storage: whatever is put in beyond the room left is turned away
+pub struct Storehouse<'a> {
+    stockpile: &'a mut dyn Stockpile,
+    capacity: HashMap<StorageType, u64>,
+}
+
+impl<'a> Storehouse<'a> {
+    pub fn new(stockpile: &'a mut dyn Stockpile, capacity: HashMap<StorageType, u64>) -> Self {
+        Self { stockpile, capacity }
+    }
+
+    /// Room left for `resource`; unlimited for resources that need no storage
+    pub fn room_for(&self, resource: &ResourceType) -> u64 {
+        let Some(kind) = resource.storage_type() else {
+            return u64::MAX;
+        };
+        let capacity = self.capacity.get(&kind).copied().unwrap_or(0);
+        capacity.saturating_sub(used_by(&*self.stockpile).get(&kind).copied().unwrap_or(0))
+    }
+}
+
+impl Stockpile for Storehouse<'_> {
+    fn available(&self, resource: &ResourceType) -> u64 {
+        self.stockpile.available(resource)
+    }
+
+    fn take(&mut self, resource: &ResourceType, amount: u64) -> u64 {
+        self.stockpile.take(resource, amount)
+    }
+
+    fn put(&mut self, resource: ResourceType, amount: u64) {
+        let amount = amount.min(self.room_for(&resource));
+        if amount > 0 {
+            self.stockpile.put(resource, amount);
+        }
+    }
+
+    fn contents(&self) -> Vec<(ResourceType, u64)> {
+        self.stockpile.contents()
+    }
+}
+
+/// Dumps whatever is over capacity then spoils perishables for `days` days, everywhere.
+///
+/// Since nothing is put into a full store, only storage that has been lost
+/// leaves anything to dump.  Returns `(structure_id, resource, amount)` for
+/// everything dumped.
+pub fn apply(simulation: &mut Simulation, days: u32) -> Vec<(Uuid, ResourceType, u64)> {
+    let mut ids: Vec<Uuid> = simulation.structures.keys().copied().collect();
+    ids.sort();
+
+    let mut dumped = Vec::new();
+    for id in ids {
+        let Some(structure) = simulation.structures.get_mut(&id) else {
+            continue;
+        };
+        let capacity = capacity(structure);
+        for (resource, amount) in dump_overflow(structure.stockpile_mut(), &capacity) {
+            dumped.push((id, resource, amount));
+        }
+        // Waste from spoilage needs room like anything else
+        spoil(&mut Storehouse::new(structure.stockpile_mut(), capacity), days);
+    }
+    dumped
+}
+
+/// Turns the share of each perishable that goes off in `days` days into Waste
+pub fn spoil(stockpile: &mut dyn Stockpile, days: u32) {
+    for (resource, amount) in stockpile.contents() {
+        let rate = resource.spoilage_per_day();
+        if rate <= 0.0 || amount == 0 {
+            continue;
+        }
+        let spoiled = (amount as f64 * (1.0 - (1.0 - rate).powi(days as i32))).round() as u64;
+        let spoiled = stockpile.take(&resource, spoiled);
+        if spoiled > 0 {
+            stockpile.put(ResourceType::Waste, spoiled);
+        }
+    }
+}
+
+/// Throws out whatever does not fit, from every resource sharing the full storage in proportion
+/// to how much of it there is.  Returns what was thrown out.
+pub fn dump_overflow(stockpile: &mut dyn Stockpile, capacity: &HashMap<StorageType, u64>) -> Vec<(ResourceType, u64)> {
+    let contents = stockpile.contents();
+    let mut dumped = Vec::new();
+    for kind in StorageType::ALL {
+        let held: Vec<&(ResourceType, u64)> =
+            contents.iter().filter(|(resource, amount)| *amount > 0 && resource.storage_type() == Some(kind)).collect();
+        let total: u64 = held.iter().map(|(_, amount)| amount).sum();
+        let excess = total.saturating_sub(capacity.get(&kind).copied().unwrap_or(0));
+        if excess == 0 {
+            continue;
+        }
+        let mut shares: Vec<u64> =
+            held.iter().map(|(_, amount)| (excess as u128 * *amount as u128 / total as u128) as u64).collect();
+        // Rounding leaves a little over; take it from whatever still has some
+        let mut remainder = excess - shares.iter().sum::<u64>();
+        for (share, (_, amount)) in shares.iter_mut().zip(&held) {
+            let extra = remainder.min(amount - *share);
+            *share += extra;
+            remainder -= extra;
+        }
+        for (share, (resource, _)) in shares.into_iter().zip(held) {
+            if share > 0 {
+                dumped.push((resource.clone(), stockpile.take(resource, share)));
+            }
+        }
+    }
+    dumped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_perishables_spoil_into_waste() {
+        let mut stockpile: HashMap<ResourceType, u64> =
+            HashMap::from([(ResourceType::Food, 1000), (ResourceType::BioMatter, 1000), (ResourceType::Metal, 1000)]);
+        spoil(&mut stockpile, 30);
+        assert!(stockpile[&ResourceType::Food] < 1000);
+        assert!(stockpile[&ResourceType::BioMatter] < stockpile[&ResourceType::Food]);
+        assert_eq!(stockpile[&ResourceType::Metal], 1000);
+        let lost = 2000 - stockpile[&ResourceType::Food] - stockpile[&ResourceType::BioMatter];
+        assert_eq!(stockpile[&ResourceType::Waste], lost);
+    }
+
+    #[test]
+    fn test_storehouse_turns_away_what_does_not_fit() {
+        let mut stockpile: HashMap<ResourceType, u64> = HashMap::from([(ResourceType::Metal, 150)]);
+        let mut store = Storehouse::new(&mut stockpile, HashMap::from([(StorageType::Bulk, 200)]));
+        assert_eq!(store.room_for(&ResourceType::Minerals), 50);
+        store.put(ResourceType::Minerals, 80);
+        store.put(ResourceType::Energy, 1000);
+        assert_eq!(store.room_for(&ResourceType::Metal), 0);
+        assert_eq!(store.room_for(&ResourceType::Water), 0);
+        assert_eq!(stockpile[&ResourceType::Minerals], 50);
+        assert_eq!(stockpile[&ResourceType::Energy], 1000);
+    }
+
+    #[test]
+    fn test_overflow_is_shared_out_by_amount() {
+        let mut stockpile: HashMap<ResourceType, u64> = HashMap::from([
+            (ResourceType::Metal, 300),
+            (ResourceType::Minerals, 100),
+            (ResourceType::Water, 50),
+            (ResourceType::Energy, 1_000_000),
+        ]);
+        let capacity = HashMap::from([(StorageType::Bulk, 200), (StorageType::Liquid, 100)]);
+        let mut dumped = dump_overflow(&mut stockpile, &capacity);
+        dumped.sort();
+        assert_eq!(dumped, vec![(ResourceType::Minerals, 50), (ResourceType::Metal, 150)]);
+        assert_eq!(stockpile[&ResourceType::Metal] + stockpile[&ResourceType::Minerals], 200);
+        assert_eq!(stockpile[&ResourceType::Water], 50);
+        assert_eq!(stockpile[&ResourceType::Energy], 1_000_000);
+    }
+
+    #[test]
+    fn test_rounding_never_leaves_an_overflow() {
+        let mut stockpile: HashMap<ResourceType, u64> =
+            HashMap::from([(ResourceType::Metal, 7), (ResourceType::NonMetal, 7), (ResourceType::Minerals, 7)]);
+        let dumped = dump_overflow(&mut stockpile, &HashMap::from([(StorageType::Bulk, 10)]));
+        assert_eq!(dumped.iter().map(|(_, amount)| amount).sum::<u64>(), 11);
+        assert_eq!(stockpile.values().sum::<u64>(), 10);
+    }
+}
@@ -0,0 +1,59 @@
+use std::collections::BTreeMap;
+use log::info;
+use serde_json::Value;
+
+use crate::save::save_error::SaveError;
+
+/// Upgrades the serialized game document in place from `from_version` to `from_version + 1`
+pub type MigrationFn = fn(&mut Value) -> Result<(), String>;
+
+/// A single upgrade step between two consecutive save format versions
+#[derive(Clone)]
+pub struct Migration {
+    pub from_version: u32,
+    pub description: &'static str,
+    pub apply: MigrationFn,
+}
+
+/// Ordered set of migrations used to bring old saves up to the current format
+#[derive(Clone)]
+pub struct MigrationRegistry {
+    migrations: BTreeMap<u32, Migration>,
+}
+
+impl MigrationRegistry {
+    /// Creates a registry with no migrations
+    pub fn empty() -> Self {
+        Self {
+            migrations: BTreeMap::new(),
+        }
+    }
+
+    /// Registers a migration, replacing any existing one for the same version
+    pub fn register(&mut self, migration: Migration) {
+        self.migrations.insert(migration.from_version, migration);
+    }
+
+    /// Runs every migration needed to take `game` from `from_version` to `to_version`
+    pub fn migrate(&self, game: &mut Value, from_version: u32, to_version: u32) -> Result<(), SaveError> {
+        for version in from_version..to_version {
+            let migration = self
+                .migrations
+                .get(&version)
+                .ok_or(SaveError::MissingMigration { from_version: version })?;
+            info!("Migrating save from version {} to {}: {}", version, version + 1, migration.description);
+            (migration.apply)(game).map_err(|message| SaveError::Migration {
+                from_version: version,
+                message,
+            })?;
+        }
+        Ok(())
+    }
+}
+
+impl Default for MigrationRegistry {
+    /// Creates a registry holding the built-in migrations; the first format needs none
+    fn default() -> Self {
+        Self::empty()
+    }
+}
@@ -0,0 +1,13 @@
+//! Save-file format for [`GameState`](crate::game_state::GameState).
+//!
+//! A save is a JSON envelope holding a format version and the serialized game,
+//! optionally gzip-compressed.  Files written by older versions are upgraded on
+//! load by the migrations registered in [`migration::MigrationRegistry`].
+//!
+//! Purely additive fields should carry `#[serde(default)]` so older saves keep
+//! loading; renames and restructures bump [`save_file::SAVE_FORMAT_VERSION`] and
+//! register a migration from the previous version.
+
+pub mod migration;
+pub mod save_error;
+pub mod save_file;
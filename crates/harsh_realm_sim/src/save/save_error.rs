@@ -0,0 +1,59 @@
+use std::fmt::{Display, Formatter};
+
+/// Errors produced while writing or reading a save file
+#[derive(Debug)]
+pub enum SaveError {
+    Io(std::io::Error),
+    Serialization(serde_json::Error),
+    /// The file parsed but is not a Harsh Realm save
+    NotASaveFile,
+    /// The save was written by a newer build than this one
+    UnsupportedVersion { found: u32, supported: u32 },
+    /// No migration is registered to upgrade from this version
+    MissingMigration { from_version: u32 },
+    /// A registered migration rejected the document
+    Migration { from_version: u32, message: String },
+}
+
+impl Display for SaveError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SaveError::Io(e) => write!(f, "save file I/O error: {}", e),
+            SaveError::Serialization(e) => write!(f, "save file is malformed: {}", e),
+            SaveError::NotASaveFile => write!(f, "file is not a Harsh Realm save"),
+            SaveError::UnsupportedVersion { found, supported } => write!(
+                f,
+                "save format version {} is newer than supported version {}",
+                found, supported
+            ),
+            SaveError::MissingMigration { from_version } => {
+                write!(f, "no migration registered from save format version {}", from_version)
+            }
+            SaveError::Migration { from_version, message } => {
+                write!(f, "migration from save format version {} failed: {}", from_version, message)
+            }
+        }
+    }
+}
+
+impl std::error::Error for SaveError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            SaveError::Io(e) => Some(e),
+            SaveError::Serialization(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<std::io::Error> for SaveError {
+    fn from(e: std::io::Error) -> Self {
+        SaveError::Io(e)
+    }
+}
+
+impl From<serde_json::Error> for SaveError {
+    fn from(e: serde_json::Error) -> Self {
+        SaveError::Serialization(e)
+    }
+}
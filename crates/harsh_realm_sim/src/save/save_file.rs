@@ -0,0 +1,130 @@
+use std::ffi::OsString;
+use std::fs::{self, File};
+use std::io::{BufWriter, Read};
+use std::path::{Path, PathBuf};
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use log::info;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::game_state::GameState;
+use crate::save::migration::MigrationRegistry;
+use crate::save::save_error::SaveError;
+
+/// Format version written by this build
+pub const SAVE_FORMAT_VERSION: u32 = 1;
+
+/// Identifies a JSON document as a Harsh Realm save
+const SAVE_FORMAT_TAG: &str = "harsh_realm_save";
+
+/// The first two bytes of any gzip stream
+const GZIP_MAGIC: [u8; 2] = [0x1f, 0x8b];
+
+/// Options controlling how a save is written
+#[derive(Debug, Clone)]
+pub struct SaveOptions {
+    /// Gzip-compress the save file
+    pub compress: bool,
+}
+
+impl Default for SaveOptions {
+    fn default() -> Self {
+        Self { compress: true }
+    }
+}
+
+#[derive(Serialize)]
+struct SaveEnvelopeRef<'a> {
+    format: &'static str,
+    format_version: u32,
+    game: &'a GameState,
+}
+
+#[derive(Deserialize)]
+struct SaveEnvelope {
+    format: String,
+    format_version: u32,
+    game: Value,
+}
+
+/// Writes the game state to `path` using the current save format
+pub fn save_game(state: &GameState, path: &Path, options: &SaveOptions) -> Result<(), SaveError> {
+    info!("Saving game to {:?} (compressed: {})", path, options.compress);
+
+    let envelope = SaveEnvelopeRef {
+        format: SAVE_FORMAT_TAG,
+        format_version: SAVE_FORMAT_VERSION,
+        game: state,
+    };
+
+    // Write beside the target and rename over it, so a crash mid-write leaves the old save intact
+    let temp_path = temp_path_for(path);
+    let written = write_envelope(&envelope, &temp_path, options).and_then(|()| Ok(fs::rename(&temp_path, path)?));
+    if written.is_err() {
+        fs::remove_file(&temp_path).ok();
+    }
+    written
+}
+
+/// A file name in the same directory as `path`, so renaming it over `path` is atomic
+fn temp_path_for(path: &Path) -> PathBuf {
+    let mut name = path.file_name().map(OsString::from).unwrap_or_default();
+    name.push(".tmp");
+    path.with_file_name(name)
+}
+
+fn write_envelope(envelope: &SaveEnvelopeRef, path: &Path, options: &SaveOptions) -> Result<(), SaveError> {
+    let writer = BufWriter::new(File::create(path)?);
+    let file = if options.compress {
+        let mut encoder = GzEncoder::new(writer, Compression::default());
+        serde_json::to_writer(&mut encoder, envelope)?;
+        encoder.finish()?
+    } else {
+        let mut writer = writer;
+        serde_json::to_writer(&mut writer, envelope)?;
+        writer
+    };
+    file.into_inner().map_err(|e| e.into_error())?.sync_all()?;
+    Ok(())
+}
+
+/// Reads a save from `path`, upgrading it with the built-in migrations
+pub fn load_game(path: &Path) -> Result<GameState, SaveError> {
+    load_game_with_migrations(path, &MigrationRegistry::default())
+}
+
+/// Reads a save from `path`, upgrading it with the given migrations
+pub fn load_game_with_migrations(path: &Path, migrations: &MigrationRegistry) -> Result<GameState, SaveError> {
+    info!("Loading game from {:?}", path);
+
+    let mut bytes = Vec::new();
+    File::open(path)?.read_to_end(&mut bytes)?;
+    if bytes.starts_with(&GZIP_MAGIC) {
+        let mut decompressed = Vec::new();
+        GzDecoder::new(bytes.as_slice()).read_to_end(&mut decompressed)?;
+        bytes = decompressed;
+    }
+
+    let envelope: SaveEnvelope = serde_json::from_slice(&bytes).map_err(|e| {
+        if e.is_data() || e.is_syntax() {
+            SaveError::NotASaveFile
+        } else {
+            SaveError::Serialization(e)
+        }
+    })?;
+    if envelope.format != SAVE_FORMAT_TAG {
+        return Err(SaveError::NotASaveFile);
+    }
+    if envelope.format_version > SAVE_FORMAT_VERSION {
+        return Err(SaveError::UnsupportedVersion {
+            found: envelope.format_version,
+            supported: SAVE_FORMAT_VERSION,
+        });
+    }
+
+    let mut game = envelope.game;
+    migrations.migrate(&mut game, envelope.format_version, SAVE_FORMAT_VERSION)?;
+    Ok(serde_json::from_value(game)?)
+}
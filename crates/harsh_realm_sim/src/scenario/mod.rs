@@ -0,0 +1,10 @@
+//! Data-driven scenario files describing a starting situation.
+//!
+//! A scenario names the solar system data to load, the calendar, the seed, the
+//! starting factions with their structures and stockpiles, and the victory
+//! conditions.  Bodies and structures are referenced by name so scenario files
+//! stay readable; the loader resolves them to ids.
+
+pub mod scenario_definition;
+pub mod scenario_error;
+pub mod scenario_loader;
@@ -0,0 +1,93 @@
+use std::collections::HashMap;
+use std::path::PathBuf;
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+
+use crate::population::person_type::PersonType;
+use crate::resources::resource_type::ResourceType;
+use crate::simulation::calendar::{CalendarDisplay, TurnLength};
+use crate::structures::installation::InstallationPurpose;
+use crate::structures::spacecraft::SpacecraftModuleType;
+use crate::units::unit_type::UnitType;
+use crate::victory::victory_condition::VictoryCondition;
+
+/// Top level of a scenario file
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScenarioDefinition {
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    /// Solar system CSV, relative to the scenario file
+    pub solar_system_data: PathBuf,
+    pub start_date: NaiveDate,
+    pub turn_length: TurnLength,
+    #[serde(default = "default_calendar_display")]
+    pub calendar_display: CalendarDisplay,
+    #[serde(default)]
+    pub seed: u64,
+    pub factions: Vec<ScenarioFaction>,
+    #[serde(default)]
+    pub victory_conditions: Vec<VictoryCondition>,
+}
+
+fn default_calendar_display() -> CalendarDisplay {
+    CalendarDisplay::Gregorian
+}
+
+/// A starting faction and everything it owns
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScenarioFaction {
+    pub name: String,
+    #[serde(default)]
+    pub treasury: f64,
+    #[serde(default)]
+    pub settlements: Vec<ScenarioSettlement>,
+    #[serde(default)]
+    pub installations: Vec<ScenarioInstallation>,
+    #[serde(default)]
+    pub spacecraft: Vec<ScenarioSpacecraft>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScenarioSettlement {
+    pub name: String,
+    pub location: ScenarioLocation,
+    #[serde(default)]
+    pub population: HashMap<PersonType, u32>,
+    #[serde(default)]
+    pub stockpile: HashMap<ResourceType, u64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScenarioInstallation {
+    pub name: String,
+    pub purpose: InstallationPurpose,
+    pub location: ScenarioLocation,
+    /// Left automated if absent
+    #[serde(default)]
+    pub crew: Option<UnitType>,
+    #[serde(default)]
+    pub stockpile: HashMap<ResourceType, u64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScenarioSpacecraft {
+    pub name: String,
+    pub location: ScenarioLocation,
+    #[serde(default)]
+    pub modules: HashMap<SpacecraftModuleType, u32>,
+    #[serde(default)]
+    pub cargo: HashMap<ResourceType, u32>,
+}
+
+/// A [`Location`](crate::maps::location::Location) with bodies and structures referenced by name
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum ScenarioLocation {
+    Surface { body: String, q: i32, r: i32 },
+    /// On the surface hex containing this latitude and longitude, in degrees
+    SurfaceAt { body: String, latitude: f64, longitude: f64 },
+    Orbit { body: String },
+    DeepSpace { x: f32, y: f32 },
+    /// Docked to the settlement, installation or spacecraft with this name
+    Docked { structure: String },
+}
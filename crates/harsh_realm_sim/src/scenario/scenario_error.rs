@@ -0,0 +1,52 @@
+use std::fmt::{Display, Formatter};
+
+/// Errors produced while loading a scenario
+#[derive(Debug)]
+pub enum ScenarioError {
+    Io(std::io::Error),
+    Parse(serde_json::Error),
+    /// The solar system data named by the scenario could not be loaded
+    SolarSystemData(Box<dyn std::error::Error>),
+    /// The scenario parsed but refers to things that do not exist or conflict
+    Invalid(Vec<String>),
+}
+
+impl Display for ScenarioError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ScenarioError::Io(e) => write!(f, "failed to read scenario: {}", e),
+            ScenarioError::Parse(e) => write!(f, "scenario is malformed: {}", e),
+            ScenarioError::SolarSystemData(e) => write!(f, "failed to load solar system data: {}", e),
+            ScenarioError::Invalid(issues) => {
+                write!(f, "scenario has {} problem(s):", issues.len())?;
+                for issue in issues {
+                    write!(f, "\n  - {}", issue)?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+impl std::error::Error for ScenarioError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            ScenarioError::Io(e) => Some(e),
+            ScenarioError::Parse(e) => Some(e),
+            ScenarioError::SolarSystemData(e) => Some(e.as_ref()),
+            ScenarioError::Invalid(_) => None,
+        }
+    }
+}
+
+impl From<std::io::Error> for ScenarioError {
+    fn from(e: std::io::Error) -> Self {
+        ScenarioError::Io(e)
+    }
+}
+
+impl From<serde_json::Error> for ScenarioError {
+    fn from(e: serde_json::Error) -> Self {
+        ScenarioError::Parse(e)
+    }
+}
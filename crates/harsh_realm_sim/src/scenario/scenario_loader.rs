@@ -0,0 +1,300 @@
+use std::collections::{HashMap, HashSet};
+use std::fs::File;
+use std::io::BufReader;
+use std::path::Path;
+use hex2d::Coordinate as HexCoord;
+use log::info;
+use uuid::Uuid;
+
+use crate::faction::faction::Faction;
+use crate::game_state::GameState;
+use crate::maps::location::Location;
+use crate::scenario::scenario_definition::{ScenarioDefinition, ScenarioLocation};
+use crate::scenario::scenario_error::ScenarioError;
+use crate::simulation::calendar::{GameCalendar, TurnLength};
+use crate::structures::installation::Installation;
+use crate::structures::settlement::Settlement;
+use crate::structures::spacecraft::Spacecraft;
+use crate::structures::structure_type::StructureType;
+use crate::universe::solar_system_manager::SolarSystemManager;
+use crate::victory::victory_condition::VictoryCondition;
+
+/// Reads a scenario file and builds a ready-to-play game from it
+pub fn load_scenario(path: &Path) -> Result<GameState, ScenarioError> {
+    let definition = read_scenario(path)?;
+    let base_dir = path.parent().unwrap_or_else(|| Path::new("."));
+    build_game(&definition, base_dir)
+}
+
+/// Like [`load_scenario`], but plays the scenario with `seed` instead of its own.
+///
+/// The seed is in place before any surface map is generated, so terrain and
+/// deposits follow it too.
+pub fn load_scenario_with_seed(path: &Path, seed: u64) -> Result<GameState, ScenarioError> {
+    let mut definition = read_scenario(path)?;
+    definition.seed = seed;
+    let base_dir = path.parent().unwrap_or_else(|| Path::new("."));
+    build_game(&definition, base_dir)
+}
+
+/// Parses a scenario file without building the game
+pub fn read_scenario(path: &Path) -> Result<ScenarioDefinition, ScenarioError> {
+    info!("Reading scenario from {:?}", path);
+    let reader = BufReader::new(File::open(path)?);
+    Ok(serde_json::from_reader(reader)?)
+}
+
+/// Builds a game from a parsed scenario, resolving data paths relative to `base_dir`
+pub fn build_game(definition: &ScenarioDefinition, base_dir: &Path) -> Result<GameState, ScenarioError> {
+    info!("Building scenario '{}'", definition.name);
+
+    let calendar = GameCalendar::new(definition.start_date, definition.turn_length, definition.calendar_display);
+    let mut state = GameState::with_calendar(calendar);
+    state.simulation.seed = definition.seed;
+    state
+        .solar_system
+        .load_from_csv(&base_dir.join(&definition.solar_system_data))
+        .map_err(ScenarioError::SolarSystemData)?;
+
+    let issues = validate(definition, &state.solar_system);
+    if !issues.is_empty() {
+        return Err(ScenarioError::Invalid(issues));
+    }
+
+    // Structures that are docked are placed after everything they can dock to exists
+    let mut structure_ids: HashMap<&str, Uuid> = HashMap::new();
+    let mut docked = Vec::new();
+
+    for scenario_faction in &definition.factions {
+        let faction = Faction::new(scenario_faction.name.clone()).with_treasury(scenario_faction.treasury);
+        let faction_id = state.simulation.add_faction(faction);
+
+        for settlement in &scenario_faction.settlements {
+            let location = resolve_location(&settlement.location, &state.solar_system, &structure_ids);
+            let structure = Settlement::new(settlement.name.clone(), location.clone())
+                .with_owner(faction_id)
+                .with_population(settlement.population.clone())
+                .with_resources(settlement.stockpile.clone());
+            let id = state.simulation.add_structure(StructureType::Settlement(structure));
+            claim_tile(&mut state, &location, faction_id, id);
+            structure_ids.insert(&settlement.name, id);
+        }
+
+        for installation in &scenario_faction.installations {
+            let location = resolve_location(&installation.location, &state.solar_system, &structure_ids);
+            let mut structure = Installation::new(installation.name.clone(), installation.purpose.clone(), location.clone())
+                .with_owner(faction_id)
+                .with_resources(installation.stockpile.clone());
+            if let Some(crew) = &installation.crew {
+                structure = structure.with_crew(crew.clone());
+            }
+            let id = state.simulation.add_structure(StructureType::Installation(structure));
+            claim_tile(&mut state, &location, faction_id, id);
+            structure_ids.insert(&installation.name, id);
+        }
+
+        for spacecraft in &scenario_faction.spacecraft {
+            if matches!(spacecraft.location, ScenarioLocation::Docked { .. }) {
+                docked.push((faction_id, spacecraft));
+                continue;
+            }
+            let location = resolve_location(&spacecraft.location, &state.solar_system, &structure_ids);
+            let id = state.simulation.add_structure(StructureType::Spacecraft(
+                Spacecraft::new(spacecraft.name.clone(), location)
+                    .with_owner(faction_id)
+                    .with_modules(spacecraft.modules.clone())
+                    .with_cargo(spacecraft.cargo.clone()),
+            ));
+            structure_ids.insert(&spacecraft.name, id);
+        }
+    }
+
+    for (faction_id, spacecraft) in docked {
+        let location = resolve_location(&spacecraft.location, &state.solar_system, &structure_ids);
+        state.simulation.add_structure(StructureType::Spacecraft(
+            Spacecraft::new(spacecraft.name.clone(), location)
+                .with_owner(faction_id)
+                .with_modules(spacecraft.modules.clone())
+                .with_cargo(spacecraft.cargo.clone()),
+        ));
+    }
+
+    state.victory_conditions = definition.victory_conditions.clone();
+
+    info!(
+        "Scenario '{}' ready: {} factions, {} structures",
+        definition.name,
+        state.simulation.factions.len(),
+        state.simulation.structures.len()
+    );
+    Ok(state)
+}
+
+/// Marks the surface hex under a settlement or installation as built on and owned
+fn claim_tile(state: &mut GameState, location: &Location, faction_id: Uuid, structure_id: Uuid) {
+    if let Some(tile) = state.solar_system.tile_mut(location, state.simulation.seed) {
+        tile.owner = Some(faction_id);
+        tile.structure_id = Some(structure_id);
+    }
+}
+
+/// Checks a scenario against the loaded solar system and returns every problem found
+pub fn validate(definition: &ScenarioDefinition, solar_system: &SolarSystemManager) -> Vec<String> {
+    let mut issues = Vec::new();
+
+    if definition.factions.is_empty() {
+        issues.push("scenario defines no factions".to_string());
+    }
+    let zero_length = match definition.turn_length {
+        TurnLength::CalendarMonths(n) | TurnLength::Days(n) | TurnLength::Sols(n) => n == 0,
+    };
+    if zero_length {
+        issues.push("turn length must be greater than zero".to_string());
+    }
+
+    let mut faction_names = HashSet::new();
+    let mut structure_names = HashSet::new();
+    // Structures that can be docked to, i.e. everything that is not itself docked
+    let mut dock_targets = HashSet::new();
+    let mut occupied_hexes = HashSet::new();
+
+    for faction in &definition.factions {
+        if faction.name.trim().is_empty() {
+            issues.push("faction with an empty name".to_string());
+        } else if !faction_names.insert(faction.name.as_str()) {
+            issues.push(format!("duplicate faction name '{}'", faction.name));
+        }
+
+        let fixed_structures = faction
+            .settlements
+            .iter()
+            .map(|s| (&s.name, &s.location))
+            .chain(faction.installations.iter().map(|i| (&i.name, &i.location)));
+        for (name, location) in fixed_structures {
+            if matches!(location, ScenarioLocation::Docked { .. }) {
+                issues.push(format!("'{}' cannot be docked; only spacecraft can dock", name));
+            }
+            if let Some((body, hex)) = surface_hex(location, solar_system) {
+                if !occupied_hexes.insert((body, hex)) {
+                    issues.push(format!(
+                        "'{}' is on hex ({}, {}) of {}, which already holds a settlement or installation",
+                        name, hex.x, hex.y, body
+                    ));
+                }
+            }
+        }
+
+        for installation in &faction.installations {
+            if let Some(crew) = installation.crew.as_ref().filter(|crew| !installation.purpose.accepts_crew(crew)) {
+                issues.push(format!("'{}' cannot be crewed by {:?}", installation.name, crew));
+            }
+        }
+
+        let all_structures = faction
+            .settlements
+            .iter()
+            .map(|s| (&s.name, &s.location))
+            .chain(faction.installations.iter().map(|i| (&i.name, &i.location)))
+            .chain(faction.spacecraft.iter().map(|s| (&s.name, &s.location)));
+        for (name, location) in all_structures {
+            if !structure_names.insert(name.as_str()) {
+                issues.push(format!("duplicate structure name '{}'", name));
+            }
+            if !matches!(location, ScenarioLocation::Docked { .. }) {
+                dock_targets.insert(name.as_str());
+            }
+            if let ScenarioLocation::Surface { body, .. }
+            | ScenarioLocation::SurfaceAt { body, .. }
+            | ScenarioLocation::Orbit { body } = location
+            {
+                match (solar_system.get_body(body), location) {
+                    (None, _) => issues.push(format!("'{}' refers to unknown body '{}'", name, body)),
+                    (Some(celestial_body), ScenarioLocation::Surface { .. } | ScenarioLocation::SurfaceAt { .. })
+                        if celestial_body.is_giant() =>
+                    {
+                        issues.push(format!("'{}' is on the surface of {}, which has no surface to build on", name, body));
+                    }
+                    (Some(celestial_body), ScenarioLocation::Surface { q, r, .. })
+                        if !celestial_body.surface_grid().contains(HexCoord::new(*q, *r)) =>
+                    {
+                        issues.push(format!("'{}' is on hex ({}, {}), which is off the surface of {}", name, q, r, body));
+                    }
+                    (Some(_), ScenarioLocation::SurfaceAt { latitude, longitude, .. })
+                        if !(-90.0..=90.0).contains(latitude) || !longitude.is_finite() =>
+                    {
+                        issues.push(format!(
+                            "'{}' is at latitude {}, longitude {}, which is not a point on {}",
+                            name, latitude, longitude, body
+                        ));
+                    }
+                    _ => {}
+                }
+            }
+        }
+    }
+
+    for spacecraft in definition.factions.iter().flat_map(|f| &f.spacecraft) {
+        if let ScenarioLocation::Docked { structure } = &spacecraft.location {
+            if !dock_targets.contains(structure.as_str()) {
+                issues.push(format!(
+                    "'{}' is docked to '{}', which is not an undocked structure in this scenario",
+                    spacecraft.name, structure
+                ));
+            }
+        }
+    }
+
+    for condition in &definition.victory_conditions {
+        match condition {
+            VictoryCondition::EconomicDominance { share } if !(*share > 0.0 && *share <= 1.0) => {
+                issues.push(format!("economic dominance share {} must be in (0, 1]", share));
+            }
+            VictoryCondition::ControlBodies { bodies } => {
+                for body in bodies.iter().filter(|b| solar_system.get_body(b).is_none()) {
+                    issues.push(format!("victory condition refers to unknown body '{}'", body));
+                }
+            }
+            VictoryCondition::TurnLimit { turns: 0 } => {
+                issues.push("turn limit must be greater than zero".to_string());
+            }
+            _ => {}
+        }
+    }
+
+    issues
+}
+
+/// Resolves a surface location to its body name and hex, if the body exists
+fn surface_hex<'a>(location: &'a ScenarioLocation, solar_system: &SolarSystemManager) -> Option<(&'a str, HexCoord)> {
+    match location {
+        ScenarioLocation::Surface { body, q, r } => Some((body, HexCoord::new(*q, *r))),
+        ScenarioLocation::SurfaceAt { body, latitude, longitude } => {
+            let grid = solar_system.get_body(body)?.surface_grid();
+            Some((body, grid.tile_at(*latitude, *longitude)))
+        }
+        _ => None,
+    }
+}
+
+/// Converts a validated scenario location to a game location
+fn resolve_location(
+    location: &ScenarioLocation,
+    solar_system: &SolarSystemManager,
+    structure_ids: &HashMap<&str, Uuid>,
+) -> Location {
+    let body_id = |name: &str| solar_system.get_body(name).map(|b| b.id).unwrap_or_default();
+    match location {
+        ScenarioLocation::Surface { body, .. } | ScenarioLocation::SurfaceAt { body, .. } => Location::Surface {
+            body_id: body_id(body),
+            hex_coord: surface_hex(location, solar_system).map_or(HexCoord::new(0, 0), |(_, hex)| hex),
+        },
+        ScenarioLocation::Orbit { body } => Location::Orbit {
+            body_id: body_id(body),
+            orbital_slot_id: Uuid::new_v4(),
+        },
+        ScenarioLocation::DeepSpace { x, y } => Location::DeepSpace { x: *x, y: *y },
+        ScenarioLocation::Docked { structure } => Location::Docked {
+            structure_id: structure_ids.get(structure.as_str()).copied().unwrap_or_default(),
+        },
+    }
+}
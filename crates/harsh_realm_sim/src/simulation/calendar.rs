@@ -0,0 +1,159 @@
+use chrono::{Months, NaiveDate};
+use serde::{Deserialize, Serialize};
+
+/// Length of a Mars solar day in Earth days
+pub const SOL_IN_DAYS: f64 = 1.0274912517;
+
+/// Length of a Mars year in Earth days
+pub const MARS_YEAR_IN_DAYS: f64 = 686.9726;
+
+/// Start of Mars Year 1 (Clancy et al. convention, Ls = 0 on 1955-04-11)
+const MARS_YEAR_EPOCH: (i32, u32, u32) = (1955, 4, 11);
+
+/// How much game time a single turn covers
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum TurnLength {
+    /// Whole calendar months, so turn length follows month length
+    CalendarMonths(u32),
+    /// A fixed number of Earth days
+    Days(u32),
+    /// A fixed number of Mars sols
+    Sols(u32),
+}
+
+impl TurnLength {
+    /// Gets the length in Earth days of a turn starting on `date`
+    pub fn days_from(&self, date: NaiveDate) -> f64 {
+        match *self {
+            TurnLength::CalendarMonths(months) => date
+                .checked_add_months(Months::new(months))
+                .map(|end| (end - date).num_days() as f64)
+                .unwrap_or(0.0),
+            TurnLength::Days(days) => days as f64,
+            TurnLength::Sols(sols) => sols as f64 * SOL_IN_DAYS,
+        }
+    }
+}
+
+/// How dates are shown to the player
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CalendarDisplay {
+    /// "2070 January 01"
+    Gregorian,
+    /// "MY 112 Sol 301", counted from the Mars Year 1 epoch
+    MarsSol,
+}
+
+/// Per-scenario calendar: start date, turn length and date display
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GameCalendar {
+    pub start_date: NaiveDate,
+    pub turn_length: TurnLength,
+    pub display: CalendarDisplay,
+    /// Fraction of a day carried over from turns that are not a whole number of days
+    #[serde(default)]
+    day_carry: f64,
+}
+
+impl GameCalendar {
+    pub fn new(start_date: NaiveDate, turn_length: TurnLength, display: CalendarDisplay) -> Self {
+        Self {
+            start_date,
+            turn_length,
+            display,
+            day_carry: 0.0,
+        }
+    }
+
+    /// Changes the turn length from the next turn onwards
+    pub fn set_turn_length(&mut self, turn_length: TurnLength) {
+        self.turn_length = turn_length;
+    }
+
+    /// Gets the whole number of days the turn starting on `date` advances the clock by.
+    ///
+    /// Fractional days (from sol-based turns) are carried into later turns so the
+    /// game date never drifts from the accumulated turn lengths.
+    pub fn advance_turn(&mut self, date: NaiveDate) -> u32 {
+        let exact = self.turn_length.days_from(date) + self.day_carry;
+        let whole = exact.floor();
+        self.day_carry = exact - whole;
+        whole as u32
+    }
+
+    /// Formats `date` using this calendar's display
+    pub fn format_date(&self, date: NaiveDate) -> String {
+        match self.display {
+            CalendarDisplay::Gregorian => date.format("%Y %B %d").to_string(),
+            CalendarDisplay::MarsSol => {
+                let (year, sol) = mars_year_and_sol(date);
+                format!("MY {} Sol {}", year, sol)
+            }
+        }
+    }
+}
+
+impl Default for GameCalendar {
+    fn default() -> Self {
+        Self::new(
+            NaiveDate::from_ymd_opt(2070, 1, 1).unwrap(),
+            TurnLength::Days(30),
+            CalendarDisplay::Gregorian,
+        )
+    }
+}
+
+/// Converts an Earth date to a Mars year and 1-based sol of that year
+pub fn mars_year_and_sol(date: NaiveDate) -> (i64, u32) {
+    let (y, m, d) = MARS_YEAR_EPOCH;
+    let epoch = NaiveDate::from_ymd_opt(y, m, d).unwrap();
+    let days = (date - epoch).num_days() as f64;
+    let year = (days / MARS_YEAR_IN_DAYS).floor();
+    let day_of_year = days - year * MARS_YEAR_IN_DAYS;
+    let sol = (day_of_year / SOL_IN_DAYS).floor() as u32 + 1;
+    (year as i64 + 1, sol)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_calendar_month_turns_follow_month_length() {
+        let mut calendar = GameCalendar::new(
+            NaiveDate::from_ymd_opt(2072, 1, 1).unwrap(),
+            TurnLength::CalendarMonths(1),
+            CalendarDisplay::Gregorian,
+        );
+
+        assert_eq!(calendar.advance_turn(NaiveDate::from_ymd_opt(2072, 1, 1).unwrap()), 31);
+        assert_eq!(calendar.advance_turn(NaiveDate::from_ymd_opt(2072, 2, 1).unwrap()), 29);
+        assert_eq!(calendar.advance_turn(NaiveDate::from_ymd_opt(2073, 2, 1).unwrap()), 28);
+    }
+
+    #[test]
+    fn test_sol_turns_carry_fractional_days() {
+        let start = NaiveDate::from_ymd_opt(2070, 1, 1).unwrap();
+        let mut calendar = GameCalendar::new(start, TurnLength::Sols(10), CalendarDisplay::MarsSol);
+
+        let mut date = start;
+        for _ in 0..10 {
+            date += chrono::Duration::days(calendar.advance_turn(date) as i64);
+        }
+
+        // 100 sols is 102.7 Earth days
+        assert_eq!((date - start).num_days(), 102);
+    }
+
+    #[test]
+    fn test_mars_sol_display() {
+        let calendar = GameCalendar::new(
+            NaiveDate::from_ymd_opt(1955, 4, 11).unwrap(),
+            TurnLength::Days(1),
+            CalendarDisplay::MarsSol,
+        );
+
+        assert_eq!(calendar.format_date(NaiveDate::from_ymd_opt(1955, 4, 11).unwrap()), "MY 1 Sol 1");
+        assert_eq!(calendar.format_date(NaiveDate::from_ymd_opt(1957, 2, 26).unwrap()), "MY 2 Sol 1");
+    }
+}
@@ -1,2 +1,6 @@
+#[allow(clippy::module_inception)]
 pub mod simulation;
-mod turn_processor;
\ No newline at end of file
+pub mod calendar;
+pub mod real_time;
+pub mod turn_history;
+pub mod turn_processor;
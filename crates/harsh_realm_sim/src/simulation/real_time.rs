@@ -0,0 +1,114 @@
+use std::collections::HashSet;
+use serde::{Deserialize, Serialize};
+
+use crate::events::game_event::EventCategory;
+
+/// How fast game time passes in real-time mode
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum SimulationSpeed {
+    DayPerSecond,
+    WeekPerSecond,
+    MonthPerSecond,
+    /// Any other rate, in game days per real second
+    Custom(f64),
+}
+
+impl SimulationSpeed {
+    /// Gets the number of game days that pass per real second
+    pub fn days_per_second(&self) -> f64 {
+        match *self {
+            SimulationSpeed::DayPerSecond => 1.0,
+            SimulationSpeed::WeekPerSecond => 7.0,
+            SimulationSpeed::MonthPerSecond => 30.0,
+            SimulationSpeed::Custom(days) => days.max(0.0),
+        }
+    }
+}
+
+/// Things that can automatically pause real-time mode
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum PauseTrigger {
+    TurnEnded,
+    NewMonth,
+    NewYear,
+    /// The simulation emitted an event of this category
+    Event(EventCategory),
+}
+
+/// Outcome of feeding real time into the clock
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ClockTick {
+    pub days_advanced: u32,
+    pub turns_completed: u32,
+    /// Set when the clock paused itself during this tick
+    pub paused_by: Option<PauseTrigger>,
+}
+
+/// State for the real-time-with-pause mode.
+///
+/// The clock converts real seconds into whole game days, which are fed one at a
+/// time through the same phase pipeline as discrete turns.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RealTimeClock {
+    pub speed: SimulationSpeed,
+    pub paused: bool,
+    pub pause_on: HashSet<PauseTrigger>,
+    /// Game time accumulated but not yet simulated, in days
+    #[serde(skip)]
+    pending_days: f64,
+}
+
+impl RealTimeClock {
+    pub fn new(speed: SimulationSpeed) -> Self {
+        Self {
+            speed,
+            paused: true,
+            pause_on: HashSet::new(),
+            pending_days: 0.0,
+        }
+    }
+
+    pub fn pause(&mut self) {
+        self.paused = true;
+        self.pending_days = 0.0;
+    }
+
+    pub fn resume(&mut self) {
+        self.paused = false;
+    }
+
+    pub fn toggle_pause(&mut self) {
+        if self.paused {
+            self.resume();
+        } else {
+            self.pause();
+        }
+    }
+
+    /// Enables or disables auto-pausing on a trigger
+    pub fn set_pause_on(&mut self, trigger: PauseTrigger, enabled: bool) {
+        if enabled {
+            self.pause_on.insert(trigger);
+        } else {
+            self.pause_on.remove(&trigger);
+        }
+    }
+
+    /// Adds `real_seconds` of wall-clock time and returns how many whole game days are due
+    pub fn accumulate(&mut self, real_seconds: f64) -> u32 {
+        if self.paused {
+            return 0;
+        }
+        self.pending_days += real_seconds.max(0.0) * self.speed.days_per_second();
+        let whole = self.pending_days.floor();
+        self.pending_days -= whole;
+        whole as u32
+    }
+}
+
+impl Default for RealTimeClock {
+    /// Paused at one day per second with no auto-pause triggers
+    fn default() -> Self {
+        Self::new(SimulationSpeed::DayPerSecond)
+    }
+}
@@ -1,14 +1,34 @@
-use log::info;
+use std::collections::HashMap;
+use log::{debug, info};
 use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use crate::events::event_bus::EventBus;
+use crate::events::game_event::GameEventKind;
+use crate::faction::faction::Faction;
+use crate::production::production_queue::Workshop;
+use crate::resources::resource_type::ResourceType;
+use crate::structures::fleet::Fleet;
+use crate::structures::structure_type::StructureType;
+
+/// Food each person eats a day
+pub const FOOD_PER_PERSON_DAY: f64 = 0.01;
 
 #[derive(Debug,Serialize,Deserialize,Clone)]
 pub struct Simulation {
     pub current_turn: u64,
+    /// Seed for all deterministic randomness in the game
+    #[serde(default)]
+    pub seed: u64,
+    pub factions: HashMap<Uuid, Faction>,
+    pub structures: HashMap<Uuid, StructureType>,
+    /// Groups of spacecraft sailing together
+    #[serde(default)]
+    pub fleets: HashMap<Uuid, Fleet>,
+    /// Domain events of recent turns and any subscribers have yet to read; not persisted in saves
+    #[serde(skip)]
+    pub events: EventBus,
     // placeholder for other elements
     // pub solar_system: SolarSystem,
-    // pub factions: HashMap<Uuid, Faction>,
-    // pub structures: HashMap<Uuid, StructureType>,
-    // pub events_log: Vec<NarrativeEvent>,
 }
 
 impl Simulation {
@@ -16,9 +36,61 @@ impl Simulation {
         info!("Initializing simulation");
         Simulation {
             current_turn: 0,
+            seed: 0,
+            factions: HashMap::new(),
+            structures: HashMap::new(),
+            fleets: HashMap::new(),
+            events: EventBus::default(),
         }
     }
+
+    /// Adds a faction to the simulation and returns its id
+    pub fn add_faction(&mut self, faction: Faction) -> Uuid {
+        let id = faction.id();
+        self.factions.insert(id, faction);
+        id
+    }
+
+    /// Adds a structure to the simulation and returns its id
+    pub fn add_structure(&mut self, structure: StructureType) -> Uuid {
+        let id = structure.id();
+        self.structures.insert(id, structure);
+        id
+    }
     
+    /// Runs the economy phase over `days` elapsed days
+    pub fn process_economy(&mut self, days: u32) {
+        debug!("Processing economy for {} days", days);
+        // TODO: resource production/consumption and construction
+    }
+
+    /// Runs the population phase over `days` elapsed days: every settlement whose
+    /// Food would not feed its people that long reports how many would go hungry
+    pub fn process_population(&mut self, days: u32) {
+        debug!("Processing population for {} days", days);
+        let mut ids: Vec<Uuid> = self.structures.keys().copied().collect();
+        ids.sort();
+
+        for id in ids {
+            let Some(StructureType::Settlement(settlement)) = self.structures.get(&id) else {
+                continue;
+            };
+            let people = settlement.total_population() as u64;
+            let needed = (people as f64 * FOOD_PER_PERSON_DAY * days as f64).ceil() as u64;
+            if needed == 0 {
+                continue;
+            }
+            let stored = settlement.stockpile().available(&ResourceType::Food);
+            if stored < needed {
+                let shortfall = people - people * stored / needed;
+                let owner = settlement.owner();
+                self.events.emit_for(owner, GameEventKind::PopulationStarving { structure_id: id, shortfall });
+            }
+        }
+        // TODO: population growth
+    }
+
+    /// Ends the current turn
     pub fn process_turn(&mut self) {
         self.current_turn += 1;
         info!("Processing turn {}", self.current_turn);
@@ -35,4 +107,4 @@ impl Default for Simulation {
     fn default() -> Self {
         Self::new()
     }
-}
\ No newline at end of file
+}
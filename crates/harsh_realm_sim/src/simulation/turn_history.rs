@@ -0,0 +1,196 @@
+use std::collections::VecDeque;
+use std::fmt::{Debug, Display, Formatter};
+use std::sync::Arc;
+
+/// Number of turn snapshots kept when no capacity is configured
+pub const DEFAULT_HISTORY_CAPACITY: usize = 10;
+
+/// Errors produced when rewinding to a turn
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RewindError {
+    /// The requested turn is not (or no longer) held in the history
+    TurnNotInHistory { requested: u64, oldest: Option<u64>, newest: Option<u64> },
+}
+
+impl Display for RewindError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RewindError::TurnNotInHistory { requested, oldest: Some(oldest), newest: Some(newest) } => write!(
+                f,
+                "turn {} is not in history (available turns {}..={})",
+                requested, oldest, newest
+            ),
+            RewindError::TurnNotInHistory { requested, .. } => {
+                write!(f, "turn {} is not in history (history is empty)", requested)
+            }
+        }
+    }
+}
+
+impl std::error::Error for RewindError {}
+
+/// Ring buffer holding the most recent turn snapshots.
+///
+/// Snapshots are shared behind an [`Arc`], so cloning the history (and the state
+/// that owns it) does not copy the snapshots themselves.  Once `capacity` turns
+/// are held, recording a new turn evicts the oldest one.
+#[derive(Clone)]
+pub struct TurnHistory<T> {
+    capacity: usize,
+    snapshots: VecDeque<(u64, Arc<T>)>,
+}
+
+impl<T> TurnHistory<T> {
+    /// Creates a history keeping at most `capacity` snapshots (0 disables recording)
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            snapshots: VecDeque::with_capacity(capacity),
+        }
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Changes the capacity, evicting the oldest snapshots if necessary
+    pub fn set_capacity(&mut self, capacity: usize) {
+        self.capacity = capacity;
+        while self.snapshots.len() > capacity {
+            self.snapshots.pop_front();
+        }
+    }
+
+    /// Records the snapshot for `turn`, replacing any snapshots at or after that turn
+    pub fn record(&mut self, turn: u64, snapshot: T) {
+        if self.capacity == 0 {
+            return;
+        }
+        self.truncate_after(turn);
+        if self.newest_turn() == Some(turn) {
+            self.snapshots.pop_back();
+        }
+        if self.snapshots.len() == self.capacity {
+            self.snapshots.pop_front();
+        }
+        self.snapshots.push_back((turn, Arc::new(snapshot)));
+    }
+
+    /// Returns true if a snapshot for `turn` is held
+    pub fn contains(&self, turn: u64) -> bool {
+        self.snapshots.iter().any(|(t, _)| *t == turn)
+    }
+
+    /// Gets the snapshot recorded for `turn`
+    pub fn get(&self, turn: u64) -> Option<&Arc<T>> {
+        self.snapshots.iter().find(|(t, _)| *t == turn).map(|(_, s)| s)
+    }
+
+    /// Drops every snapshot after `turn` and returns the snapshot for `turn`
+    pub fn rewind_to(&mut self, turn: u64) -> Result<Arc<T>, RewindError> {
+        let snapshot = self.get(turn).cloned().ok_or(RewindError::TurnNotInHistory {
+            requested: turn,
+            oldest: self.oldest_turn(),
+            newest: self.newest_turn(),
+        })?;
+        self.truncate_after(turn);
+        Ok(snapshot)
+    }
+
+    /// Gets the turns currently held, oldest first
+    pub fn turns(&self) -> Vec<u64> {
+        self.snapshots.iter().map(|(t, _)| *t).collect()
+    }
+
+    pub fn oldest_turn(&self) -> Option<u64> {
+        self.snapshots.front().map(|(t, _)| *t)
+    }
+
+    pub fn newest_turn(&self) -> Option<u64> {
+        self.snapshots.back().map(|(t, _)| *t)
+    }
+
+    pub fn len(&self) -> usize {
+        self.snapshots.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.snapshots.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.snapshots.clear();
+    }
+
+    fn truncate_after(&mut self, turn: u64) {
+        while self.newest_turn().is_some_and(|t| t > turn) {
+            self.snapshots.pop_back();
+        }
+    }
+}
+
+impl<T> Default for TurnHistory<T> {
+    fn default() -> Self {
+        Self::new(DEFAULT_HISTORY_CAPACITY)
+    }
+}
+
+impl<T> Debug for TurnHistory<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TurnHistory")
+            .field("capacity", &self.capacity)
+            .field("turns", &self.turns())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_evicts_oldest_when_full() {
+        let mut history = TurnHistory::new(3);
+        for turn in 0..5 {
+            history.record(turn, turn * 10);
+        }
+
+        assert_eq!(history.turns(), vec![2, 3, 4]);
+        assert!(!history.contains(1));
+    }
+
+    #[test]
+    fn test_rewind_drops_newer_turns() {
+        let mut history = TurnHistory::new(5);
+        for turn in 0..4 {
+            history.record(turn, turn * 10);
+        }
+
+        let snapshot = history.rewind_to(1).unwrap();
+        assert_eq!(*snapshot, 10);
+        assert_eq!(history.turns(), vec![0, 1]);
+
+        // Replaying from turn 1 overwrites the branch that was rewound away
+        history.record(2, 99);
+        assert_eq!(**history.get(2).unwrap(), 99);
+    }
+
+    #[test]
+    fn test_rewind_to_missing_turn() {
+        let mut history = TurnHistory::new(2);
+        history.record(5, ());
+        history.record(6, ());
+
+        assert_eq!(
+            history.rewind_to(1),
+            Err(RewindError::TurnNotInHistory { requested: 1, oldest: Some(5), newest: Some(6) })
+        );
+    }
+
+    #[test]
+    fn test_zero_capacity_records_nothing() {
+        let mut history = TurnHistory::new(0);
+        history.record(0, ());
+        assert!(history.is_empty());
+    }
+}
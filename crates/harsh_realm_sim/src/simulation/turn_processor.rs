@@ -1,13 +1,176 @@
 // src/simulation/turn_processor.rs
-// This file can contain logic for processing specific phases of a turn,
-// if you want to break down Simulation::process_turn into smaller,
-// more manageable functions. For now, it might be simple or even empty if
-// all turn logic is directly in Simulation.
-
-// Example placeholder if you want to use it later:
-// use crate::simulation::simulation::Simulation;
-//
-// pub fn process_game_turn(simulation: &mut Simulation) {
-//     // Delegating to the Simulation struct for now
-//     simulation.process_turn();
-// }
\ No newline at end of file
+// Phased pipeline that advances the world through time.  Both discrete turns
+// and the real-time clock feed elapsed days through the same pipeline, which
+// decides which subsystem phases are due to run.
+
+use chrono::{Datelike, NaiveDate};
+use serde::{Deserialize, Serialize};
+
+/// A subsystem update, listed in the order phases run within a tick
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum TurnPhase {
+    /// Celestial body positions
+    Orbits,
+    /// Spacecraft travelling to their destinations
+    Movement,
+    /// Resource production, consumption and construction
+    Economy,
+    /// Population growth and needs
+    Population,
+    /// Sensor sweeps and intel delivery for each faction's knowledge
+    Sensors,
+}
+
+/// How often a phase runs
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PhaseCadence {
+    /// Whenever time advances, however little
+    EveryTick,
+    /// Once at least one day has accumulated
+    Daily,
+    /// When the calendar month changes
+    Monthly,
+}
+
+/// A phase together with its cadence and the days accumulated since it last ran
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PhaseSchedule {
+    pub phase: TurnPhase,
+    pub cadence: PhaseCadence,
+    pending_days: u32,
+}
+
+/// Ordered set of phase schedules plus progress through the current turn
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TurnPipeline {
+    schedules: Vec<PhaseSchedule>,
+    /// Days left before the current turn ends (0 when no turn is in progress)
+    turn_days_remaining: u32,
+}
+
+impl TurnPipeline {
+    /// Creates a pipeline running the given phases in order
+    pub fn new(phases: Vec<(TurnPhase, PhaseCadence)>) -> Self {
+        Self {
+            schedules: phases
+                .into_iter()
+                .map(|(phase, cadence)| PhaseSchedule {
+                    phase,
+                    cadence,
+                    pending_days: 0,
+                })
+                .collect(),
+            turn_days_remaining: 0,
+        }
+    }
+
+    /// Changes the cadence of a phase
+    pub fn set_cadence(&mut self, phase: TurnPhase, cadence: PhaseCadence) {
+        for schedule in self.schedules.iter_mut().filter(|s| s.phase == phase) {
+            schedule.cadence = cadence;
+        }
+    }
+
+    /// Gets the phase schedules in run order
+    pub fn schedules(&self) -> &[PhaseSchedule] {
+        &self.schedules
+    }
+
+    /// Returns true if a turn has started and not yet ended
+    pub fn turn_in_progress(&self) -> bool {
+        self.turn_days_remaining > 0
+    }
+
+    pub fn turn_days_remaining(&self) -> u32 {
+        self.turn_days_remaining
+    }
+
+    /// Starts a turn lasting `days` days
+    pub fn begin_turn(&mut self, days: u32) {
+        self.turn_days_remaining = days;
+    }
+
+    /// Counts `days` against the current turn and returns true if the turn has ended
+    pub fn consume_turn_days(&mut self, days: u32) -> bool {
+        self.turn_days_remaining = self.turn_days_remaining.saturating_sub(days);
+        self.turn_days_remaining == 0
+    }
+
+    /// Records `days` of elapsed time from `previous_date` to `date` and returns the
+    /// phases now due, in run order, with the days each has to catch up on.
+    pub fn advance(&mut self, days: u32, previous_date: NaiveDate, date: NaiveDate) -> Vec<(TurnPhase, u32)> {
+        let month_changed = (previous_date.year(), previous_date.month()) != (date.year(), date.month());
+
+        let mut due = Vec::new();
+        for schedule in &mut self.schedules {
+            schedule.pending_days += days;
+            let is_due = match schedule.cadence {
+                PhaseCadence::EveryTick => true,
+                PhaseCadence::Daily => schedule.pending_days >= 1,
+                PhaseCadence::Monthly => month_changed,
+            };
+            if is_due {
+                due.push((schedule.phase, schedule.pending_days));
+                schedule.pending_days = 0;
+            }
+        }
+        due
+    }
+}
+
+impl Default for TurnPipeline {
+    /// Orbits every tick, movement and economy daily, population monthly and sensors daily
+    fn default() -> Self {
+        Self::new(vec![
+            (TurnPhase::Orbits, PhaseCadence::EveryTick),
+            (TurnPhase::Movement, PhaseCadence::Daily),
+            (TurnPhase::Economy, PhaseCadence::Daily),
+            (TurnPhase::Population, PhaseCadence::Monthly),
+            (TurnPhase::Sensors, PhaseCadence::Daily),
+        ])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    #[test]
+    fn test_phases_run_at_their_cadence() {
+        let mut pipeline = TurnPipeline::default();
+
+        let due = pipeline.advance(1, date(2070, 1, 30), date(2070, 1, 31));
+        assert_eq!(
+            due,
+            vec![(TurnPhase::Orbits, 1), (TurnPhase::Movement, 1), (TurnPhase::Economy, 1), (TurnPhase::Sensors, 1)]
+        );
+
+        // Population catches up on every day since it last ran once the month turns
+        let due = pipeline.advance(1, date(2070, 1, 31), date(2070, 2, 1));
+        assert_eq!(
+            due,
+            vec![
+                (TurnPhase::Orbits, 1),
+                (TurnPhase::Movement, 1),
+                (TurnPhase::Economy, 1),
+                (TurnPhase::Population, 2),
+                (TurnPhase::Sensors, 1)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_set_cadence() {
+        let mut pipeline = TurnPipeline::default();
+        pipeline.set_cadence(TurnPhase::Movement, PhaseCadence::Monthly);
+        pipeline.set_cadence(TurnPhase::Economy, PhaseCadence::Monthly);
+        pipeline.set_cadence(TurnPhase::Sensors, PhaseCadence::Monthly);
+
+        let due = pipeline.advance(5, date(2070, 1, 1), date(2070, 1, 6));
+        assert_eq!(due, vec![(TurnPhase::Orbits, 5)]);
+    }
+}
@@ -0,0 +1,782 @@
+// src/structures/fleet.rs
+// Fleets: groups of one faction's spacecraft that take orders together.  A
+// fleet sails at the speed of its slowest ship, slowed further by how tightly
+// it keeps formation, and every ship in it flies the same order: move
+// somewhere, patrol a circuit, escort another ship or blockade a location;
+// a blockade on station turns away every other faction's ships arriving there.
+// Orders are turned into courses for each ship in the movement phase, before
+// ships move, and fleets find out where they have got to once they have.
+
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::maps::location::Location;
+use crate::simulation::simulation::Simulation;
+use crate::structures::movement::{self, MovementError, MovementReport};
+use crate::structures::spacecraft::{Spacecraft, SpacecraftModuleType};
+use crate::structures::structure_type::StructureType;
+use crate::units::unit_type::UnitType;
+use crate::universe::solar_system_manager::SolarSystemManager;
+
+/// How a fleet arranges itself around its flagship
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Formation {
+    /// Ships follow one another, for getting somewhere quickly
+    #[default]
+    Column,
+    /// Ships abreast, bringing every gun to bear
+    Line,
+    /// Escorts spread out around the flagship to catch anything closing on it
+    Screen,
+}
+
+impl Formation {
+    /// Share of its slowest ship's speed a fleet keeps in this formation
+    pub fn speed_factor(&self) -> f64 {
+        match self {
+            Formation::Column => 1.0,
+            Formation::Line => 0.9,
+            Formation::Screen => 0.8,
+        }
+    }
+}
+
+/// What a fleet has been told to do
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub enum FleetOrder {
+    /// Stay where it is
+    #[default]
+    Hold,
+    /// Go to a location, then hold there
+    Move { destination: Location },
+    /// Visit each waypoint in turn, round and round
+    Patrol { waypoints: Vec<Location>, next: usize },
+    /// Dock with another ship and go wherever it goes
+    Escort { spacecraft_id: Uuid },
+    /// Go to a location and stay there until ordered otherwise
+    Blockade { location: Location },
+}
+
+/// How far a fleet has got with its order
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum OrderStatus {
+    /// Courses have yet to be plotted
+    #[default]
+    Pending,
+    /// Ships are on their way
+    Underway,
+    /// The fleet is where its order wants it
+    OnStation,
+}
+
+/// Reasons a fleet could not be formed or ordered
+#[derive(Debug, Clone, PartialEq)]
+pub enum FleetError {
+    UnknownFleet(Uuid),
+    UnknownSpacecraft(Uuid),
+    /// Fleets need at least one ship
+    NoShips,
+    AlreadyInFleet { spacecraft_id: Uuid, fleet_id: Uuid },
+    NotInFleet { spacecraft_id: Uuid, fleet_id: Uuid },
+    /// Every ship in a fleet must belong to the same faction
+    DifferentOwners,
+    SameFleet,
+    /// Splitting off every ship would leave an empty fleet
+    WouldEmptyFleet,
+    /// A patrol needs somewhere to go
+    NoWaypoints,
+    /// A fleet cannot escort one of its own ships
+    EscortingItself(Uuid),
+    /// Fleets only escort ships of their own faction
+    EscortingForeign(Uuid),
+}
+
+impl Display for FleetError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FleetError::UnknownFleet(id) => write!(f, "no fleet with id {}", id),
+            FleetError::UnknownSpacecraft(id) => write!(f, "no spacecraft with id {}", id),
+            FleetError::NoShips => write!(f, "a fleet needs at least one ship"),
+            FleetError::AlreadyInFleet { spacecraft_id, fleet_id } => {
+                write!(f, "spacecraft {} already sails with fleet {}", spacecraft_id, fleet_id)
+            }
+            FleetError::NotInFleet { spacecraft_id, fleet_id } => {
+                write!(f, "spacecraft {} is not in fleet {}", spacecraft_id, fleet_id)
+            }
+            FleetError::DifferentOwners => write!(f, "ships of different factions cannot share a fleet"),
+            FleetError::SameFleet => write!(f, "a fleet cannot merge with itself"),
+            FleetError::WouldEmptyFleet => write!(f, "at least one ship must stay with the fleet"),
+            FleetError::NoWaypoints => write!(f, "a patrol needs at least one waypoint"),
+            FleetError::EscortingItself(id) => write!(f, "spacecraft {} is part of the fleet escorting it", id),
+            FleetError::EscortingForeign(id) => write!(f, "spacecraft {} belongs to another faction", id),
+        }
+    }
+}
+
+impl std::error::Error for FleetError {}
+
+/// A group of spacecraft under one order
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Fleet {
+    id: Uuid,
+    name: String,
+    owner: Option<Uuid>,
+    /// Member ships, in the order they joined
+    members: Vec<Uuid>,
+    flagship: Uuid,
+    #[serde(default)]
+    formation: Formation,
+    #[serde(default)]
+    order: FleetOrder,
+    #[serde(default)]
+    status: OrderStatus,
+}
+
+impl Fleet {
+    pub fn id(&self) -> Uuid {
+        self.id
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn owner(&self) -> Option<Uuid> {
+        self.owner
+    }
+
+    pub fn members(&self) -> &[Uuid] {
+        &self.members
+    }
+
+    pub fn flagship(&self) -> Uuid {
+        self.flagship
+    }
+
+    pub fn formation(&self) -> Formation {
+        self.formation
+    }
+
+    pub fn order(&self) -> &FleetOrder {
+        &self.order
+    }
+
+    pub fn status(&self) -> OrderStatus {
+        self.status
+    }
+
+    /// Where the current order sends the fleet next, if anywhere
+    fn target(&self) -> Option<Location> {
+        match &self.order {
+            FleetOrder::Hold => None,
+            FleetOrder::Move { destination } => Some(destination.clone()),
+            FleetOrder::Patrol { waypoints, next } => waypoints.get(*next).cloned(),
+            FleetOrder::Escort { spacecraft_id } => Some(Location::Docked { structure_id: *spacecraft_id }),
+            FleetOrder::Blockade { location } => Some(location.clone()),
+        }
+    }
+}
+
+/// Totals over every ship in a fleet
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct FleetStats {
+    pub ships: u32,
+    /// Hulls plus modules
+    pub hull_size: u32,
+    /// Military modules, as counted towards a faction's fleet strength
+    pub strength: u32,
+    pub modules: HashMap<SpacecraftModuleType, u32>,
+    pub garrison: HashMap<UnitType, u32>,
+    pub propellant: u64,
+    /// Speed the fleet sails at, relative to a new bare hull
+    pub speed: f64,
+    /// Wear averaged over the ships
+    pub wear: f32,
+}
+
+/// What happened to fleets during a movement phase
+#[derive(Debug, Clone, Default)]
+pub struct FleetReport {
+    /// Fleets that got where their order sent them, and where their flagship ended up
+    pub arrived: Vec<(Uuid, Location)>,
+    /// Fleets whose order could not be carried out; they now hold where they are
+    pub blocked: Vec<(Uuid, MovementError)>,
+}
+
+fn spacecraft(simulation: &Simulation, spacecraft_id: Uuid) -> Result<&Spacecraft, FleetError> {
+    match simulation.structures.get(&spacecraft_id) {
+        Some(StructureType::Spacecraft(spacecraft)) => Ok(spacecraft),
+        _ => Err(FleetError::UnknownSpacecraft(spacecraft_id)),
+    }
+}
+
+fn set_fleet(simulation: &mut Simulation, ships: &[Uuid], fleet_id: Option<Uuid>) {
+    for ship in ships {
+        if let Some(StructureType::Spacecraft(spacecraft)) = simulation.structures.get_mut(ship) {
+            spacecraft.set_fleet(fleet_id);
+        }
+    }
+}
+
+fn fleet(simulation: &Simulation, fleet_id: Uuid) -> Result<&Fleet, FleetError> {
+    simulation.fleets.get(&fleet_id).ok_or(FleetError::UnknownFleet(fleet_id))
+}
+
+fn fleet_mut(simulation: &mut Simulation, fleet_id: Uuid) -> Result<&mut Fleet, FleetError> {
+    simulation.fleets.get_mut(&fleet_id).ok_or(FleetError::UnknownFleet(fleet_id))
+}
+
+/// The faction every one of `ships` belongs to, checking none is already in a fleet
+fn common_owner(simulation: &Simulation, ships: &[Uuid], joining: Option<Uuid>) -> Result<Option<Uuid>, FleetError> {
+    let mut owners = Vec::new();
+    for &ship in ships {
+        let spacecraft = spacecraft(simulation, ship)?;
+        match spacecraft.fleet_id() {
+            Some(fleet_id) if Some(fleet_id) != joining => {
+                return Err(FleetError::AlreadyInFleet { spacecraft_id: ship, fleet_id })
+            }
+            _ => owners.push(spacecraft.owner()),
+        }
+    }
+    let Some(&owner) = owners.first() else {
+        return Err(FleetError::NoShips);
+    };
+    if owners.iter().any(|&other| other != owner) {
+        return Err(FleetError::DifferentOwners);
+    }
+    Ok(owner)
+}
+
+/// Forms a fleet from `ships`, none of which may already be in one; the first is the flagship
+pub fn create(simulation: &mut Simulation, name: String, ships: Vec<Uuid>) -> Result<Uuid, FleetError> {
+    let mut members = Vec::new();
+    for ship in ships {
+        if !members.contains(&ship) {
+            members.push(ship);
+        }
+    }
+    let owner = common_owner(simulation, &members, None)?;
+    let fleet = Fleet {
+        id: Uuid::new_v4(),
+        name,
+        owner,
+        flagship: members[0],
+        members,
+        formation: Formation::default(),
+        order: FleetOrder::default(),
+        status: OrderStatus::OnStation,
+    };
+    let id = fleet.id;
+    set_fleet(simulation, &fleet.members, Some(id));
+    simulation.fleets.insert(id, fleet);
+    Ok(id)
+}
+
+/// Breaks a fleet up, leaving its ships where they are; returns the ships
+pub fn disband(simulation: &mut Simulation, fleet_id: Uuid) -> Result<Vec<Uuid>, FleetError> {
+    let fleet = simulation.fleets.remove(&fleet_id).ok_or(FleetError::UnknownFleet(fleet_id))?;
+    set_fleet(simulation, &fleet.members, None);
+    Ok(fleet.members)
+}
+
+/// Moves every ship of `from` into `into`, which keeps its flagship and order; `from` is disbanded
+pub fn merge(simulation: &mut Simulation, into: Uuid, from: Uuid) -> Result<(), FleetError> {
+    if into == from {
+        return Err(FleetError::SameFleet);
+    }
+    if fleet(simulation, into)?.owner != fleet(simulation, from)?.owner {
+        return Err(FleetError::DifferentOwners);
+    }
+    let ships = disband(simulation, from)?;
+    set_fleet(simulation, &ships, Some(into));
+    let fleet = fleet_mut(simulation, into)?;
+    fleet.members.extend(ships);
+    // The newcomers need courses of their own
+    if fleet.order != FleetOrder::Hold {
+        fleet.status = OrderStatus::Pending;
+    }
+    Ok(())
+}
+
+/// Detaches `ships` into a new fleet with the same order; returns the new fleet.  A flagship
+/// that leaves takes command of the new fleet and the next ship takes over the old one.
+pub fn split(simulation: &mut Simulation, fleet_id: Uuid, ships: Vec<Uuid>, name: String) -> Result<Uuid, FleetError> {
+    let original = fleet(simulation, fleet_id)?;
+    if let Some(&stranger) = ships.iter().find(|ship| !original.members.contains(ship)) {
+        return Err(FleetError::NotInFleet { spacecraft_id: stranger, fleet_id });
+    }
+    if ships.is_empty() {
+        return Err(FleetError::NoShips);
+    }
+    if original.members.iter().all(|member| ships.contains(member)) {
+        return Err(FleetError::WouldEmptyFleet);
+    }
+
+    let original = fleet_mut(simulation, fleet_id)?;
+    original.members.retain(|member| !ships.contains(member));
+    let flagship = if ships.contains(&original.flagship) {
+        let flagship = original.flagship;
+        original.flagship = original.members[0];
+        flagship
+    } else {
+        ships[0]
+    };
+    let detached = Fleet {
+        id: Uuid::new_v4(),
+        name,
+        owner: original.owner,
+        members: ships,
+        flagship,
+        formation: original.formation,
+        order: original.order.clone(),
+        status: if original.order == FleetOrder::Hold { OrderStatus::OnStation } else { OrderStatus::Pending },
+    };
+    let id = detached.id;
+    set_fleet(simulation, &detached.members, Some(id));
+    simulation.fleets.insert(id, detached);
+    Ok(id)
+}
+
+/// Puts one of a fleet's ships in command
+pub fn set_flagship(simulation: &mut Simulation, fleet_id: Uuid, spacecraft_id: Uuid) -> Result<(), FleetError> {
+    let fleet = fleet_mut(simulation, fleet_id)?;
+    if !fleet.members.contains(&spacecraft_id) {
+        return Err(FleetError::NotInFleet { spacecraft_id, fleet_id });
+    }
+    fleet.flagship = spacecraft_id;
+    Ok(())
+}
+
+/// Changes formation; ships already under way keep the pace they set out at
+pub fn set_formation(simulation: &mut Simulation, fleet_id: Uuid, formation: Formation) -> Result<(), FleetError> {
+    fleet_mut(simulation, fleet_id)?.formation = formation;
+    Ok(())
+}
+
+/// Gives a fleet a new order, carried out from the next movement phase.  Holding stops every
+/// ship where it is.
+pub fn set_order(simulation: &mut Simulation, fleet_id: Uuid, order: FleetOrder) -> Result<(), FleetError> {
+    let (members, owner) = fleet(simulation, fleet_id).map(|fleet| (fleet.members.clone(), fleet.owner))?;
+    match &order {
+        FleetOrder::Patrol { waypoints, .. } if waypoints.is_empty() => return Err(FleetError::NoWaypoints),
+        FleetOrder::Escort { spacecraft_id } => {
+            let charge = spacecraft(simulation, *spacecraft_id)?;
+            if members.contains(spacecraft_id) {
+                return Err(FleetError::EscortingItself(*spacecraft_id));
+            }
+            if charge.owner() != owner {
+                return Err(FleetError::EscortingForeign(*spacecraft_id));
+            }
+        }
+        _ => {}
+    }
+    for ship in &members {
+        if let Some(StructureType::Spacecraft(spacecraft)) = simulation.structures.get_mut(ship) {
+            spacecraft.set_destination(None);
+        }
+    }
+    let fleet = fleet_mut(simulation, fleet_id)?;
+    fleet.status = if order == FleetOrder::Hold { OrderStatus::OnStation } else { OrderStatus::Pending };
+    fleet.order = match order {
+        FleetOrder::Patrol { waypoints, next } => FleetOrder::Patrol { next: next % waypoints.len(), waypoints },
+        order => order,
+    };
+    Ok(())
+}
+
+/// Speed of the slowest ship, cut by the fleet's formation
+pub fn speed(simulation: &Simulation, fleet_id: Uuid) -> Result<f64, FleetError> {
+    let fleet = fleet(simulation, fleet_id)?;
+    let slowest = fleet
+        .members
+        .iter()
+        .filter_map(|&ship| spacecraft(simulation, ship).ok())
+        .map(Spacecraft::speed)
+        .fold(f64::INFINITY, f64::min);
+    Ok(if slowest.is_finite() { slowest * fleet.formation.speed_factor() } else { 0.0 })
+}
+
+/// Adds up what a fleet's ships have between them
+pub fn stats(simulation: &Simulation, fleet_id: Uuid) -> Result<FleetStats, FleetError> {
+    let fleet = fleet(simulation, fleet_id)?;
+    let mut stats = FleetStats { speed: speed(simulation, fleet_id)?, ..FleetStats::default() };
+    let mut wear = 0.0;
+    for ship in fleet.members.iter().filter_map(|&ship| spacecraft(simulation, ship).ok()) {
+        stats.ships += 1;
+        stats.hull_size += ship.hull_size();
+        stats.strength += ship.modules().get(&SpacecraftModuleType::Military).copied().unwrap_or(0);
+        for (module, &count) in ship.modules() {
+            *stats.modules.entry(module.clone()).or_default() += count;
+        }
+        for (unit, &count) in ship.garrison() {
+            *stats.garrison.entry(unit.clone()).or_default() += count;
+        }
+        stats.propellant += ship.propellant();
+        wear += ship.wear();
+    }
+    if stats.ships > 0 {
+        stats.wear = wear / stats.ships as f32;
+    }
+    Ok(stats)
+}
+
+/// Fleets blockading `location`: its body, orbit and anything docked there, or the deep space point itself
+pub fn blockading(simulation: &Simulation, location: &Location) -> Vec<Uuid> {
+    let body = movement::body_of(simulation, location);
+    let mut fleets: Vec<Uuid> = simulation
+        .fleets
+        .values()
+        .filter(|fleet| match &fleet.order {
+            FleetOrder::Blockade { location: held } => {
+                held == location || (body.is_some() && movement::body_of(simulation, held) == body)
+            }
+            _ => false,
+        })
+        .map(|fleet| fleet.id)
+        .collect();
+    fleets.sort();
+    fleets
+}
+
+/// The first fleet on station blockading `location` against ships of `faction`, i.e. one that
+/// belongs to anybody else
+pub fn blockaded_against(simulation: &Simulation, location: &Location, faction: Option<Uuid>) -> Option<Uuid> {
+    blockading(simulation, location).into_iter().find(|id| {
+        let fleet = &simulation.fleets[id];
+        fleet.status == OrderStatus::OnStation && fleet.owner != faction
+    })
+}
+
+/// Drops ships that no longer exist from their fleets, disbanding any left empty
+fn prune(simulation: &mut Simulation) {
+    let Simulation { fleets, structures, .. } = simulation;
+    for fleet in fleets.values_mut() {
+        fleet.members.retain(|ship| matches!(structures.get(ship), Some(StructureType::Spacecraft(_))));
+        if !fleet.members.contains(&fleet.flagship) {
+            if let Some(&next) = fleet.members.first() {
+                fleet.flagship = next;
+            }
+        }
+    }
+    fleets.retain(|_, fleet| !fleet.members.is_empty());
+}
+
+/// Plots courses for every fleet with an order to start on, each ship flying at the fleet's
+/// speed.  A fleet that cannot get every ship there goes nowhere and holds instead.
+pub fn dispatch(simulation: &mut Simulation, solar_system: &SolarSystemManager) -> FleetReport {
+    prune(simulation);
+    let mut ids: Vec<Uuid> = simulation.fleets.keys().copied().collect();
+    ids.sort();
+
+    let mut report = FleetReport::default();
+    for id in ids {
+        let fleet = simulation.fleets[&id].clone();
+        if fleet.status != OrderStatus::Pending {
+            continue;
+        }
+        if let FleetOrder::Escort { spacecraft_id } = fleet.order {
+            if spacecraft(simulation, spacecraft_id).is_err() {
+                report.blocked.push((id, MovementError::UnknownSpacecraft(spacecraft_id)));
+                hold(simulation, id);
+                continue;
+            }
+        }
+        let Some(target) = fleet.target() else {
+            continue;
+        };
+        let speed = speed(simulation, id).unwrap_or(0.0);
+        let mut courses = Vec::new();
+        let mut blocked = None;
+        for &ship in &fleet.members {
+            if simulation.structures.get(&ship).is_some_and(|ship| ship.location() == &target) {
+                continue;
+            }
+            match movement::plot_at(simulation, solar_system, ship, &target, speed) {
+                Ok(trajectory) => courses.push((ship, trajectory)),
+                Err(error) => {
+                    blocked = Some(error);
+                    break;
+                }
+            }
+        }
+        if let Some(error) = blocked {
+            report.blocked.push((id, error));
+            hold(simulation, id);
+            continue;
+        }
+
+        let underway = !courses.is_empty();
+        for (ship, trajectory) in courses {
+            if let Some(StructureType::Spacecraft(spacecraft)) = simulation.structures.get_mut(&ship) {
+                spacecraft.set_destination(Some(target.clone()));
+                spacecraft.set_trajectory(Some(trajectory));
+            }
+        }
+        if underway {
+            if let Some(fleet) = simulation.fleets.get_mut(&id) {
+                fleet.status = OrderStatus::Underway;
+            }
+        } else {
+            arrive(simulation, id, &mut report);
+        }
+    }
+    report
+}
+
+/// Catches fleets up with where their ships got to in the movement phase: a fleet is there
+/// once none of its ships is still on the way, and one whose ship was turned back holds.
+pub fn settle(simulation: &mut Simulation, movement: &MovementReport) -> FleetReport {
+    let mut ids: Vec<Uuid> = simulation.fleets.keys().copied().collect();
+    ids.sort();
+
+    let mut report = FleetReport::default();
+    for id in ids {
+        let fleet = simulation.fleets[&id].clone();
+        if fleet.status != OrderStatus::Underway {
+            continue;
+        }
+        if let Some((_, error)) = movement.blocked.iter().find(|(ship, _)| fleet.members.contains(ship)) {
+            report.blocked.push((id, error.clone()));
+            hold(simulation, id);
+            continue;
+        }
+        let travelling = fleet.members.iter().any(|ship| match simulation.structures.get(ship) {
+            Some(StructureType::Spacecraft(spacecraft)) => spacecraft.destination().is_some(),
+            _ => false,
+        });
+        if !travelling {
+            arrive(simulation, id, &mut report);
+        }
+    }
+    report
+}
+
+/// Stops every ship of a fleet and sets it to hold
+fn hold(simulation: &mut Simulation, fleet_id: Uuid) {
+    let Some(fleet) = simulation.fleets.get_mut(&fleet_id) else {
+        return;
+    };
+    fleet.order = FleetOrder::Hold;
+    fleet.status = OrderStatus::OnStation;
+    let members = fleet.members.clone();
+    for ship in members {
+        if let Some(StructureType::Spacecraft(spacecraft)) = simulation.structures.get_mut(&ship) {
+            spacecraft.set_destination(None);
+        }
+    }
+}
+
+/// A fleet has reached where its order sent it: a move ends, a patrol heads for its next waypoint
+fn arrive(simulation: &mut Simulation, fleet_id: Uuid, report: &mut FleetReport) {
+    let Some(fleet) = simulation.fleets.get_mut(&fleet_id) else {
+        return;
+    };
+    match &mut fleet.order {
+        FleetOrder::Move { .. } => {
+            fleet.order = FleetOrder::Hold;
+            fleet.status = OrderStatus::OnStation;
+        }
+        FleetOrder::Patrol { waypoints, next } => {
+            *next = (*next + 1) % waypoints.len();
+            // A single waypoint patrol is a blockade in all but name
+            fleet.status = if waypoints.len() > 1 { OrderStatus::Pending } else { OrderStatus::OnStation };
+        }
+        FleetOrder::Hold | FleetOrder::Escort { .. } | FleetOrder::Blockade { .. } => fleet.status = OrderStatus::OnStation,
+    }
+    if let Some(location) = simulation.structures.get(&fleet.flagship).map(|flagship| flagship.location().clone()) {
+        report.arrived.push((fleet_id, location));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::structures::movement::PROPELLANT;
+    use crate::structures::test_support::world;
+
+    fn ship(simulation: &mut Simulation, owner: Uuid, modules: u32) -> Uuid {
+        let ship = Spacecraft::new("Cutter".to_string(), Location::DeepSpace { x: 1.5e8, y: 0.0 })
+            .with_owner(owner)
+            .with_modules(HashMap::from([(SpacecraftModuleType::Military, modules)]))
+            .with_cargo(HashMap::from([(PROPELLANT, 1_000_000)]));
+        simulation.add_structure(StructureType::Spacecraft(ship))
+    }
+
+    fn location(simulation: &Simulation, ship: Uuid) -> &Location {
+        simulation.structures[&ship].location()
+    }
+
+    #[test]
+    fn test_fleets_form_merge_and_split() {
+        let (mut simulation, _, _) = world();
+        let (ours, theirs) = (Uuid::new_v4(), Uuid::new_v4());
+        let [a, b, c, d] = [0, 1, 2, 3].map(|modules| ship(&mut simulation, ours, modules));
+        let stranger = ship(&mut simulation, theirs, 1);
+
+        assert_eq!(create(&mut simulation, "Empty".to_string(), vec![]), Err(FleetError::NoShips));
+        assert_eq!(create(&mut simulation, "Mixed".to_string(), vec![a, stranger]), Err(FleetError::DifferentOwners));
+        let first = create(&mut simulation, "First".to_string(), vec![a, b]).unwrap();
+        assert_eq!(
+            create(&mut simulation, "Again".to_string(), vec![b]),
+            Err(FleetError::AlreadyInFleet { spacecraft_id: b, fleet_id: first })
+        );
+        let second = create(&mut simulation, "Second".to_string(), vec![c, d]).unwrap();
+        assert_eq!(simulation.fleets[&first].flagship(), a);
+
+        merge(&mut simulation, first, second).unwrap();
+        assert!(!simulation.fleets.contains_key(&second));
+        assert_eq!(simulation.fleets[&first].members(), &[a, b, c, d]);
+        assert_eq!(spacecraft(&simulation, d).unwrap().fleet_id(), Some(first));
+        assert_eq!(merge(&mut simulation, first, first), Err(FleetError::SameFleet));
+
+        // The flagship goes with the detachment and the next ship takes over
+        let detached = split(&mut simulation, first, vec![a, c], "Detached".to_string()).unwrap();
+        assert_eq!(simulation.fleets[&detached].flagship(), a);
+        assert_eq!(simulation.fleets[&first].flagship(), b);
+        assert_eq!(split(&mut simulation, first, vec![b, d], "All".to_string()), Err(FleetError::WouldEmptyFleet));
+        assert_eq!(
+            set_flagship(&mut simulation, first, a),
+            Err(FleetError::NotInFleet { spacecraft_id: a, fleet_id: first })
+        );
+        set_flagship(&mut simulation, first, d).unwrap();
+        assert_eq!(simulation.fleets[&first].flagship(), d);
+
+        assert_eq!(disband(&mut simulation, detached), Ok(vec![a, c]));
+        assert_eq!(spacecraft(&simulation, a).unwrap().fleet_id(), None);
+    }
+
+    #[test]
+    fn test_fleets_sail_at_the_pace_of_the_slowest_ship() {
+        let (mut simulation, _, _) = world();
+        let owner = Uuid::new_v4();
+        let fast = ship(&mut simulation, owner, 0);
+        let slow = ship(&mut simulation, owner, 4);
+        let fleet = create(&mut simulation, "Convoy".to_string(), vec![fast, slow]).unwrap();
+
+        let slowest = spacecraft(&simulation, slow).unwrap().speed();
+        assert!(slowest < spacecraft(&simulation, fast).unwrap().speed());
+        assert_eq!(speed(&simulation, fleet), Ok(slowest));
+        set_formation(&mut simulation, fleet, Formation::Screen).unwrap();
+        assert_eq!(speed(&simulation, fleet), Ok(slowest * 0.8));
+
+        let stats = stats(&simulation, fleet).unwrap();
+        assert_eq!((stats.ships, stats.hull_size, stats.strength), (2, 6, 4));
+        assert_eq!(stats.propellant, 2_000_000);
+    }
+
+    #[test]
+    fn test_ships_keep_together_and_arrive_as_one() {
+        let (mut simulation, solar_system, _) = world();
+        let owner = Uuid::new_v4();
+        let fast = ship(&mut simulation, owner, 0);
+        let slow = ship(&mut simulation, owner, 4);
+        let fleet = create(&mut simulation, "Convoy".to_string(), vec![fast, slow]).unwrap();
+        let target = Location::DeepSpace { x: 0.0, y: 2.3e8 };
+        set_order(&mut simulation, fleet, FleetOrder::Move { destination: target.clone() }).unwrap();
+
+        let mut arrived = Vec::new();
+        for _ in 0..2000 {
+            let mut report = dispatch(&mut simulation, &solar_system);
+            let moved = movement::advance(&mut simulation, &solar_system, 1);
+            report.arrived.extend(settle(&mut simulation, &moved).arrived);
+            // Neither ship pulls ahead of the other
+            assert_eq!(location(&simulation, fast), location(&simulation, slow));
+            if !report.arrived.is_empty() {
+                arrived = report.arrived;
+                break;
+            }
+        }
+        assert_eq!(arrived, vec![(fleet, target.clone())]);
+        assert_eq!(simulation.fleets[&fleet].order(), &FleetOrder::Hold);
+    }
+
+    #[test]
+    fn test_patrols_go_round_and_escorts_dock() {
+        let (mut simulation, solar_system, body_id) = world();
+        let owner = Uuid::new_v4();
+        let picket = ship(&mut simulation, owner, 1);
+        let fleet = create(&mut simulation, "Picket".to_string(), vec![picket]).unwrap();
+        assert_eq!(
+            set_order(&mut simulation, fleet, FleetOrder::Patrol { waypoints: vec![], next: 0 }),
+            Err(FleetError::NoWaypoints)
+        );
+
+        let orbit = Location::Orbit { body_id, orbital_slot_id: Uuid::new_v4() };
+        let beacon = Location::DeepSpace { x: 1.0e3, y: 0.0 };
+        let waypoints = vec![orbit.clone(), beacon.clone()];
+        set_order(&mut simulation, fleet, FleetOrder::Patrol { waypoints, next: 0 }).unwrap();
+        let mut visited = Vec::new();
+        for _ in 0..200 {
+            dispatch(&mut simulation, &solar_system);
+            let moved = movement::advance(&mut simulation, &solar_system, 1);
+            visited.extend(settle(&mut simulation, &moved).arrived.into_iter().map(|(_, location)| location));
+            if visited.len() == 3 {
+                break;
+            }
+        }
+        assert_eq!(visited, vec![orbit.clone(), beacon, orbit.clone()]);
+
+        let liner = ship(&mut simulation, owner, 0);
+        assert_eq!(
+            set_order(&mut simulation, fleet, FleetOrder::Escort { spacecraft_id: picket }),
+            Err(FleetError::EscortingItself(picket))
+        );
+        let stranger = ship(&mut simulation, Uuid::new_v4(), 0);
+        assert_eq!(
+            set_order(&mut simulation, fleet, FleetOrder::Escort { spacecraft_id: stranger }),
+            Err(FleetError::EscortingForeign(stranger))
+        );
+        set_order(&mut simulation, fleet, FleetOrder::Escort { spacecraft_id: liner }).unwrap();
+        for _ in 0..200 {
+            dispatch(&mut simulation, &solar_system);
+            let moved = movement::advance(&mut simulation, &solar_system, 1);
+            settle(&mut simulation, &moved);
+        }
+        assert_eq!(location(&simulation, picket), &Location::Docked { structure_id: liner });
+        assert_eq!(simulation.fleets[&fleet].status(), OrderStatus::OnStation);
+
+        // With its charge gone before it sets out the escort stands down
+        let ferry = ship(&mut simulation, owner, 0);
+        set_order(&mut simulation, fleet, FleetOrder::Escort { spacecraft_id: ferry }).unwrap();
+        simulation.structures.remove(&ferry);
+        let report = dispatch(&mut simulation, &solar_system);
+        assert_eq!(report.blocked, vec![(fleet, MovementError::UnknownSpacecraft(ferry))]);
+        assert_eq!(simulation.fleets[&fleet].order(), &FleetOrder::Hold);
+
+        set_order(&mut simulation, fleet, FleetOrder::Blockade { location: orbit.clone() }).unwrap();
+        assert_eq!(blockading(&simulation, &orbit), vec![fleet]);
+    }
+
+    #[test]
+    fn test_blockades_turn_away_other_factions() {
+        let (mut simulation, solar_system, body_id) = world();
+        let (ours, theirs) = (Uuid::new_v4(), Uuid::new_v4());
+        let guard = ship(&mut simulation, ours, 2);
+        let fleet = create(&mut simulation, "Cordon".to_string(), vec![guard]).unwrap();
+        let orbit = Location::Orbit { body_id, orbital_slot_id: Uuid::new_v4() };
+        set_order(&mut simulation, fleet, FleetOrder::Blockade { location: orbit.clone() }).unwrap();
+        for _ in 0..200 {
+            dispatch(&mut simulation, &solar_system);
+            let moved = movement::advance(&mut simulation, &solar_system, 1);
+            settle(&mut simulation, &moved);
+        }
+        assert_eq!(simulation.fleets[&fleet].status(), OrderStatus::OnStation);
+
+        // The blockade covers the whole body, not just the slot it was ordered to
+        let elsewhere = Location::Orbit { body_id, orbital_slot_id: Uuid::new_v4() };
+        let runner = ship(&mut simulation, theirs, 0);
+        let supply = ship(&mut simulation, ours, 0);
+        movement::set_course(&mut simulation, &solar_system, runner, elsewhere.clone()).unwrap();
+        movement::set_course(&mut simulation, &solar_system, supply, elsewhere.clone()).unwrap();
+        let mut blocked = Vec::new();
+        for _ in 0..200 {
+            blocked.extend(movement::advance(&mut simulation, &solar_system, 1).blocked);
+        }
+        assert_eq!(blocked, vec![(runner, MovementError::Blockaded { fleet_id: fleet })]);
+        assert!(matches!(location(&simulation, runner), Location::DeepSpace { .. }));
+        assert_eq!(location(&simulation, supply), &elsewhere);
+    }
+}
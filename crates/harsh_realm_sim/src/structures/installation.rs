@@ -1,9 +1,64 @@
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
+use crate::buildings::building_type::BuildingType;
+use crate::maps::location::Location;
+use crate::population::person_type::PersonType;
+use crate::production::production_queue::{BuildItem, ProductionQueue, Stockpile, Workshop};
+use crate::resources::resource_type::ResourceType;
+use crate::structures::settlement::Settlement;
 use crate::units::unit_type::UnitType;
 
-#[derive(Clone, Debug,Deserialize,Serialize)]
+/// Workers an installation has with a crew aboard
+pub const CREWED_LABOR: u32 = 100;
+
+/// Work an uncrewed installation's automation does, in workers
+pub const AUTOMATED_LABOR: u32 = 25;
+
+/// Share of a crewed installation's output its automation manages alone
+pub const AUTOMATED_OUTPUT: f64 = 0.5;
+
+/// Highest tier an installation can be upgraded to
+pub const MAX_TIER: u8 = 3;
+
+/// People in a crew unit, who become workers when an installation is settled
+pub const CREW_SIZE: u32 = 20;
+
+/// Reasons an installation operation was refused
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InstallationError {
+    UnknownInstallation(Uuid),
+    /// The installation is already at `MAX_TIER`
+    FullyUpgraded,
+    InsufficientResources { resource: ResourceType, requested: u64, available: u64 },
+    /// No unit of this type is stationed at the installation
+    NoSuchUnit(UnitType),
+    /// The unit is not trained for the installation's work
+    UnsuitableCrew { purpose: InstallationPurpose, unit: UnitType },
+    /// Settling an installation needs a crew to become its first inhabitants
+    Uncrewed,
+}
+
+impl Display for InstallationError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            InstallationError::UnknownInstallation(id) => write!(f, "no installation with id {}", id),
+            InstallationError::FullyUpgraded => write!(f, "installation is already at tier {}", MAX_TIER),
+            InstallationError::InsufficientResources { resource, requested, available } => {
+                write!(f, "needs {} {:?} but only {} is stockpiled", requested, resource, available)
+            }
+            InstallationError::NoSuchUnit(unit) => write!(f, "no {:?} stationed here", unit),
+            InstallationError::UnsuitableCrew { purpose, unit } => write!(f, "{:?} cannot crew a {:?} installation", unit, purpose),
+            InstallationError::Uncrewed => write!(f, "installation has no crew to settle it"),
+        }
+    }
+}
+
+impl std::error::Error for InstallationError {}
+
+#[derive(Clone, Debug,Deserialize,Serialize,PartialEq,Eq,Hash)]
 pub enum InstallationPurpose {
     Mine,
     Refinery,
@@ -12,10 +67,293 @@ pub enum InstallationPurpose {
     Research,
 }
 
+impl InstallationPurpose {
+    /// Returns true if the installation does nothing without a crew
+    pub fn needs_crew(&self) -> bool {
+        matches!(self, InstallationPurpose::Military | InstallationPurpose::Research)
+    }
+
+    /// Returns true if `unit` can crew an installation with this purpose
+    pub fn accepts_crew(&self, unit: &UnitType) -> bool {
+        match self {
+            InstallationPurpose::Military => matches!(unit, UnitType::Infantry | UnitType::Marines),
+            _ => matches!(unit, UnitType::Crew | UnitType::Engineers),
+        }
+    }
+
+    /// The settlement building that does the same work, if any
+    pub fn building(&self) -> Option<BuildingType> {
+        match self {
+            InstallationPurpose::Mine => Some(BuildingType::Mine),
+            InstallationPurpose::Refinery => Some(BuildingType::Refinery),
+            InstallationPurpose::Factory => Some(BuildingType::Factory),
+            InstallationPurpose::Research => Some(BuildingType::Laboratory),
+            InstallationPurpose::Military => None,
+        }
+    }
+}
+
+fn first_tier() -> u8 {
+    1
+}
+
+fn full_power() -> f64 {
+    1.0
+}
+
 #[derive(Debug,Clone,Serialize,Deserialize)]
 pub struct Installation {
     id: Uuid,
     name: String,
+    location: Location,
+    #[serde(default)]
+    owner: Option<Uuid>,
     purpose: InstallationPurpose,
     crew: Option<UnitType>,
+    /// Upgrade level from 1 to `MAX_TIER`, multiplying output
+    #[serde(default = "first_tier")]
+    tier: u8,
+    /// Research done so far, for research stations
+    #[serde(default)]
+    research: f64,
+    /// Units and ships being made, in order
+    #[serde(default)]
+    production: ProductionQueue,
+    /// Units stationed here
+    #[serde(default)]
+    garrison: HashMap<UnitType, u32>,
+    #[serde(default)]
+    resources: HashMap<ResourceType, u64>,
+    /// Wear on equipment from 0 (new) to 1 (worn out)
+    #[serde(default)]
+    wear: f32,
+    /// Share of the power it needed that it got this turn
+    #[serde(default = "full_power")]
+    power: f64,
+}
+
+impl Installation {
+    pub fn new(name: String, purpose: InstallationPurpose, location: Location) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            name,
+            location,
+            owner: None,
+            purpose,
+            crew: None,
+            tier: 1,
+            research: 0.0,
+            production: ProductionQueue::new(),
+            garrison: HashMap::new(),
+            resources: HashMap::new(),
+            wear: 0.0,
+            power: 1.0,
+        }
+    }
+
+    pub fn with_owner(mut self, faction_id: Uuid) -> Self {
+        self.owner = Some(faction_id);
+        self
+    }
+
+    pub fn with_crew(mut self, crew: UnitType) -> Self {
+        self.crew = Some(crew);
+        self
+    }
+
+    pub fn with_resources(mut self, resources: HashMap<ResourceType, u64>) -> Self {
+        self.resources = resources;
+        self
+    }
+
+    pub fn id(&self) -> Uuid {
+        self.id
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn location(&self) -> &Location {
+        &self.location
+    }
+
+    pub fn owner(&self) -> Option<Uuid> {
+        self.owner
+    }
+
+    pub fn purpose(&self) -> &InstallationPurpose {
+        &self.purpose
+    }
+
+    pub fn crew(&self) -> Option<&UnitType> {
+        self.crew.as_ref()
+    }
+
+    pub fn tier(&self) -> u8 {
+        self.tier
+    }
+
+    pub fn research(&self) -> f64 {
+        self.research
+    }
+
+    pub fn add_research(&mut self, amount: f64) {
+        self.research += amount;
+    }
+
+    /// Output relative to a crewed tier 1 installation: nothing if it needs a crew and has none
+    pub fn output_factor(&self) -> f64 {
+        let staffing = match (&self.crew, self.purpose.needs_crew()) {
+            (Some(_), _) => 1.0,
+            (None, true) => 0.0,
+            (None, false) => AUTOMATED_OUTPUT,
+        };
+        self.tier as f64 * staffing
+    }
+
+    pub fn power(&self) -> f64 {
+        self.power
+    }
+
+    pub fn set_power(&mut self, power: f64) {
+        self.power = power.clamp(0.0, 1.0);
+    }
+
+    /// What it actually turns out this turn: `output_factor` held back by any power shortage
+    pub fn effective_output(&self) -> f64 {
+        self.output_factor() * self.power
+    }
+
+    /// Puts a unit from the garrison in charge, returning the crew it replaced to the garrison
+    pub fn assign_crew(&mut self, unit: UnitType) -> Result<Option<UnitType>, InstallationError> {
+        if !self.purpose.accepts_crew(&unit) {
+            return Err(InstallationError::UnsuitableCrew { purpose: self.purpose.clone(), unit });
+        }
+        match self.garrison.get_mut(&unit) {
+            Some(count) if *count > 0 => *count -= 1,
+            _ => return Err(InstallationError::NoSuchUnit(unit)),
+        }
+        if self.garrison.get(&unit) == Some(&0) {
+            self.garrison.remove(&unit);
+        }
+        let previous = self.crew.replace(unit);
+        if let Some(previous) = &previous {
+            self.add_units(previous.clone(), 1);
+        }
+        Ok(previous)
+    }
+
+    /// Sends the crew to the garrison, leaving the installation automated
+    pub fn release_crew(&mut self) -> Option<UnitType> {
+        let crew = self.crew.take()?;
+        self.add_units(crew.clone(), 1);
+        Some(crew)
+    }
+
+    /// Materials needed to reach the next tier, or `None` at the top tier
+    pub fn upgrade_cost(&self) -> Option<HashMap<ResourceType, u64>> {
+        if self.tier >= MAX_TIER {
+            return None;
+        }
+        let scale = self.tier as u64;
+        Some(HashMap::from([(ResourceType::Metal, 1000 * scale), (ResourceType::NonMetal, 500 * scale)]))
+    }
+
+    /// Pays for and applies the next upgrade from the stockpile, returning the new tier
+    pub fn upgrade(&mut self) -> Result<u8, InstallationError> {
+        let cost = self.upgrade_cost().ok_or(InstallationError::FullyUpgraded)?;
+        for (resource, &requested) in &cost {
+            let available = self.resources.available(resource);
+            if requested > available {
+                return Err(InstallationError::InsufficientResources { resource: resource.clone(), requested, available });
+            }
+        }
+        for (resource, amount) in &cost {
+            self.resources.take(resource, *amount);
+        }
+        self.tier += 1;
+        Ok(self.tier)
+    }
+
+    /// Turns a crewed installation into a settlement with the same id, stockpile, garrison and queue.
+    /// The crew become its workers and each tier of its work becomes a building.
+    pub fn into_settlement(self) -> Result<Settlement, InstallationError> {
+        if self.crew.is_none() {
+            return Err(InstallationError::Uncrewed);
+        }
+        let mut settlement = Settlement::new(self.name, self.location)
+            .with_id(self.id)
+            .with_population(HashMap::from([(PersonType::Worker, CREW_SIZE)]))
+            .with_resources(self.resources)
+            .with_production(self.production);
+        if let Some(owner) = self.owner {
+            settlement = settlement.with_owner(owner);
+        }
+        if let Some(building) = self.purpose.building() {
+            settlement = settlement.with_buildings(HashMap::from([(building, self.tier as u32)]));
+        }
+        for (unit, count) in self.garrison {
+            settlement.add_units(unit, count);
+        }
+        settlement.add_wear(self.wear);
+        Ok(settlement)
+    }
+
+    pub fn resources(&self) -> &HashMap<ResourceType, u64> {
+        &self.resources
+    }
+
+    /// Adds `amount` of `resource` to the installation's stockpile
+    pub fn add_resource(&mut self, resource: ResourceType, amount: u64) {
+        *self.resources.entry(resource).or_default() += amount;
+    }
+
+    pub fn garrison(&self) -> &HashMap<UnitType, u32> {
+        &self.garrison
+    }
+
+    /// Stations `count` more units of `unit_type` here
+    pub fn add_units(&mut self, unit_type: UnitType, count: u32) {
+        if count > 0 {
+            *self.garrison.entry(unit_type).or_default() += count;
+        }
+    }
+
+    pub fn wear(&self) -> f32 {
+        self.wear
+    }
+
+    /// Wears equipment down by `amount`, up to fully worn out
+    pub fn add_wear(&mut self, amount: f32) {
+        self.wear = (self.wear + amount).clamp(0.0, 1.0);
+    }
+}
+
+impl Workshop for Installation {
+    fn production(&self) -> &ProductionQueue {
+        &self.production
+    }
+
+    fn stockpile(&self) -> &dyn Stockpile {
+        &self.resources
+    }
+
+    fn workshop_mut(&mut self) -> (&mut ProductionQueue, &mut dyn Stockpile) {
+        (&mut self.production, &mut self.resources)
+    }
+
+    fn labor(&self) -> u32 {
+        let labor = if self.crew.is_some() { CREWED_LABOR } else { AUTOMATED_LABOR };
+        ((labor * self.tier as u32) as f64 * self.power).round() as u32
+    }
+
+    /// Factories build ships and military bases train units; nothing builds buildings
+    fn can_produce(&self, item: &BuildItem) -> bool {
+        match item {
+            BuildItem::Building(_) => false,
+            BuildItem::Unit(_) => self.purpose == InstallationPurpose::Military,
+            BuildItem::Spacecraft { .. } => self.purpose == InstallationPurpose::Factory,
+        }
+    }
 }
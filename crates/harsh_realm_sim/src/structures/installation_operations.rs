@@ -0,0 +1,278 @@
+// src/structures/installation_operations.rs
+// Running installations.  Every working installation needs power in
+// proportion to its tier; its own plant covers half of that and the rest is
+// drawn from Energy in its stockpile, so supply runs decide whether it works
+// flat out.  What it then does depends on its purpose: refineries process
+// raw materials as far as there is room for the products, research stations
+// accumulate research, mines dig (see `resources::extraction`), factories and
+// military bases work their queues, all at the pace their power allows.
+
+use uuid::Uuid;
+
+use crate::production::production_queue::Workshop;
+use crate::production::production_queue::Stockpile;
+use crate::resources::resource_type::ResourceType;
+use crate::resources::storage::{self, Storehouse};
+use crate::simulation::simulation::Simulation;
+use crate::structures::installation::{Installation, InstallationError, InstallationPurpose};
+use crate::structures::structure_type::StructureType;
+use crate::units::unit_type::UnitType;
+
+/// Energy a day each tier of a working installation needs
+pub const ENERGY_PER_TIER_DAY: u64 = 20;
+
+/// Energy a day each tier of an installation's own plant supplies
+pub const GENERATED_ENERGY_PER_TIER_DAY: u64 = 10;
+
+/// Raw material a crewed tier 1 refinery processes each day
+pub const REFINERY_THROUGHPUT_PER_DAY: u64 = 40;
+
+/// Research a crewed tier 1 research station does each day
+pub const RESEARCH_PER_DAY: f64 = 1.0;
+
+/// Powers every installation for `days` days, recording the share of its needs each got so
+/// that mining, refining, research and production all run at that pace.  Runs before any of them.
+///
+/// Returns `(structure_id, shortfall)` for installations that ran short of Energy.
+pub fn power(simulation: &mut Simulation, days: u32) -> Vec<(Uuid, u64)> {
+    let mut ids: Vec<Uuid> = simulation.structures.keys().copied().collect();
+    ids.sort();
+
+    let mut shortages = Vec::new();
+    for id in ids {
+        let Some(StructureType::Installation(installation)) = simulation.structures.get_mut(&id) else {
+            continue;
+        };
+        let (power, shortfall) = draw_power(installation, days);
+        installation.set_power(power);
+        if shortfall > 0 {
+            shortages.push((id, shortfall));
+        }
+    }
+    shortages
+}
+
+/// Runs every refinery and research station for `days` days at the power it got
+pub fn operate(simulation: &mut Simulation, days: u32) {
+    let mut ids: Vec<Uuid> = simulation.structures.keys().copied().collect();
+    ids.sort();
+
+    for id in ids {
+        let Some(structure) = simulation.structures.get_mut(&id) else {
+            continue;
+        };
+        let capacity = storage::capacity(structure);
+        let StructureType::Installation(installation) = structure else {
+            continue;
+        };
+        let output = installation.effective_output() * days as f64;
+        match installation.purpose() {
+            InstallationPurpose::Refinery => {
+                let throughput = (REFINERY_THROUGHPUT_PER_DAY as f64 * output) as u64;
+                refine(&mut Storehouse::new(installation.stockpile_mut(), capacity), throughput)
+            }
+            InstallationPurpose::Research => installation.add_research(RESEARCH_PER_DAY * output),
+            InstallationPurpose::Mine | InstallationPurpose::Factory | InstallationPurpose::Military => {}
+        }
+    }
+}
+
+/// Share of the power an installation needs that it got, and how much Energy it went without
+fn draw_power(installation: &mut Installation, days: u32) -> (f64, u64) {
+    if installation.output_factor() <= 0.0 {
+        return (0.0, 0);
+    }
+    let tier_days = installation.tier() as u64 * days as u64;
+    let needed = ENERGY_PER_TIER_DAY * tier_days;
+    let wanted = needed.saturating_sub(GENERATED_ENERGY_PER_TIER_DAY * tier_days);
+    let drawn = installation.stockpile_mut().take(&ResourceType::Energy, wanted);
+    let shortfall = wanted - drawn;
+    if needed == 0 {
+        return (1.0, shortfall);
+    }
+    (1.0 - shortfall as f64 / needed as f64, shortfall)
+}
+
+/// Processes up to `throughput` raw material: Minerals into Metal, NonMetal and Waste, then Ice
+/// into as much Water as there is room for
+fn refine(stockpile: &mut Storehouse, throughput: u64) {
+    // The products of Minerals are all bulk, so they always fit where the Minerals were
+    let minerals = stockpile.take(&ResourceType::Minerals, throughput);
+    if minerals > 0 {
+        let metal = minerals / 2;
+        let non_metal = minerals / 4;
+        stockpile.put(ResourceType::Metal, metal);
+        stockpile.put(ResourceType::NonMetal, non_metal);
+        stockpile.put(ResourceType::Waste, minerals - metal - non_metal);
+    }
+    let room = stockpile.room_for(&ResourceType::Water);
+    let ice = stockpile.take(&ResourceType::Ice, (throughput - minerals).min(room));
+    if ice > 0 {
+        stockpile.put(ResourceType::Water, ice);
+    }
+}
+
+fn installation_mut(simulation: &mut Simulation, installation_id: Uuid) -> Result<&mut Installation, InstallationError> {
+    match simulation.structures.get_mut(&installation_id) {
+        Some(StructureType::Installation(installation)) => Ok(installation),
+        _ => Err(InstallationError::UnknownInstallation(installation_id)),
+    }
+}
+
+/// Upgrades an installation to its next tier, paying from its stockpile; returns the new tier
+pub fn upgrade(simulation: &mut Simulation, installation_id: Uuid) -> Result<u8, InstallationError> {
+    installation_mut(simulation, installation_id)?.upgrade()
+}
+
+/// Puts a unit from an installation's garrison in charge of it, returning the crew it replaced
+pub fn assign_crew(simulation: &mut Simulation, installation_id: Uuid, unit: UnitType) -> Result<Option<UnitType>, InstallationError> {
+    installation_mut(simulation, installation_id)?.assign_crew(unit)
+}
+
+/// Replaces a crewed installation with a settlement under the same id, so tiles,
+/// docked ships and faction intelligence that refer to it still do
+pub fn convert_to_settlement(simulation: &mut Simulation, installation_id: Uuid) -> Result<Uuid, InstallationError> {
+    let installation = installation_mut(simulation, installation_id)?;
+    if installation.crew().is_none() {
+        return Err(InstallationError::Uncrewed);
+    }
+    let Some(StructureType::Installation(installation)) = simulation.structures.remove(&installation_id) else {
+        return Err(InstallationError::UnknownInstallation(installation_id));
+    };
+    let settlement = installation.into_settlement()?;
+    Ok(simulation.add_structure(StructureType::Settlement(settlement)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    use crate::buildings::building_type::BuildingType;
+    use crate::maps::location::Location;
+    use crate::population::person_type::PersonType;
+
+    fn installation(purpose: InstallationPurpose) -> Installation {
+        Installation::new("Outpost".to_string(), purpose, Location::DeepSpace { x: 0.0, y: 0.0 })
+    }
+
+    fn run(installation: Installation, days: u32) -> (Installation, Vec<(Uuid, u64)>) {
+        let mut simulation = Simulation::new();
+        let id = simulation.add_structure(StructureType::Installation(installation));
+        let shortages = power(&mut simulation, days);
+        operate(&mut simulation, days);
+        let Some(StructureType::Installation(installation)) = simulation.structures.remove(&id) else {
+            panic!("installation disappeared");
+        };
+        (installation, shortages)
+    }
+
+    #[test]
+    fn test_refineries_process_by_crew_power_and_tier() {
+        let ore = || HashMap::from([(ResourceType::Minerals, 4_000), (ResourceType::Energy, 10_000)]);
+        let (crewed, shortages) = run(installation(InstallationPurpose::Refinery).with_crew(UnitType::Crew).with_resources(ore()), 10);
+        assert!(shortages.is_empty());
+        assert_eq!(crewed.resources()[&ResourceType::Minerals], 4_000 - 400);
+        assert_eq!(crewed.resources()[&ResourceType::Metal], 200);
+        assert_eq!(crewed.resources()[&ResourceType::Energy], 10_000 - 100);
+
+        let (automated, _) = run(installation(InstallationPurpose::Refinery).with_resources(ore()), 10);
+        assert_eq!(automated.resources()[&ResourceType::Metal], 100);
+
+        // Its own plant keeps it at half power with nothing stockpiled
+        let unpowered = HashMap::from([(ResourceType::Ice, 1000)]);
+        let (unpowered, shortages) = run(installation(InstallationPurpose::Refinery).with_crew(UnitType::Crew).with_resources(unpowered), 10);
+        assert_eq!(shortages.len(), 1);
+        assert_eq!(shortages[0].1, 100);
+        assert_eq!(unpowered.resources()[&ResourceType::Water], 200);
+
+        // Ice waits for room in the tanks
+        let nearly_full = HashMap::from([
+            (ResourceType::Ice, 1000),
+            (ResourceType::Water, storage::INSTALLATION_CAPACITY - 50),
+            (ResourceType::Energy, 10_000),
+        ]);
+        let (full, _) = run(installation(InstallationPurpose::Refinery).with_crew(UnitType::Crew).with_resources(nearly_full), 10);
+        assert_eq!(full.resources()[&ResourceType::Water], storage::INSTALLATION_CAPACITY);
+        assert_eq!(full.resources()[&ResourceType::Ice], 950);
+    }
+
+    #[test]
+    fn test_power_shortages_slow_the_queue() {
+        let supplies = HashMap::from([(ResourceType::Energy, 1_000)]);
+        let (powered, _) = run(installation(InstallationPurpose::Factory).with_crew(UnitType::Crew).with_resources(supplies), 10);
+        let (starved, shortages) = run(installation(InstallationPurpose::Factory).with_crew(UnitType::Crew), 10);
+        assert_eq!(shortages.len(), 1);
+        assert_eq!(starved.power(), 0.5);
+        assert_eq!(starved.labor() * 2, powered.labor());
+    }
+
+    #[test]
+    fn test_research_stations_need_a_crew() {
+        let (idle, shortages) = run(installation(InstallationPurpose::Research), 30);
+        assert_eq!(idle.research(), 0.0);
+        assert!(shortages.is_empty());
+
+        let supplies = HashMap::from([(ResourceType::Energy, 1_000)]);
+        let (working, _) = run(installation(InstallationPurpose::Research).with_crew(UnitType::Engineers).with_resources(supplies), 30);
+        assert!((working.research() - 30.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_crews_come_from_the_garrison() {
+        let mut base = installation(InstallationPurpose::Military);
+        base.add_units(UnitType::Crew, 1);
+        base.add_units(UnitType::Marines, 2);
+        assert_eq!(
+            base.assign_crew(UnitType::Crew),
+            Err(InstallationError::UnsuitableCrew { purpose: InstallationPurpose::Military, unit: UnitType::Crew })
+        );
+        assert_eq!(base.assign_crew(UnitType::Infantry), Err(InstallationError::NoSuchUnit(UnitType::Infantry)));
+        assert_eq!(base.assign_crew(UnitType::Marines), Ok(None));
+        assert_eq!(base.garrison()[&UnitType::Marines], 1);
+        assert_eq!(base.assign_crew(UnitType::Marines), Ok(Some(UnitType::Marines)));
+        assert_eq!(base.garrison()[&UnitType::Marines], 1);
+        assert_eq!(base.release_crew(), Some(UnitType::Marines));
+        assert_eq!(base.garrison()[&UnitType::Marines], 2);
+        assert_eq!(base.output_factor(), 0.0);
+    }
+
+    #[test]
+    fn test_upgrades_cost_more_each_tier() {
+        let mut mine = installation(InstallationPurpose::Mine)
+            .with_resources(HashMap::from([(ResourceType::Metal, 3000), (ResourceType::NonMetal, 1500)]));
+        assert_eq!(mine.upgrade(), Ok(2));
+        assert_eq!(mine.upgrade(), Ok(3));
+        assert_eq!(mine.resources()[&ResourceType::Metal], 0);
+        assert_eq!(mine.upgrade(), Err(InstallationError::FullyUpgraded));
+        assert_eq!(mine.output_factor(), 1.5);
+
+        let mut poor = installation(InstallationPurpose::Mine);
+        assert!(matches!(poor.upgrade(), Err(InstallationError::InsufficientResources { available: 0, .. })));
+        assert_eq!(poor.tier(), 1);
+    }
+
+    #[test]
+    fn test_conversion_keeps_id_and_stockpile() {
+        let mut simulation = Simulation::new();
+        let mut yard = installation(InstallationPurpose::Factory)
+            .with_owner(Uuid::new_v4())
+            .with_resources(HashMap::from([(ResourceType::Metal, 5000), (ResourceType::NonMetal, 5000)]));
+        yard.add_units(UnitType::Engineers, 1);
+        yard.upgrade().unwrap();
+        let owner = yard.owner();
+        let yard = simulation.add_structure(StructureType::Installation(yard));
+        assert_eq!(convert_to_settlement(&mut simulation, yard), Err(InstallationError::Uncrewed));
+
+        assign_crew(&mut simulation, yard, UnitType::Engineers).unwrap();
+        assert_eq!(convert_to_settlement(&mut simulation, yard), Ok(yard));
+        let Some(StructureType::Settlement(town)) = simulation.structures.get(&yard) else {
+            panic!("the yard did not become a settlement");
+        };
+        assert_eq!(town.owner(), owner);
+        assert_eq!(town.resources()[&ResourceType::Metal], 4000);
+        assert_eq!(town.buildings()[&BuildingType::Factory], 2);
+        assert_eq!(town.population()[&PersonType::Worker], crate::structures::installation::CREW_SIZE);
+        assert_eq!(convert_to_settlement(&mut simulation, yard), Err(InstallationError::UnknownInstallation(yard)));
+    }
+}
@@ -1,4 +1,10 @@
+pub mod fleet;
 pub mod installation;
+pub mod installation_operations;
+pub mod movement;
 pub mod settlement;
+pub mod settlement_lifecycle;
 pub mod spacecraft;
 pub mod structure_type;
+#[cfg(test)]
+pub(crate) mod test_support;
@@ -0,0 +1,449 @@
+// src/structures/movement.rs
+// Spacecraft travel.  Setting a destination plots a trajectory from the
+// transfer estimate in `universe::navigation`: it leaves from where the craft
+// is today and aims for where the destination will be on arrival.  Each turn
+// the craft moves along it through deep space, burning propellant as it goes,
+// and on arrival it docks with whatever it was sent to or takes up orbit,
+// unless another faction's fleet is blockading the place (see
+// `structures::fleet`), in which case it is turned away short of it.
+
+use std::fmt::{Display, Formatter};
+use chrono::{Duration, NaiveDate};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::maps::location::Location;
+use crate::resources::resource_type::ResourceType;
+use crate::simulation::simulation::Simulation;
+use crate::structures::fleet;
+use crate::structures::settlement_lifecycle;
+use crate::structures::structure_type::StructureType;
+use crate::universe::navigation::{self, NavigationError};
+use crate::universe::orbital_system::CartesianPosition;
+use crate::universe::solar_system_manager::SolarSystemManager;
+
+/// What spacecraft burn to change course
+pub const PROPELLANT: ResourceType = ResourceType::Hydrocarbons;
+
+/// Propellant each unit of hull size burns per km/s of delta-v
+pub const PROPELLANT_PER_KM_S: f64 = 10.0;
+
+/// Slowest a craft can go, however laden or worn
+const MIN_SPEED: f64 = 0.1;
+
+/// Reasons a course could not be plotted
+#[derive(Debug, Clone, PartialEq)]
+pub enum MovementError {
+    UnknownSpacecraft(Uuid),
+    /// The craft or its destination cannot be placed
+    Navigation(NavigationError),
+    InsufficientPropellant { needed: u64, available: u64 },
+    /// Another faction's fleet is blockading the destination
+    Blockaded { fleet_id: Uuid },
+}
+
+impl Display for MovementError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MovementError::UnknownSpacecraft(id) => write!(f, "no spacecraft with id {}", id),
+            MovementError::Navigation(error) => write!(f, "{}", error),
+            MovementError::InsufficientPropellant { needed, available } => {
+                write!(f, "needs {} propellant but only {} is aboard", needed, available)
+            }
+            MovementError::Blockaded { fleet_id } => write!(f, "turned away by the blockade of fleet {}", fleet_id),
+        }
+    }
+}
+
+impl std::error::Error for MovementError {}
+
+impl From<NavigationError> for MovementError {
+    fn from(error: NavigationError) -> Self {
+        MovementError::Navigation(error)
+    }
+}
+
+/// A plotted course and how far along it a craft is
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Trajectory {
+    /// The destination as ordered
+    destination: Location,
+    /// Where the craft will end up: docked with what it was sent to, or in orbit
+    arrival: Location,
+    departure: NaiveDate,
+    /// Heliocentric position on departure
+    start: CartesianPosition,
+    /// Heliocentric position of the destination on arrival
+    end: CartesianPosition,
+    delta_v: f64,
+    travel_days: f64,
+    elapsed_days: f64,
+    propellant: u64,
+    propellant_spent: u64,
+    /// Whether the trip stays at one body, between its surface and orbit
+    local: bool,
+}
+
+impl Trajectory {
+    pub fn destination(&self) -> &Location {
+        &self.destination
+    }
+
+    pub fn arrival(&self) -> &Location {
+        &self.arrival
+    }
+
+    pub fn departure(&self) -> NaiveDate {
+        self.departure
+    }
+
+    /// Date the craft gets there
+    pub fn eta(&self) -> NaiveDate {
+        self.departure + Duration::days(self.travel_days.ceil() as i64)
+    }
+
+    /// Share of the trip done, from 0 to 1
+    pub fn progress(&self) -> f64 {
+        if self.travel_days <= 0.0 {
+            return 1.0;
+        }
+        (self.elapsed_days / self.travel_days).min(1.0)
+    }
+
+    pub fn remaining_days(&self) -> f64 {
+        (self.travel_days - self.elapsed_days).max(0.0)
+    }
+
+    pub fn delta_v(&self) -> f64 {
+        self.delta_v
+    }
+
+    /// Propellant the whole trip burns
+    pub fn propellant(&self) -> u64 {
+        self.propellant
+    }
+
+    pub fn propellant_spent(&self) -> u64 {
+        self.propellant_spent
+    }
+
+    /// Where the craft is now, in km from the Sun
+    pub fn position(&self) -> CartesianPosition {
+        let t = self.progress();
+        CartesianPosition { x: self.start.x + (self.end.x - self.start.x) * t, y: self.start.y + (self.end.y - self.start.y) * t }
+    }
+}
+
+/// What happened to spacecraft during a movement phase
+#[derive(Debug, Clone, Default)]
+pub struct MovementReport {
+    /// Craft that reached their destination, and where they ended up
+    pub arrived: Vec<(Uuid, Location)>,
+    /// Craft whose ordered destination could not be reached; their orders were cancelled
+    pub blocked: Vec<(Uuid, MovementError)>,
+}
+
+/// The body a location is on or around, if any
+pub(crate) fn body_of(simulation: &Simulation, location: &Location) -> Option<Uuid> {
+    match navigation::resolve(simulation, location).ok()? {
+        Location::Surface { body_id, .. } | Location::Orbit { body_id, .. } => Some(*body_id),
+        _ => None,
+    }
+}
+
+/// Where a craft sent to `destination` ends up: docked with the structure it was sent to or one
+/// standing on the target hex, otherwise in orbit or at the deep space point
+fn arrival_for(simulation: &Simulation, spacecraft_id: Uuid, destination: &Location) -> Result<Location, NavigationError> {
+    let arrival = match destination {
+        Location::Surface { body_id, .. } => match settlement_lifecycle::occupant(simulation, destination) {
+            Some(structure_id) => Location::Docked { structure_id },
+            None => Location::Orbit { body_id: *body_id, orbital_slot_id: Uuid::new_v4() },
+        },
+        other => other.clone(),
+    };
+    // Docking with something that is itself (eventually) docked to this craft would loop
+    let mut host = &arrival;
+    for _ in 0..=simulation.structures.len() {
+        let Location::Docked { structure_id } = host else {
+            return Ok(arrival);
+        };
+        if *structure_id == spacecraft_id {
+            return Err(NavigationError::DockingLoop(spacecraft_id));
+        }
+        host = simulation.structures.get(structure_id).ok_or(NavigationError::UnknownStructure(*structure_id))?.location();
+    }
+    Err(NavigationError::DockingLoop(spacecraft_id))
+}
+
+/// Plots a course for a craft from where it is now to `destination`, without committing to it
+pub fn plot(
+    simulation: &Simulation,
+    solar_system: &SolarSystemManager,
+    spacecraft_id: Uuid,
+    destination: &Location,
+) -> Result<Trajectory, MovementError> {
+    let Some(StructureType::Spacecraft(spacecraft)) = simulation.structures.get(&spacecraft_id) else {
+        return Err(MovementError::UnknownSpacecraft(spacecraft_id));
+    };
+    plot_at(simulation, solar_system, spacecraft_id, destination, spacecraft.speed())
+}
+
+/// Plots a course as `plot` does, flown at `speed` rather than the craft's own, as when keeping
+/// station with slower ships
+pub(crate) fn plot_at(
+    simulation: &Simulation,
+    solar_system: &SolarSystemManager,
+    spacecraft_id: Uuid,
+    destination: &Location,
+    speed: f64,
+) -> Result<Trajectory, MovementError> {
+    let Some(StructureType::Spacecraft(spacecraft)) = simulation.structures.get(&spacecraft_id) else {
+        return Err(MovementError::UnknownSpacecraft(spacecraft_id));
+    };
+    let date = solar_system.get_game_date();
+    let arrival = arrival_for(simulation, spacecraft_id, destination)?;
+    let estimate = navigation::estimate(solar_system, simulation, spacecraft.location(), &arrival, date)?;
+    let travel_days = estimate.travel_days / speed.max(MIN_SPEED);
+    let arrives = date + Duration::days(travel_days.ceil() as i64);
+    let propellant = (estimate.delta_v * PROPELLANT_PER_KM_S * spacecraft.hull_size() as f64).ceil() as u64;
+    let available = spacecraft.propellant();
+    if propellant > available {
+        return Err(MovementError::InsufficientPropellant { needed: propellant, available });
+    }
+
+    Ok(Trajectory {
+        destination: destination.clone(),
+        arrival: arrival.clone(),
+        departure: date,
+        start: navigation::position(solar_system, simulation, spacecraft.location(), date)?,
+        end: navigation::position(solar_system, simulation, &arrival, arrives)?,
+        delta_v: estimate.delta_v,
+        travel_days,
+        elapsed_days: 0.0,
+        propellant,
+        propellant_spent: 0,
+        local: body_of(simulation, spacecraft.location()).is_some_and(|body| body_of(simulation, &arrival) == Some(body)),
+    })
+}
+
+/// Orders a craft to `destination`, plotting its course now; returns the course
+pub fn set_course(
+    simulation: &mut Simulation,
+    solar_system: &SolarSystemManager,
+    spacecraft_id: Uuid,
+    destination: Location,
+) -> Result<Trajectory, MovementError> {
+    let trajectory = plot(simulation, solar_system, spacecraft_id, &destination)?;
+    if let Some(StructureType::Spacecraft(spacecraft)) = simulation.structures.get_mut(&spacecraft_id) {
+        spacecraft.set_destination(Some(destination));
+        spacecraft.set_trajectory(Some(trajectory.clone()));
+    }
+    Ok(trajectory)
+}
+
+/// Moves every craft with a destination along its course for `days` days, plotting
+/// courses for any that do not have one yet
+pub fn advance(simulation: &mut Simulation, solar_system: &SolarSystemManager, days: u32) -> MovementReport {
+    let mut ids: Vec<Uuid> = simulation
+        .structures
+        .iter()
+        .filter_map(|(id, structure)| match structure {
+            StructureType::Spacecraft(spacecraft) if spacecraft.destination().is_some() => Some(*id),
+            _ => None,
+        })
+        .collect();
+    ids.sort();
+
+    let mut report = MovementReport::default();
+    for id in ids {
+        let Some(StructureType::Spacecraft(spacecraft)) = simulation.structures.get(&id) else {
+            continue;
+        };
+        let destination = spacecraft.destination().cloned();
+        let needs_course = match (spacecraft.trajectory(), &destination) {
+            (Some(trajectory), Some(destination)) => trajectory.destination() != destination,
+            _ => true,
+        };
+        if needs_course {
+            let Some(destination) = destination else {
+                continue;
+            };
+            match plot(simulation, solar_system, id, &destination) {
+                Ok(trajectory) => {
+                    if let Some(StructureType::Spacecraft(spacecraft)) = simulation.structures.get_mut(&id) {
+                        spacecraft.set_trajectory(Some(trajectory));
+                    }
+                }
+                Err(error) => {
+                    if let Some(StructureType::Spacecraft(spacecraft)) = simulation.structures.get_mut(&id) {
+                        spacecraft.set_destination(None);
+                    }
+                    report.blocked.push((id, error));
+                    continue;
+                }
+            }
+        }
+
+        let Some(StructureType::Spacecraft(spacecraft)) = simulation.structures.get_mut(&id) else {
+            continue;
+        };
+        let Some(mut trajectory) = spacecraft.trajectory().cloned() else {
+            continue;
+        };
+        trajectory.elapsed_days += days as f64;
+        let burned = ((trajectory.propellant as f64 * trajectory.progress()).round() as u64).min(trajectory.propellant);
+        trajectory.propellant_spent += spacecraft.burn_propellant(burned.saturating_sub(trajectory.propellant_spent));
+
+        // Whatever the craft was to dock with may have gone while it was on the way
+        if let Location::Docked { structure_id } = trajectory.arrival {
+            if !simulation.structures.contains_key(&structure_id) {
+                let end = trajectory.end.clone();
+                trajectory.arrival = Location::DeepSpace { x: end.x as f32, y: end.y as f32 };
+            }
+        }
+
+        // A hostile blockade turns the craft away where its course ends
+        let owner = simulation.structures.get(&id).and_then(StructureType::owner);
+        let blockade = if trajectory.progress() >= 1.0 {
+            fleet::blockaded_against(simulation, trajectory.arrival(), owner)
+        } else {
+            None
+        };
+
+        let Some(StructureType::Spacecraft(spacecraft)) = simulation.structures.get_mut(&id) else {
+            continue;
+        };
+        if let Some(fleet_id) = blockade {
+            let end = trajectory.end.clone();
+            spacecraft.set_location(Location::DeepSpace { x: end.x as f32, y: end.y as f32 });
+            spacecraft.set_destination(None);
+            report.blocked.push((id, MovementError::Blockaded { fleet_id }));
+        } else if trajectory.progress() >= 1.0 {
+            let arrival = trajectory.arrival().clone();
+            spacecraft.set_location(arrival.clone());
+            spacecraft.set_destination(None);
+            report.arrived.push((id, arrival));
+        } else {
+            if !trajectory.local {
+                let position = trajectory.position();
+                spacecraft.set_location(Location::DeepSpace { x: position.x as f32, y: position.y as f32 });
+            }
+            spacecraft.set_trajectory(Some(trajectory));
+        }
+    }
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use hex2d::Coordinate as HexCoord;
+
+    use crate::structures::settlement::Settlement;
+    use crate::structures::spacecraft::Spacecraft;
+    use crate::structures::test_support::world;
+
+    fn launch(simulation: &mut Simulation, location: Location, propellant: u32) -> Uuid {
+        let ship = Spacecraft::new("Skiff".to_string(), location).with_cargo(HashMap::from([(PROPELLANT, propellant)]));
+        simulation.add_structure(StructureType::Spacecraft(ship))
+    }
+
+    fn spacecraft(simulation: &Simulation, id: Uuid) -> &Spacecraft {
+        let Some(StructureType::Spacecraft(spacecraft)) = simulation.structures.get(&id) else {
+            panic!("no spacecraft {}", id);
+        };
+        spacecraft
+    }
+
+    #[test]
+    fn test_ships_need_propellant_to_leave() {
+        let (mut simulation, solar_system, body_id) = world();
+        let pad = Location::Surface { body_id, hex_coord: HexCoord::new(0, 0) };
+        let ship = launch(&mut simulation, pad, 0);
+        let orbit = Location::Orbit { body_id, orbital_slot_id: Uuid::new_v4() };
+        assert!(matches!(
+            set_course(&mut simulation, &solar_system, ship, orbit),
+            Err(MovementError::InsufficientPropellant { available: 0, .. })
+        ));
+        assert!(spacecraft(&simulation, ship).destination().is_none());
+        assert_eq!(
+            set_course(&mut simulation, &solar_system, Uuid::nil(), Location::DeepSpace { x: 0.0, y: 0.0 }).unwrap_err(),
+            MovementError::UnknownSpacecraft(Uuid::nil())
+        );
+    }
+
+    #[test]
+    fn test_landing_on_a_settlement_docks_with_it() {
+        let (mut simulation, solar_system, body_id) = world();
+        let orbit = Location::Orbit { body_id, orbital_slot_id: Uuid::new_v4() };
+        let ship = launch(&mut simulation, orbit.clone(), 10_000);
+        let town = Location::Surface { body_id, hex_coord: HexCoord::new(0, 0) };
+        let town_id = simulation.add_structure(StructureType::Settlement(Settlement::new("Base".to_string(), town.clone())));
+
+        let trajectory = set_course(&mut simulation, &solar_system, ship, town).unwrap();
+        assert_eq!(trajectory.arrival(), &Location::Docked { structure_id: town_id });
+        assert!(trajectory.propellant() > 0);
+
+        let report = advance(&mut simulation, &solar_system, 1);
+        assert_eq!(report.arrived, vec![(ship, Location::Docked { structure_id: town_id })]);
+        let ship = spacecraft(&simulation, ship);
+        assert_eq!(ship.location(), &Location::Docked { structure_id: town_id });
+        assert_eq!(ship.propellant(), 10_000 - trajectory.propellant());
+        assert!(ship.destination().is_none() && ship.trajectory().is_none());
+
+        // An empty hex has nothing to dock with, so the ship waits in orbit
+        let (simulation, _, body_id) = world();
+        let bare = Location::Surface { body_id, hex_coord: HexCoord::new(0, 0) };
+        let arrival = arrival_for(&simulation, Uuid::new_v4(), &bare).unwrap();
+        assert!(matches!(arrival, Location::Orbit { body_id: orbiting, .. } if orbiting == body_id));
+    }
+
+    #[test]
+    fn test_deep_space_trips_move_through_space_and_burn_as_they_go() {
+        let (mut simulation, solar_system, _) = world();
+        let ship = launch(&mut simulation, Location::DeepSpace { x: 1.5e8, y: 0.0 }, 1_000_000);
+        let target = Location::DeepSpace { x: 0.0, y: 2.3e8 };
+        let trajectory = set_course(&mut simulation, &solar_system, ship, target.clone()).unwrap();
+        assert!(trajectory.travel_days > 2.0);
+        assert_eq!(trajectory.eta(), trajectory.departure() + Duration::days(trajectory.travel_days.ceil() as i64));
+
+        let half = (trajectory.travel_days / 2.0).floor() as u32;
+        advance(&mut simulation, &solar_system, half);
+        let flying = spacecraft(&simulation, ship);
+        let progress = flying.trajectory().unwrap().progress();
+        assert!(progress > 0.0 && progress < 1.0);
+        let spent = flying.trajectory().unwrap().propellant_spent();
+        assert!(spent > 0 && spent < trajectory.propellant());
+        let Location::DeepSpace { x, y } = *flying.location() else {
+            panic!("ship should be between the two points");
+        };
+        assert!(x < 1.5e8 && x > 0.0 && y > 0.0 && y < 2.3e8);
+
+        let report = advance(&mut simulation, &solar_system, trajectory.travel_days.ceil() as u32);
+        assert_eq!(report.arrived, vec![(ship, target.clone())]);
+        assert_eq!(spacecraft(&simulation, ship).location(), &target);
+        assert_eq!(spacecraft(&simulation, ship).propellant(), 1_000_000 - trajectory.propellant());
+    }
+
+    #[test]
+    fn test_unreachable_orders_are_cancelled() {
+        let (mut simulation, solar_system, _) = world();
+        let ship = launch(&mut simulation, Location::DeepSpace { x: 0.0, y: 0.0 }, 100);
+        let Some(StructureType::Spacecraft(spacecraft_mut)) = simulation.structures.get_mut(&ship) else {
+            unreachable!();
+        };
+        let nowhere = Uuid::new_v4();
+        spacecraft_mut.set_destination(Some(Location::Orbit { body_id: nowhere, orbital_slot_id: Uuid::new_v4() }));
+
+        let report = advance(&mut simulation, &solar_system, 1);
+        assert_eq!(report.blocked, vec![(ship, MovementError::Navigation(NavigationError::UnknownBody(nowhere)))]);
+        assert!(spacecraft(&simulation, ship).destination().is_none());
+
+        // Nor can a ship dock with itself
+        assert_eq!(
+            plot(&simulation, &solar_system, ship, &Location::Docked { structure_id: ship }).unwrap_err(),
+            MovementError::Navigation(NavigationError::DockingLoop(ship))
+        );
+    }
+}
@@ -1,19 +1,405 @@
 use crate::buildings::building_type::BuildingType;
+use crate::maps::location::Location;
 use crate::population::person_type::PersonType;
+use crate::production::production_queue::{BuildItem, ProductionQueue, Stockpile, Workshop};
 use crate::resources::resource_type::ResourceType;
+use crate::units::unit_type::UnitType;
 use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
+/// Reasons a settlement operation was refused
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SettlementError {
+    /// No settlement with this id exists
+    UnknownSettlement(Uuid),
+    /// The location is not a surface hex or orbit of a known body
+    InvalidLocation,
+    /// Another settlement or installation already stands at the location
+    Occupied { structure_id: Uuid },
+    InsufficientPopulation { person_type: PersonType, requested: u32, available: u32 },
+    InsufficientResources { resource: ResourceType, requested: u64, available: u64 },
+    /// Merging settlements requires both to belong to the same faction
+    DifferentOwners,
+    /// Merging settlements requires both to be on or around the same body
+    DifferentBodies,
+    /// A settlement cannot be merged into itself
+    SameSettlement,
+    /// The capturing faction already owns the settlement
+    AlreadyOwned,
+}
+
+impl Display for SettlementError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SettlementError::UnknownSettlement(id) => write!(f, "no settlement with id {}", id),
+            SettlementError::InvalidLocation => write!(f, "settlements must be on a surface hex or in orbit of a known body"),
+            SettlementError::Occupied { structure_id } => write!(f, "location is already occupied by {}", structure_id),
+            SettlementError::InsufficientPopulation { person_type, requested, available } => {
+                write!(f, "needs {} {:?} but only {} live here", requested, person_type, available)
+            }
+            SettlementError::InsufficientResources { resource, requested, available } => {
+                write!(f, "needs {} {:?} but only {} is stockpiled", requested, resource, available)
+            }
+            SettlementError::DifferentOwners => write!(f, "settlements belong to different factions"),
+            SettlementError::DifferentBodies => write!(f, "settlements are on different bodies"),
+            SettlementError::SameSettlement => write!(f, "a settlement cannot be merged into itself"),
+            SettlementError::AlreadyOwned => write!(f, "faction already owns the settlement"),
+        }
+    }
+}
+
+impl std::error::Error for SettlementError {}
+
+/// Shielding a settlement's habitats start with, in g/cm²: about 3 m of regolith piled over them
+pub const HABITAT_SHIELDING_G_CM2: f64 = 500.0;
+
+fn habitat_shielding() -> f64 {
+    HABITAT_SHIELDING_G_CM2
+}
+
 #[derive(Debug,Clone,Serialize,Deserialize)]
 pub struct Settlement {
     id: Uuid,
     name: String,
+    location: Location,
+    #[serde(default)]
+    owner: Option<Uuid>,
     population: HashMap<PersonType, u32>,
     buildings: HashMap<BuildingType, u32>,
-    // production queues
-    // unit production queue
-    // buidling production queue
+    /// Buildings, units and ships being made, in order
+    #[serde(default)]
+    production: ProductionQueue,
+    /// Units stationed here
+    #[serde(default)]
+    garrison: HashMap<UnitType, u32>,
     resources: HashMap<ResourceType, u64>,
     // local market
+    /// Wear on equipment from 0 (new) to 1 (worn out)
+    #[serde(default)]
+    wear: f32,
+    /// Matter between the inhabitants and the sky, in g/cm²
+    #[serde(default = "habitat_shielding")]
+    shielding_g_cm2: f64,
+}
+
+impl Settlement {
+    pub fn new(name: String, location: Location) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            name,
+            location,
+            owner: None,
+            population: HashMap::new(),
+            buildings: HashMap::new(),
+            production: ProductionQueue::new(),
+            garrison: HashMap::new(),
+            resources: HashMap::new(),
+            wear: 0.0,
+            shielding_g_cm2: HABITAT_SHIELDING_G_CM2,
+        }
+    }
+
+    pub fn with_owner(mut self, faction_id: Uuid) -> Self {
+        self.owner = Some(faction_id);
+        self
+    }
+
+    pub fn with_population(mut self, population: HashMap<PersonType, u32>) -> Self {
+        self.population = population;
+        self
+    }
+
+    pub fn with_resources(mut self, resources: HashMap<ResourceType, u64>) -> Self {
+        self.resources = resources;
+        self
+    }
+
+    /// Keeps the id of the structure this settlement grew out of
+    pub(crate) fn with_id(mut self, id: Uuid) -> Self {
+        self.id = id;
+        self
+    }
+
+    pub(crate) fn with_production(mut self, production: ProductionQueue) -> Self {
+        self.production = production;
+        self
+    }
+
+    pub fn with_shielding(mut self, shielding_g_cm2: f64) -> Self {
+        self.shielding_g_cm2 = shielding_g_cm2;
+        self
+    }
+
+    pub fn with_buildings(mut self, buildings: HashMap<BuildingType, u32>) -> Self {
+        self.buildings = buildings;
+        self
+    }
+
+    pub fn id(&self) -> Uuid {
+        self.id
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn location(&self) -> &Location {
+        &self.location
+    }
+
+    pub fn owner(&self) -> Option<Uuid> {
+        self.owner
+    }
+
+    pub fn population(&self) -> &HashMap<PersonType, u32> {
+        &self.population
+    }
+
+    /// Everyone living here, of every type
+    pub fn total_population(&self) -> u32 {
+        self.population.values().sum()
+    }
+
+    /// Hands the settlement to `faction_id`, returning the previous owner
+    pub fn change_owner(&mut self, faction_id: Uuid) -> Option<Uuid> {
+        self.owner.replace(faction_id)
+    }
+
+    /// Adds `count` people of `person_type`
+    pub fn add_population(&mut self, person_type: PersonType, count: u32) {
+        if count > 0 {
+            *self.population.entry(person_type).or_default() += count;
+        }
+    }
+
+    /// Removes exactly `count` people of `person_type`, or nobody if there are too few
+    pub fn remove_population(&mut self, person_type: PersonType, count: u32) -> Result<(), SettlementError> {
+        let available = self.population.get(&person_type).copied().unwrap_or(0);
+        if count > available {
+            return Err(SettlementError::InsufficientPopulation { person_type, requested: count, available });
+        }
+        if count == available {
+            self.population.remove(&person_type);
+        } else {
+            self.population.insert(person_type, available - count);
+        }
+        Ok(())
+    }
+
+    pub fn buildings(&self) -> &HashMap<BuildingType, u32> {
+        &self.buildings
+    }
+
+    /// Adds one finished `building`
+    pub fn add_building(&mut self, building: BuildingType) {
+        *self.buildings.entry(building).or_default() += 1;
+    }
+
+    pub fn garrison(&self) -> &HashMap<UnitType, u32> {
+        &self.garrison
+    }
+
+    /// Stations `count` more units of `unit_type` here
+    pub fn add_units(&mut self, unit_type: UnitType, count: u32) {
+        if count > 0 {
+            *self.garrison.entry(unit_type).or_default() += count;
+        }
+    }
+
+    pub fn resources(&self) -> &HashMap<ResourceType, u64> {
+        &self.resources
+    }
+
+    /// Adds `amount` of `resource` to the settlement's stockpile
+    pub fn add_resource(&mut self, resource: ResourceType, amount: u64) {
+        *self.resources.entry(resource).or_default() += amount;
+    }
+
+    /// Takes up to `amount` of `resource` from the stockpile, returning how much was taken
+    pub fn remove_resource(&mut self, resource: &ResourceType, amount: u64) -> u64 {
+        let Some(stock) = self.resources.get_mut(resource) else {
+            return 0;
+        };
+        let taken = amount.min(*stock);
+        *stock -= taken;
+        taken
+    }
+
+    /// Takes every listed amount from the stockpile, or nothing at all if any falls short
+    pub fn take_resources(&mut self, amounts: &HashMap<ResourceType, u64>) -> Result<(), SettlementError> {
+        for (resource, &requested) in amounts {
+            let available = self.resources.get(resource).copied().unwrap_or(0);
+            if requested > available {
+                return Err(SettlementError::InsufficientResources { resource: resource.clone(), requested, available });
+            }
+        }
+        for (resource, amount) in amounts {
+            self.remove_resource(resource, *amount);
+        }
+        Ok(())
+    }
+
+    /// Builds one `building`, paying its cost from the stockpile
+    pub fn construct(&mut self, building: BuildingType) -> Result<(), SettlementError> {
+        self.take_resources(&building.cost())?;
+        self.add_building(building);
+        Ok(())
+    }
+
+    /// Moves everyone, every building and the whole stockpile of `other` into this settlement.
+    /// Equipment wear is averaged by population.
+    pub fn absorb(&mut self, other: Settlement) -> Result<(), SettlementError> {
+        if other.id == self.id {
+            return Err(SettlementError::SameSettlement);
+        }
+        if other.owner != self.owner {
+            return Err(SettlementError::DifferentOwners);
+        }
+        let (ours, theirs) = (self.total_population() as f32, other.total_population() as f32);
+        if ours + theirs > 0.0 {
+            self.wear = (self.wear * ours + other.wear * theirs) / (ours + theirs);
+        } else {
+            self.wear = self.wear.max(other.wear);
+        }
+        for (person_type, count) in other.population {
+            self.add_population(person_type, count);
+        }
+        for (building, count) in other.buildings {
+            *self.buildings.entry(building).or_default() += count;
+        }
+        for (unit_type, count) in other.garrison {
+            self.add_units(unit_type, count);
+        }
+        self.production.append(other.production);
+        for (resource, amount) in other.resources {
+            self.add_resource(resource, amount);
+        }
+        Ok(())
+    }
+
+    pub fn wear(&self) -> f32 {
+        self.wear
+    }
+
+    /// Wears equipment down by `amount`, up to fully worn out
+    pub fn add_wear(&mut self, amount: f32) {
+        self.wear = (self.wear + amount).clamp(0.0, 1.0);
+    }
+
+    pub fn shielding_g_cm2(&self) -> f64 {
+        self.shielding_g_cm2
+    }
+}
+
+impl Workshop for Settlement {
+    fn production(&self) -> &ProductionQueue {
+        &self.production
+    }
+
+    fn stockpile(&self) -> &dyn Stockpile {
+        &self.resources
+    }
+
+    fn workshop_mut(&mut self) -> (&mut ProductionQueue, &mut dyn Stockpile) {
+        (&mut self.production, &mut self.resources)
+    }
+
+    /// Everyone but children works
+    fn labor(&self) -> u32 {
+        self.total_population() - self.population.get(&PersonType::Child).copied().unwrap_or(0)
+    }
+
+    /// Settlements put up buildings and train units anywhere; ships need a factory
+    fn can_produce(&self, item: &BuildItem) -> bool {
+        match item {
+            BuildItem::Building(_) | BuildItem::Unit(_) => true,
+            BuildItem::Spacecraft { .. } => self.buildings.get(&BuildingType::Factory).is_some_and(|&n| n > 0),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use hex2d::Coordinate as HexCoord;
+
+    fn settlement() -> Settlement {
+        let location = Location::Surface { body_id: Uuid::new_v4(), hex_coord: HexCoord::new(0, 0) };
+        Settlement::new("Test".to_string(), location)
+    }
+
+    #[test]
+    fn test_population_cannot_go_negative() {
+        let mut settlement = settlement();
+        settlement.add_population(PersonType::Worker, 10);
+        settlement.add_population(PersonType::Scientist, 2);
+        assert_eq!(settlement.total_population(), 12);
+
+        assert!(settlement.remove_population(PersonType::Worker, 4).is_ok());
+        assert_eq!(settlement.population()[&PersonType::Worker], 6);
+        assert_eq!(
+            settlement.remove_population(PersonType::Scientist, 3),
+            Err(SettlementError::InsufficientPopulation { person_type: PersonType::Scientist, requested: 3, available: 2 })
+        );
+        assert_eq!(settlement.population()[&PersonType::Scientist], 2);
+        assert!(settlement.remove_population(PersonType::Scientist, 2).is_ok());
+        assert!(!settlement.population().contains_key(&PersonType::Scientist));
+    }
+
+    #[test]
+    fn test_taking_resources_is_all_or_nothing() {
+        let mut settlement = settlement().with_resources(HashMap::from([(ResourceType::Metal, 100), (ResourceType::Water, 5)]));
+        let too_much = HashMap::from([(ResourceType::Metal, 50), (ResourceType::Water, 10)]);
+        assert!(matches!(
+            settlement.take_resources(&too_much),
+            Err(SettlementError::InsufficientResources { resource: ResourceType::Water, requested: 10, available: 5 })
+        ));
+        assert_eq!(settlement.resources()[&ResourceType::Metal], 100);
+
+        assert!(settlement.take_resources(&HashMap::from([(ResourceType::Metal, 50), (ResourceType::Water, 5)])).is_ok());
+        assert_eq!(settlement.resources()[&ResourceType::Metal], 50);
+        assert_eq!(settlement.resources()[&ResourceType::Water], 0);
+    }
+
+    #[test]
+    fn test_construction_pays_for_buildings() {
+        let mut settlement = settlement().with_resources(BuildingType::Mine.cost());
+        assert!(settlement.construct(BuildingType::Mine).is_ok());
+        assert_eq!(settlement.buildings()[&BuildingType::Mine], 1);
+        assert!(settlement.resources().values().all(|&amount| amount == 0));
+        assert!(settlement.construct(BuildingType::Mine).is_err());
+        assert_eq!(settlement.buildings()[&BuildingType::Mine], 1);
+    }
+
+    #[test]
+    fn test_absorb_combines_everything() {
+        let owner = Uuid::new_v4();
+        let mut town = settlement().with_owner(owner).with_population(HashMap::from([(PersonType::Worker, 30)]));
+        town.add_wear(0.4);
+        let mut village = settlement()
+            .with_owner(owner)
+            .with_population(HashMap::from([(PersonType::Worker, 10)]))
+            .with_buildings(HashMap::from([(BuildingType::Factory, 1)]))
+            .with_resources(HashMap::from([(ResourceType::Food, 20)]));
+        village.add_wear(0.8);
+
+        assert_eq!(town.clone().absorb(settlement()), Err(SettlementError::DifferentOwners));
+        assert_eq!(town.clone().absorb(town.clone()), Err(SettlementError::SameSettlement));
+
+        town.absorb(village).unwrap();
+        assert_eq!(town.population()[&PersonType::Worker], 40);
+        assert_eq!(town.buildings()[&BuildingType::Factory], 1);
+        assert_eq!(town.resources()[&ResourceType::Food], 20);
+        assert!((town.wear() - 0.5).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_change_owner_returns_previous() {
+        let (old, new) = (Uuid::new_v4(), Uuid::new_v4());
+        let mut settlement = settlement().with_owner(old);
+        assert_eq!(settlement.change_owner(new), Some(old));
+        assert_eq!(settlement.owner(), Some(new));
+    }
 }
@@ -0,0 +1,261 @@
+// src/structures/settlement_lifecycle.rs
+// Founding, merging, abandoning and capturing settlements.  These change the
+// world around a settlement as well as the settlement itself: each one keeps
+// the surface tile's owner and structure in step, and no two settlements or
+// installations may stand on the same hex or orbital slot.
+
+use uuid::Uuid;
+
+use crate::maps::location::Location;
+use crate::simulation::simulation::Simulation;
+use crate::structures::settlement::{Settlement, SettlementError};
+use crate::structures::structure_type::{StructureKind, StructureType};
+use crate::universe::solar_system_manager::SolarSystemManager;
+
+/// The settlement or installation standing at `location`, if any
+pub fn occupant(simulation: &Simulation, location: &Location) -> Option<Uuid> {
+    simulation
+        .structures
+        .values()
+        .filter(|structure| structure.kind() != StructureKind::Spacecraft)
+        .find(|structure| structure.location() == location)
+        .map(|structure| structure.id())
+}
+
+/// Adds `settlement` to the world and claims its hex for its owner
+pub fn found(
+    simulation: &mut Simulation,
+    solar_system: &mut SolarSystemManager,
+    settlement: Settlement,
+) -> Result<Uuid, SettlementError> {
+    let location = settlement.location().clone();
+    let buildable = match &location {
+        Location::Surface { body_id, .. } => solar_system
+            .get_body_by_id(*body_id)
+            .is_some_and(|body| !body.is_giant() && solar_system.is_valid_location(&location)),
+        Location::Orbit { body_id, .. } => solar_system.get_body_by_id(*body_id).is_some(),
+        Location::DeepSpace { .. } | Location::Docked { .. } => false,
+    };
+    if !buildable {
+        return Err(SettlementError::InvalidLocation);
+    }
+    if let Some(structure_id) = occupant(simulation, &location) {
+        return Err(SettlementError::Occupied { structure_id });
+    }
+
+    let owner = settlement.owner();
+    let id = simulation.add_structure(StructureType::Settlement(settlement));
+    if let Some(tile) = solar_system.tile_mut(&location, simulation.seed) {
+        tile.owner = owner.or(tile.owner);
+        tile.structure_id = Some(id);
+    }
+    Ok(id)
+}
+
+/// Removes a settlement, leaving its hex empty; returns what was left behind
+pub fn abandon(
+    simulation: &mut Simulation,
+    solar_system: &mut SolarSystemManager,
+    settlement_id: Uuid,
+) -> Result<Settlement, SettlementError> {
+    let settlement = take(simulation, settlement_id)?;
+    release_tile(simulation, solar_system, settlement.location(), settlement_id);
+    Ok(settlement)
+}
+
+/// Hands a settlement and its hex to `faction_id`, returning the previous owner
+pub fn capture(
+    simulation: &mut Simulation,
+    solar_system: &mut SolarSystemManager,
+    settlement_id: Uuid,
+    faction_id: Uuid,
+) -> Result<Option<Uuid>, SettlementError> {
+    let settlement = settlement_mut(simulation, settlement_id)?;
+    if settlement.owner() == Some(faction_id) {
+        return Err(SettlementError::AlreadyOwned);
+    }
+    let previous = settlement.change_owner(faction_id);
+    let location = settlement.location().clone();
+    if let Some(tile) = solar_system.tile_mut(&location, simulation.seed) {
+        tile.owner = Some(faction_id);
+    }
+    Ok(previous)
+}
+
+/// Folds `absorbed` into `into`; both must share an owner and a body.  The absorbed settlement's hex is freed.
+pub fn merge(
+    simulation: &mut Simulation,
+    solar_system: &mut SolarSystemManager,
+    into: Uuid,
+    absorbed: Uuid,
+) -> Result<(), SettlementError> {
+    if into == absorbed {
+        return Err(SettlementError::SameSettlement);
+    }
+    let target = settlement_mut(simulation, into)?;
+    let (owner, body) = (target.owner(), body_of(target.location()));
+    let source = settlement_mut(simulation, absorbed)?;
+    if source.owner() != owner {
+        return Err(SettlementError::DifferentOwners);
+    }
+    if body.is_none() || body_of(source.location()) != body {
+        return Err(SettlementError::DifferentBodies);
+    }
+
+    let source = take(simulation, absorbed)?;
+    release_tile(simulation, solar_system, &source.location().clone(), absorbed);
+    settlement_mut(simulation, into)?.absorb(source)
+}
+
+fn body_of(location: &Location) -> Option<Uuid> {
+    match location {
+        Location::Surface { body_id, .. } | Location::Orbit { body_id, .. } => Some(*body_id),
+        Location::DeepSpace { .. } | Location::Docked { .. } => None,
+    }
+}
+
+fn settlement_mut(simulation: &mut Simulation, settlement_id: Uuid) -> Result<&mut Settlement, SettlementError> {
+    match simulation.structures.get_mut(&settlement_id) {
+        Some(StructureType::Settlement(settlement)) => Ok(settlement),
+        _ => Err(SettlementError::UnknownSettlement(settlement_id)),
+    }
+}
+
+fn take(simulation: &mut Simulation, settlement_id: Uuid) -> Result<Settlement, SettlementError> {
+    settlement_mut(simulation, settlement_id)?;
+    match simulation.structures.remove(&settlement_id) {
+        Some(StructureType::Settlement(settlement)) => Ok(settlement),
+        _ => Err(SettlementError::UnknownSettlement(settlement_id)),
+    }
+}
+
+/// Clears the tile's structure if it still points at `structure_id`; ownership stays until territory is next resolved
+fn release_tile(simulation: &Simulation, solar_system: &mut SolarSystemManager, location: &Location, structure_id: Uuid) {
+    if let Some(tile) = solar_system.tile_mut(location, simulation.seed) {
+        if tile.structure_id == Some(structure_id) {
+            tile.structure_id = None;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use hex2d::Coordinate as HexCoord;
+
+    use crate::population::person_type::PersonType;
+    use crate::resources::resource_type::ResourceType;
+    use crate::structures::installation::{Installation, InstallationPurpose};
+    use crate::structures::test_support::world;
+
+    fn surface(body_id: Uuid, x: i32, y: i32) -> Location {
+        Location::Surface { body_id, hex_coord: HexCoord::new(x, y) }
+    }
+
+    /// A hex bordering the pole at (0, 0)
+    fn beside_pole(solar_system: &SolarSystemManager, body_id: Uuid) -> Location {
+        let grid = solar_system.get_body_by_id(body_id).unwrap().surface_grid();
+        Location::Surface { body_id, hex_coord: grid.neighbors(HexCoord::new(0, 0))[0] }
+    }
+
+    fn tile(solar_system: &mut SolarSystemManager, simulation: &Simulation, location: &Location) -> (Option<Uuid>, Option<Uuid>) {
+        let tile = solar_system.tile_mut(location, simulation.seed).unwrap();
+        (tile.owner, tile.structure_id)
+    }
+
+    #[test]
+    fn test_found_claims_the_hex_once() {
+        let (mut simulation, mut solar_system, body_id) = world();
+        let faction = Uuid::new_v4();
+        let here = surface(body_id, 0, 0);
+
+        let id = found(&mut simulation, &mut solar_system, Settlement::new("First".to_string(), here.clone()).with_owner(faction)).unwrap();
+        assert_eq!(tile(&mut solar_system, &simulation, &here), (Some(faction), Some(id)));
+
+        let second = Settlement::new("Second".to_string(), here.clone());
+        assert_eq!(found(&mut simulation, &mut solar_system, second), Err(SettlementError::Occupied { structure_id: id }));
+        let nowhere = Settlement::new("Lost".to_string(), Location::DeepSpace { x: 0.0, y: 0.0 });
+        assert_eq!(found(&mut simulation, &mut solar_system, nowhere), Err(SettlementError::InvalidLocation));
+        let off_map = Settlement::new("Off".to_string(), surface(body_id, 10_000, 10_000));
+        assert_eq!(found(&mut simulation, &mut solar_system, off_map), Err(SettlementError::InvalidLocation));
+        assert_eq!(simulation.structures.len(), 1);
+    }
+
+    #[test]
+    fn test_installations_block_founding() {
+        let (mut simulation, mut solar_system, body_id) = world();
+        let here = surface(body_id, 1, 0);
+        let mine = simulation.add_structure(StructureType::Installation(Installation::new("Pit".to_string(), InstallationPurpose::Mine, here.clone())));
+        let settlement = Settlement::new("Camp".to_string(), here);
+        assert_eq!(found(&mut simulation, &mut solar_system, settlement), Err(SettlementError::Occupied { structure_id: mine }));
+    }
+
+    #[test]
+    fn test_abandon_frees_the_hex() {
+        let (mut simulation, mut solar_system, body_id) = world();
+        let here = surface(body_id, 0, 0);
+        let settlement = Settlement::new("Ghost".to_string(), here.clone())
+            .with_resources(HashMap::from([(ResourceType::Metal, 10)]));
+        let id = found(&mut simulation, &mut solar_system, settlement).unwrap();
+
+        let left = abandon(&mut simulation, &mut solar_system, id).unwrap();
+        assert_eq!(left.resources()[&ResourceType::Metal], 10);
+        assert!(simulation.structures.is_empty());
+        assert_eq!(tile(&mut solar_system, &simulation, &here).1, None);
+        assert_eq!(abandon(&mut simulation, &mut solar_system, id).unwrap_err(), SettlementError::UnknownSettlement(id));
+        assert!(found(&mut simulation, &mut solar_system, Settlement::new("Again".to_string(), here)).is_ok());
+    }
+
+    #[test]
+    fn test_capture_transfers_settlement_and_hex() {
+        let (mut simulation, mut solar_system, body_id) = world();
+        let (defender, attacker) = (Uuid::new_v4(), Uuid::new_v4());
+        let here = surface(body_id, 0, 0);
+        let id = found(&mut simulation, &mut solar_system, Settlement::new("Keep".to_string(), here.clone()).with_owner(defender)).unwrap();
+
+        assert_eq!(capture(&mut simulation, &mut solar_system, id, attacker), Ok(Some(defender)));
+        assert_eq!(simulation.structures[&id].owner(), Some(attacker));
+        assert_eq!(tile(&mut solar_system, &simulation, &here), (Some(attacker), Some(id)));
+        assert_eq!(capture(&mut simulation, &mut solar_system, id, attacker), Err(SettlementError::AlreadyOwned));
+    }
+
+    #[test]
+    fn test_merge_moves_everything_and_frees_the_hex() {
+        let (mut simulation, mut solar_system, body_id) = world();
+        let faction = Uuid::new_v4();
+        let (here, there) = (surface(body_id, 0, 0), beside_pole(&solar_system, body_id));
+        let town = Settlement::new("Town".to_string(), here).with_owner(faction).with_population(HashMap::from([(PersonType::Worker, 5)]));
+        let village = Settlement::new("Village".to_string(), there.clone())
+            .with_owner(faction)
+            .with_population(HashMap::from([(PersonType::Worker, 3)]))
+            .with_resources(HashMap::from([(ResourceType::Water, 40)]));
+        let town = found(&mut simulation, &mut solar_system, town).unwrap();
+        let village = found(&mut simulation, &mut solar_system, village).unwrap();
+
+        assert_eq!(merge(&mut simulation, &mut solar_system, town, town), Err(SettlementError::SameSettlement));
+        merge(&mut simulation, &mut solar_system, town, village).unwrap();
+        let Some(StructureType::Settlement(merged)) = simulation.structures.get(&town) else {
+            panic!("town disappeared");
+        };
+        assert_eq!(merged.population()[&PersonType::Worker], 8);
+        assert_eq!(merged.resources()[&ResourceType::Water], 40);
+        assert!(!simulation.structures.contains_key(&village));
+        assert_eq!(tile(&mut solar_system, &simulation, &there).1, None);
+    }
+
+    #[test]
+    fn test_merge_refuses_strangers() {
+        let (mut simulation, mut solar_system, body_id) = world();
+        let (_, _, other_body) = world();
+        let (faction, there) = (Uuid::new_v4(), beside_pole(&solar_system, body_id));
+        let ours = found(&mut simulation, &mut solar_system, Settlement::new("A".to_string(), surface(body_id, 0, 0)).with_owner(faction)).unwrap();
+        let theirs = found(&mut simulation, &mut solar_system, Settlement::new("B".to_string(), there).with_owner(Uuid::new_v4())).unwrap();
+        assert_eq!(merge(&mut simulation, &mut solar_system, ours, theirs), Err(SettlementError::DifferentOwners));
+
+        let far = Settlement::new("C".to_string(), Location::Orbit { body_id: other_body, orbital_slot_id: Uuid::new_v4() }).with_owner(faction);
+        let far = simulation.add_structure(StructureType::Settlement(far));
+        assert_eq!(merge(&mut simulation, &mut solar_system, ours, far), Err(SettlementError::DifferentBodies));
+        assert_eq!(simulation.structures.len(), 3);
+    }
+}
@@ -2,10 +2,20 @@ use std::collections::HashMap;
 use serde::{Deserialize, Serialize};
 use crate::maps::location::Location;
 use crate::population::person_type::PersonType;
+use crate::production::production_queue::{BuildItem, ProductionQueue, Stockpile, Workshop};
+use crate::structures::installation::CREWED_LABOR;
+use crate::structures::movement::{Trajectory, PROPELLANT};
 use crate::resources::resource_type::ResourceType;
+use crate::resources::storage::StorageType;
 use crate::units::unit_type::UnitType;
 use uuid::Uuid;
 
+/// Share of its speed a hull loses for each module fitted
+pub const SPEED_LOSS_PER_MODULE: f64 = 0.05;
+
+/// Share of its speed a fully worn out ship has lost
+pub const SPEED_LOSS_WHEN_WORN: f64 = 0.5;
+
 #[derive(Debug,Clone,Serialize,Deserialize,Hash,PartialEq,Eq)]
 pub enum SpacecraftModuleType {
     Mine,
@@ -14,17 +24,223 @@ pub enum SpacecraftModuleType {
     Laboratory,
     Military,
     Administrative,
+    CargoHold,
+    LiquidTank,
+    GasTank,
+    PressureTank,
+    CryoTank,
+}
 
+impl SpacecraftModuleType {
+    /// The kind of storage the module adds, if it is a cargo module
+    pub fn storage(&self) -> Option<StorageType> {
+        match self {
+            SpacecraftModuleType::CargoHold => Some(StorageType::Bulk),
+            SpacecraftModuleType::LiquidTank => Some(StorageType::Liquid),
+            SpacecraftModuleType::GasTank => Some(StorageType::Gas),
+            SpacecraftModuleType::PressureTank => Some(StorageType::Pressurized),
+            SpacecraftModuleType::CryoTank => Some(StorageType::Cryogenic),
+            _ => None,
+        }
+    }
 }
 
 #[derive(Debug,Clone,Serialize,Deserialize)]
 pub struct Spacecraft {
     id: Uuid,
+    #[serde(default)]
+    name: String,
+    #[serde(default)]
+    owner: Option<Uuid>,
     location: Location,
     destination: Option<Location>,
+    /// The course being flown towards `destination`, once plotted
+    #[serde(default)]
+    trajectory: Option<Trajectory>,
     modules: HashMap<SpacecraftModuleType, u32>,
     cargo: HashMap<ResourceType, u32>,
     population: Option<HashMap<PersonType, u32>>,
     crew: Option<UnitType>,
-    fleed_it: Option<Uuid>,
-}
\ No newline at end of file
+    /// The fleet the ship sails with, if any
+    #[serde(alias = "fleed_it")]
+    fleet_id: Option<Uuid>,
+    /// Units and ships being made, in order
+    #[serde(default)]
+    production: ProductionQueue,
+    /// Units carried aboard
+    #[serde(default)]
+    garrison: HashMap<UnitType, u32>,
+    /// Wear on equipment from 0 (new) to 1 (worn out)
+    #[serde(default)]
+    wear: f32,
+}
+
+impl Spacecraft {
+    pub fn new(name: String, location: Location) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            name,
+            owner: None,
+            location,
+            destination: None,
+            trajectory: None,
+            modules: HashMap::new(),
+            cargo: HashMap::new(),
+            population: None,
+            crew: None,
+            fleet_id: None,
+            production: ProductionQueue::new(),
+            garrison: HashMap::new(),
+            wear: 0.0,
+        }
+    }
+
+    pub fn with_owner(mut self, faction_id: Uuid) -> Self {
+        self.owner = Some(faction_id);
+        self
+    }
+
+    pub fn with_modules(mut self, modules: HashMap<SpacecraftModuleType, u32>) -> Self {
+        self.modules = modules;
+        self
+    }
+
+    pub fn with_cargo(mut self, cargo: HashMap<ResourceType, u32>) -> Self {
+        self.cargo = cargo;
+        self
+    }
+
+    pub fn id(&self) -> Uuid {
+        self.id
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn owner(&self) -> Option<Uuid> {
+        self.owner
+    }
+
+    pub fn location(&self) -> &Location {
+        &self.location
+    }
+
+    pub(crate) fn set_location(&mut self, location: Location) {
+        self.location = location;
+    }
+
+    pub fn destination(&self) -> Option<&Location> {
+        self.destination.as_ref()
+    }
+
+    /// Sends the craft to `destination`, or holds it where it is with `None`.  The course
+    /// is plotted in the next movement phase; use `movement::set_course` to plot it now.
+    pub fn set_destination(&mut self, destination: Option<Location>) {
+        if destination != self.destination {
+            self.trajectory = None;
+        }
+        self.destination = destination;
+    }
+
+    pub fn trajectory(&self) -> Option<&Trajectory> {
+        self.trajectory.as_ref()
+    }
+
+    pub(crate) fn set_trajectory(&mut self, trajectory: Option<Trajectory>) {
+        self.trajectory = trajectory;
+    }
+
+    /// Propellant in the cargo
+    pub fn propellant(&self) -> u64 {
+        self.cargo.available(&PROPELLANT)
+    }
+
+    /// Burns up to `amount` propellant from the cargo, returning how much was burned
+    pub(crate) fn burn_propellant(&mut self, amount: u64) -> u64 {
+        self.cargo.take(&PROPELLANT, amount)
+    }
+
+    /// Cruising speed relative to a new bare hull; modules and wear slow a ship down
+    pub fn speed(&self) -> f64 {
+        let modules = self.modules.values().sum::<u32>() as f64;
+        (1.0 - SPEED_LOSS_WHEN_WORN * self.wear as f64) / (1.0 + SPEED_LOSS_PER_MODULE * modules)
+    }
+
+    pub fn fleet_id(&self) -> Option<Uuid> {
+        self.fleet_id
+    }
+
+    pub(crate) fn set_fleet(&mut self, fleet_id: Option<Uuid>) {
+        self.fleet_id = fleet_id;
+    }
+
+    /// The hull plus every module fitted to it; bigger ships burn more propellant
+    pub fn hull_size(&self) -> u32 {
+        1 + self.modules.values().sum::<u32>()
+    }
+
+    pub fn modules(&self) -> &HashMap<SpacecraftModuleType, u32> {
+        &self.modules
+    }
+
+    pub fn cargo(&self) -> &HashMap<ResourceType, u32> {
+        &self.cargo
+    }
+
+    pub fn population(&self) -> Option<&HashMap<PersonType, u32>> {
+        self.population.as_ref()
+    }
+
+    pub fn garrison(&self) -> &HashMap<UnitType, u32> {
+        &self.garrison
+    }
+
+    /// Takes `count` more units of `unit_type` aboard
+    pub fn add_units(&mut self, unit_type: UnitType, count: u32) {
+        if count > 0 {
+            *self.garrison.entry(unit_type).or_default() += count;
+        }
+    }
+
+    pub fn wear(&self) -> f32 {
+        self.wear
+    }
+
+    /// Wears equipment down by `amount`, up to fully worn out
+    pub fn add_wear(&mut self, amount: f32) {
+        self.wear = (self.wear + amount).clamp(0.0, 1.0);
+    }
+}
+
+impl Workshop for Spacecraft {
+    fn production(&self) -> &ProductionQueue {
+        &self.production
+    }
+
+    fn stockpile(&self) -> &dyn Stockpile {
+        &self.cargo
+    }
+
+    fn workshop_mut(&mut self) -> (&mut ProductionQueue, &mut dyn Stockpile) {
+        (&mut self.production, &mut self.cargo)
+    }
+
+    /// Passengers who are not children, plus the crew
+    fn labor(&self) -> u32 {
+        let passengers = self.population.as_ref().map_or(0, |population| {
+            population.iter().filter(|(person_type, _)| **person_type != PersonType::Child).map(|(_, &n)| n).sum()
+        });
+        passengers + if self.crew.is_some() { CREWED_LABOR } else { 0 }
+    }
+
+    /// Factory modules build ships and military modules train units
+    fn can_produce(&self, item: &BuildItem) -> bool {
+        let has = |module: SpacecraftModuleType| self.modules.get(&module).is_some_and(|&n| n > 0);
+        match item {
+            BuildItem::Building(_) => false,
+            BuildItem::Unit(_) => has(SpacecraftModuleType::Military),
+            BuildItem::Spacecraft { .. } => has(SpacecraftModuleType::Factory),
+        }
+    }
+}
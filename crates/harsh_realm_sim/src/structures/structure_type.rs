@@ -1,10 +1,117 @@
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::maps::location::Location;
+use crate::production::production_queue::{Stockpile, Workshop};
+
 use crate::structures::{
     installation::Installation, settlement::Settlement, spacecraft::Spacecraft,
 };
 
-#[allow(dead_code)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum StructureType {
     Settlement(Settlement),
     Installation(Installation),
     Spacecraft(Spacecraft),
 }
+
+/// Which kind of structure a [`StructureType`] wraps, without its contents
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum StructureKind {
+    Settlement,
+    Installation,
+    Spacecraft,
+}
+
+impl StructureType {
+    /// Gets the wrapped structure's production queue and facilities
+    pub fn workshop(&self) -> &dyn Workshop {
+        match self {
+            StructureType::Settlement(settlement) => settlement,
+            StructureType::Installation(installation) => installation,
+            StructureType::Spacecraft(spacecraft) => spacecraft,
+        }
+    }
+
+    /// Gets the wrapped structure's production queue and facilities
+    pub fn workshop_mut(&mut self) -> &mut dyn Workshop {
+        match self {
+            StructureType::Settlement(settlement) => settlement,
+            StructureType::Installation(installation) => installation,
+            StructureType::Spacecraft(spacecraft) => spacecraft,
+        }
+    }
+
+    /// Gets the wrapped structure's stockpile, or a ship's cargo
+    pub fn stockpile(&self) -> &dyn Stockpile {
+        self.workshop().stockpile()
+    }
+
+    /// Gets the wrapped structure's stockpile, or a ship's cargo
+    pub fn stockpile_mut(&mut self) -> &mut dyn Stockpile {
+        self.workshop_mut().stockpile_mut()
+    }
+
+    /// Gets the kind of the wrapped structure
+    pub fn kind(&self) -> StructureKind {
+        match self {
+            StructureType::Settlement(_) => StructureKind::Settlement,
+            StructureType::Installation(_) => StructureKind::Installation,
+            StructureType::Spacecraft(_) => StructureKind::Spacecraft,
+        }
+    }
+
+    /// Gets the id of the wrapped structure
+    pub fn id(&self) -> Uuid {
+        match self {
+            StructureType::Settlement(settlement) => settlement.id(),
+            StructureType::Installation(installation) => installation.id(),
+            StructureType::Spacecraft(spacecraft) => spacecraft.id(),
+        }
+    }
+
+    /// Gets the name of the wrapped structure
+    pub fn name(&self) -> &str {
+        match self {
+            StructureType::Settlement(settlement) => settlement.name(),
+            StructureType::Installation(installation) => installation.name(),
+            StructureType::Spacecraft(spacecraft) => spacecraft.name(),
+        }
+    }
+
+    /// Gets the id of the faction owning the wrapped structure
+    pub fn owner(&self) -> Option<Uuid> {
+        match self {
+            StructureType::Settlement(settlement) => settlement.owner(),
+            StructureType::Installation(installation) => installation.owner(),
+            StructureType::Spacecraft(spacecraft) => spacecraft.owner(),
+        }
+    }
+
+    /// Gets the location of the wrapped structure
+    pub fn location(&self) -> &Location {
+        match self {
+            StructureType::Settlement(settlement) => settlement.location(),
+            StructureType::Installation(installation) => installation.location(),
+            StructureType::Spacecraft(spacecraft) => spacecraft.location(),
+        }
+    }
+
+    /// Gets the wear on the wrapped structure's equipment, from 0 (new) to 1 (worn out)
+    pub fn wear(&self) -> f32 {
+        match self {
+            StructureType::Settlement(settlement) => settlement.wear(),
+            StructureType::Installation(installation) => installation.wear(),
+            StructureType::Spacecraft(spacecraft) => spacecraft.wear(),
+        }
+    }
+
+    /// Wears the wrapped structure's equipment down by `amount`
+    pub fn add_wear(&mut self, amount: f32) {
+        match self {
+            StructureType::Settlement(settlement) => settlement.add_wear(amount),
+            StructureType::Installation(installation) => installation.add_wear(amount),
+            StructureType::Spacecraft(spacecraft) => spacecraft.add_wear(amount),
+        }
+    }
+}
@@ -0,0 +1,19 @@
+// src/structures/test_support.rs
+// Fixtures shared by the structure modules' unit tests: a bare world with a
+// single airless moon to settle and sail to.
+
+use chrono::NaiveDate;
+use uuid::Uuid;
+
+use crate::simulation::simulation::Simulation;
+use crate::universe::celestial_body::{CelestialBody, CelestialBodyType};
+use crate::universe::solar_system_manager::SolarSystemManager;
+
+/// An empty simulation and a solar system holding one Moon-sized body, whose id is returned too
+pub(crate) fn world() -> (Simulation, SolarSystemManager, Uuid) {
+    let mut solar_system = SolarSystemManager::new(NaiveDate::from_ymd_opt(2100, 1, 1).unwrap());
+    let body = CelestialBody::new("Rock".to_string(), CelestialBodyType::Moon, "Test".to_string(), 7.3e22, 3474.0);
+    let body_id = body.id;
+    solar_system.add_body(body);
+    (Simulation::new(), solar_system, body_id)
+}
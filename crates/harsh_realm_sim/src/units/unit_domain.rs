@@ -1,3 +1,7 @@
+use serde::{Deserialize, Serialize};
+
+/// Where a unit operates, which decides the terrain it can move through
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum UnitDomain {
     SurfaceGround,
     SurfaceAerospace,
@@ -1,11 +1,10 @@
-use std::fmt::{Debug, Formatter};
 use serde::{Deserialize, Serialize};
 
-#[derive(Clone, Serialize, Deserialize)]
-pub enum UnitType {}
-
-impl Debug for UnitType {
-    fn fmt(&self, _f: &mut Formatter<'_>) -> std::fmt::Result {
-        todo!()
-    }
-}
\ No newline at end of file
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum UnitType {
+    Infantry,
+    Engineers,
+    Marines,
+    /// Trained hands to run a ship or installation
+    Crew,
+}
@@ -1,8 +1,24 @@
+use std::sync::Arc;
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
+use crate::maps::sphere_grid::SphereGrid;
+use crate::maps::surface_map::SurfaceMap;
+use crate::resources::deposit::Deposit;
 use crate::universe::orbital_system::OrbitalState;
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+/// Bodies at least this wide are gas or ice giants without a solid surface
+pub const GIANT_PLANET_MIN_DIAMETER_KM: f64 = 40_000.0;
+
+/// Bodies narrower than this are too small to hold an atmosphere and are mined as a whole
+pub const SMALL_BODY_MAX_DIAMETER_KM: f64 = 1000.0;
+
+/// Rotation periods at least this long, in days, are taken to be errors in the data
+const MAX_ROTATION_PERIOD_DAYS: f64 = 1000.0;
+
+/// Newtonian constant of gravitation, m³ kg⁻¹ s⁻²
+const GRAVITATIONAL_CONSTANT: f64 = 6.674e-11;
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub enum CelestialBodyType {
     Star,
     Planet,
@@ -12,6 +28,31 @@ pub enum CelestialBodyType {
     DwarfPlanet,
 }
 
+/// Bulk make-up of a body, inferred from its density
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Composition {
+    /// Iron-nickel, like 16 Psyche
+    Metallic,
+    /// Silicate rock, like 4 Vesta
+    Stony,
+    /// Rock with water and organics, like 1 Ceres
+    Carbonaceous,
+    /// Mostly water ice, like the outer moons
+    Icy,
+}
+
+impl Composition {
+    /// Classifies a bulk density in g/cm³
+    pub fn from_density(density: f64) -> Self {
+        match density {
+            d if d >= 3.5 => Composition::Metallic,
+            d if d >= 2.6 => Composition::Stony,
+            d if d >= 1.9 => Composition::Carbonaceous,
+            _ => Composition::Icy,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct CelestialBody {
     pub name: String,
@@ -21,6 +62,19 @@ pub struct CelestialBody {
     pub orbital_state: Option<OrbitalState>,
     pub mass: f64,  // Mass in kg
     pub diameter: f64,  // Diameter in km
+    /// Sidereal rotation period in days from the RP column; negative for retrograde spin
+    #[serde(default)]
+    pub rotation_period: Option<f64>,
+    /// Surface grid sized by the TH column of the data; derived from the diameter when absent
+    #[serde(default)]
+    pub hex_grid: Option<SphereGrid>,
+    /// Resources mined from the body as a whole; only small bodies have these
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub deposits: Vec<Deposit>,
+    /// Created on first use, since most bodies are never visited.  Shared with turn
+    /// snapshots until either side changes it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    surface_map: Option<Arc<SurfaceMap>>,
 }
 
 impl CelestialBody {
@@ -33,6 +87,10 @@ impl CelestialBody {
             orbital_state: None,
             mass,
             diameter,
+            rotation_period: None,
+            hex_grid: None,
+            deposits: Vec::new(),
+            surface_map: None,
         }
     }
 
@@ -41,6 +99,101 @@ impl CelestialBody {
         self
     }
 
+    pub fn with_hex_grid(mut self, hex_grid: SphereGrid) -> Self {
+        self.hex_grid = Some(hex_grid);
+        self
+    }
+
+    pub fn with_rotation_period(mut self, rotation_period: f64) -> Self {
+        self.rotation_period = Some(rotation_period);
+        self
+    }
+
+    /// Gets the body's surface grid
+    pub fn surface_grid(&self) -> SphereGrid {
+        self.hex_grid.unwrap_or_else(|| SphereGrid::from_diameter(self.diameter))
+    }
+
+    /// Gets the surface map if it has been created
+    pub fn surface_map(&self) -> Option<&SurfaceMap> {
+        self.surface_map.as_deref()
+    }
+
+    /// Gets the surface map if it has been created, copying it first if a snapshot shares it
+    pub fn surface_map_mut(&mut self) -> Option<&mut SurfaceMap> {
+        self.surface_map.as_mut().map(Arc::make_mut)
+    }
+
+    /// Replaces the surface map; see `SolarSystemManager::surface_map_mut` for lazy generation
+    pub fn set_surface_map(&mut self, surface_map: SurfaceMap) {
+        self.surface_map = Some(Arc::new(surface_map));
+    }
+
+    /// Returns true for gas and ice giants, which have no solid surface
+    pub fn is_giant(&self) -> bool {
+        self.diameter >= GIANT_PLANET_MIN_DIAMETER_KM
+    }
+
+    /// Name of the body this one orbits, taken from regions such as "Jupiter Orbit"
+    pub fn parent_name(&self) -> Option<&str> {
+        self.region.strip_suffix(" Orbit")
+    }
+
+    /// Time the body takes to turn once, in days; moons without data are taken to be tidally locked
+    pub fn day_length(&self) -> Option<f64> {
+        let rotation = self
+            .rotation_period
+            .map(f64::abs)
+            .filter(|days| *days > 0.0 && *days < MAX_ROTATION_PERIOD_DAYS);
+        rotation.or_else(|| {
+            self.parent_name()?;
+            self.orbital_state.as_ref().map(|state| state.parameters.orbital_period)
+        })
+    }
+
+    /// Returns true for bodies small enough to be mined as a whole, like asteroids
+    pub fn is_small_body(&self) -> bool {
+        self.diameter > 0.0 && self.diameter < SMALL_BODY_MAX_DIAMETER_KM
+    }
+
+    /// Bulk density in g/cm³
+    pub fn density(&self) -> f64 {
+        let radius_cm = self.diameter * 50_000.0;
+        if radius_cm <= 0.0 {
+            return 0.0;
+        }
+        let volume = 4.0 / 3.0 * std::f64::consts::PI * radius_cm.powi(3);
+        self.mass * 1000.0 / volume
+    }
+
+    pub fn composition(&self) -> Composition {
+        Composition::from_density(self.density())
+    }
+
+    /// Surface gravity in m/s²
+    pub fn surface_gravity(&self) -> f64 {
+        let radius_m = self.diameter * 500.0;
+        if radius_m <= 0.0 {
+            return 0.0;
+        }
+        GRAVITATIONAL_CONSTANT * self.mass / (radius_m * radius_m)
+    }
+
+    /// Escape velocity at the surface in km/s
+    pub fn escape_velocity(&self) -> f64 {
+        (2.0 * self.surface_gravity() * self.diameter * 500.0).sqrt() / 1000.0
+    }
+
+    /// Speed of a low circular orbit skimming the surface, in km/s
+    pub fn orbital_velocity(&self) -> f64 {
+        (self.surface_gravity() * self.diameter * 500.0).sqrt() / 1000.0
+    }
+
+    /// Standard gravitational parameter GM in km³/s²
+    pub fn gravitational_parameter(&self) -> f64 {
+        GRAVITATIONAL_CONSTANT * self.mass * 1e-9
+    }
+
     #[allow(dead_code)]
     pub fn update_orbital_position(&mut self, days_elapsed: f64) {
         if let Some(ref mut orbital_state) = self.orbital_state {
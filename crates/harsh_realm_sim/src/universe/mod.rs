@@ -1,6 +1,7 @@
 pub mod celestial_body;
+pub mod navigation;
 pub mod orbital_mechanics;
 pub mod orbital_system;
 pub mod solar_system;
 pub mod solar_system_manager;
-mod space_region;
\ No newline at end of file
+mod space_region;
@@ -0,0 +1,238 @@
+// src/universe/navigation.rs
+// Where things are and what it takes to get between them.  Any location,
+// once docking has been followed to the structure it ends at, is a point in
+// the heliocentric plane on a given date.  Travel is estimated as a climb out
+// of each endpoint's gravity wells to the nearest body both share (or the
+// Sun), a Hohmann transfer around it, and a climb back down.
+
+use std::fmt::{Display, Formatter};
+use chrono::NaiveDate;
+use hex2d::Coordinate as HexCoord;
+use uuid::Uuid;
+
+use crate::maps::location::Location;
+use crate::simulation::simulation::Simulation;
+use crate::universe::celestial_body::CelestialBody;
+use crate::universe::orbital_mechanics::{self, SUN_GRAVITATIONAL_PARAMETER};
+use crate::universe::orbital_system::CartesianPosition;
+use crate::universe::solar_system_manager::SolarSystemManager;
+
+/// Time spent on each ascent to or descent from a surface
+pub const SURFACE_LEG_DAYS: f64 = 0.02;
+
+const SECONDS_PER_DAY: f64 = 86_400.0;
+
+/// Errors produced when a location cannot be placed
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum NavigationError {
+    /// The location names a body that is not in the solar system
+    UnknownBody(Uuid),
+    /// The location is a hex that does not exist on its body's surface
+    OffSurface { body_id: Uuid, hex_coord: HexCoord },
+    /// The location is docked to a structure that does not exist
+    UnknownStructure(Uuid),
+    /// Following docked structures leads back to this one
+    DockingLoop(Uuid),
+}
+
+impl Display for NavigationError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            NavigationError::UnknownBody(id) => write!(f, "no celestial body with id {}", id),
+            NavigationError::OffSurface { body_id, hex_coord } => {
+                write!(f, "hex ({}, {}) is not on the surface of body {}", hex_coord.x, hex_coord.y, body_id)
+            }
+            NavigationError::UnknownStructure(id) => write!(f, "docked to missing structure {}", id),
+            NavigationError::DockingLoop(id) => write!(f, "structure {} is docked in a loop", id),
+        }
+    }
+}
+
+impl std::error::Error for NavigationError {}
+
+/// What a trip between two locations would take
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TravelEstimate {
+    /// Straight-line distance on the departure date, or over the ground between two hexes of one body
+    pub distance_km: f64,
+    /// Total of every burn, in km/s
+    pub delta_v: f64,
+    pub travel_days: f64,
+}
+
+/// A resolved location: on or around a body, or a point in deep space
+enum Anchor<'a> {
+    Body { body: &'a CelestialBody, hex: Option<HexCoord> },
+    DeepSpace(CartesianPosition),
+}
+
+/// Follows a chain of docked structures to the location they all share
+pub fn resolve<'a>(simulation: &'a Simulation, location: &'a Location) -> Result<&'a Location, NavigationError> {
+    let mut current = location;
+    for _ in 0..=simulation.structures.len() {
+        let Location::Docked { structure_id } = current else {
+            return Ok(current);
+        };
+        current = simulation
+            .structures
+            .get(structure_id)
+            .ok_or(NavigationError::UnknownStructure(*structure_id))?
+            .location();
+    }
+    match current {
+        Location::Docked { structure_id } => Err(NavigationError::DockingLoop(*structure_id)),
+        resolved => Ok(resolved),
+    }
+}
+
+fn anchor<'a>(
+    solar_system: &'a SolarSystemManager,
+    simulation: &'a Simulation,
+    location: &'a Location,
+) -> Result<Anchor<'a>, NavigationError> {
+    let body = |body_id: &Uuid| solar_system.get_body_by_id(*body_id).ok_or(NavigationError::UnknownBody(*body_id));
+    match resolve(simulation, location)? {
+        Location::Surface { body_id, hex_coord } => {
+            let body = body(body_id)?;
+            if !body.surface_grid().contains(*hex_coord) {
+                return Err(NavigationError::OffSurface { body_id: *body_id, hex_coord: *hex_coord });
+            }
+            Ok(Anchor::Body { body, hex: Some(*hex_coord) })
+        }
+        Location::Orbit { body_id, .. } => Ok(Anchor::Body { body: body(body_id)?, hex: None }),
+        Location::DeepSpace { x, y } => Ok(Anchor::DeepSpace(CartesianPosition { x: *x as f64, y: *y as f64 })),
+        Location::Docked { structure_id } => Err(NavigationError::DockingLoop(*structure_id)),
+    }
+}
+
+fn anchor_position(solar_system: &SolarSystemManager, anchor: &Anchor, date: NaiveDate) -> CartesianPosition {
+    match anchor {
+        Anchor::Body { body, .. } => solar_system.body_position(body, date),
+        Anchor::DeepSpace(position) => position.clone(),
+    }
+}
+
+/// Heliocentric position of `location` on `date`, in km; deep space coordinates are taken as km
+pub fn position(
+    solar_system: &SolarSystemManager,
+    simulation: &Simulation,
+    location: &Location,
+    date: NaiveDate,
+) -> Result<CartesianPosition, NavigationError> {
+    Ok(anchor_position(solar_system, &anchor(solar_system, simulation, location)?, date))
+}
+
+/// Straight-line distance between two locations on `date`, in km
+pub fn distance_km(
+    solar_system: &SolarSystemManager,
+    simulation: &Simulation,
+    from: &Location,
+    to: &Location,
+    date: NaiveDate,
+) -> Result<f64, NavigationError> {
+    let from = position(solar_system, simulation, from, date)?;
+    let to = position(solar_system, simulation, to, date)?;
+    Ok((from.x - to.x).hypot(from.y - to.y))
+}
+
+/// Estimates the distance, delta-v and time to travel from one location to another leaving on `date`
+pub fn estimate(
+    solar_system: &SolarSystemManager,
+    simulation: &Simulation,
+    from: &Location,
+    to: &Location,
+    date: NaiveDate,
+) -> Result<TravelEstimate, NavigationError> {
+    let departure = anchor(solar_system, simulation, from)?;
+    let arrival = anchor(solar_system, simulation, to)?;
+
+    if let (Anchor::Body { body, hex: from_hex }, Anchor::Body { body: other, hex: to_hex }) = (&departure, &arrival) {
+        if body.id == other.id {
+            return Ok(local_trip(body, *from_hex, *to_hex));
+        }
+    }
+
+    let from_position = anchor_position(solar_system, &departure, date);
+    let to_position = anchor_position(solar_system, &arrival, date);
+    let distance_km = (from_position.x - to_position.x).hypot(from_position.y - to_position.y);
+
+    // Climb out to the nearest body both ends orbit, transfer around it, climb back in
+    let from_chain = ancestry(solar_system, &departure);
+    let to_chain = ancestry(solar_system, &arrival);
+    let common = from_chain.iter().find(|body| to_chain.iter().any(|other| other.id == body.id)).copied();
+    let (climb_out, from_radius, out_days) = climb(&departure, &from_chain, common);
+    let (climb_in, to_radius, in_days) = climb(&arrival, &to_chain, common);
+    let mu = common.map_or(SUN_GRAVITATIONAL_PARAMETER, |body| body.gravitational_parameter());
+    let transfer = orbital_mechanics::hohmann(mu, from_radius, to_radius);
+
+    Ok(TravelEstimate {
+        distance_km,
+        delta_v: climb_out + transfer.delta_v + climb_in,
+        travel_days: out_days + transfer.days + in_days,
+    })
+}
+
+/// A trip that never leaves one body: between hexes, or between the surface and orbit
+fn local_trip(body: &CelestialBody, from: Option<HexCoord>, to: Option<HexCoord>) -> TravelEstimate {
+    let orbital_velocity = body.orbital_velocity();
+    let ascent = orbital_mechanics::ascent_delta_v(orbital_velocity);
+    match (from, to) {
+        (Some(from), Some(to)) if from != to => {
+            // Both hexes were checked when anchored.  A ballistic hop costs about sqrt(g·d) each way, never more than reaching orbit
+            let radius_km = body.diameter / 2.0;
+            let distance_km = body.surface_grid().distance_km(from, to, radius_km).unwrap_or(0.0);
+            let share = if radius_km > 0.0 { (distance_km / radius_km).sqrt().min(1.0) } else { 0.0 };
+            let coast_days = if orbital_velocity > 0.0 { distance_km / orbital_velocity / SECONDS_PER_DAY } else { 0.0 };
+            TravelEstimate {
+                distance_km,
+                delta_v: 2.0 * ascent * share,
+                travel_days: 2.0 * SURFACE_LEG_DAYS + coast_days,
+            }
+        }
+        (Some(_), None) | (None, Some(_)) => {
+            TravelEstimate { distance_km: 0.0, delta_v: ascent, travel_days: SURFACE_LEG_DAYS }
+        }
+        _ => TravelEstimate { distance_km: 0.0, delta_v: 0.0, travel_days: 0.0 },
+    }
+}
+
+/// The body an anchor sits at followed by each body it orbits in turn, ending before the Sun
+fn ancestry<'a>(solar_system: &'a SolarSystemManager, anchor: &Anchor<'a>) -> Vec<&'a CelestialBody> {
+    let Anchor::Body { body, .. } = anchor else {
+        return Vec::new();
+    };
+    let mut chain = vec![*body];
+    while let Some(parent) = solar_system.parent_of(chain[chain.len() - 1]) {
+        if chain.iter().any(|body| body.id == parent.id) {
+            break;
+        }
+        chain.push(parent);
+    }
+    chain
+}
+
+/// Delta-v and days to get from an anchor into orbit around `common` (the Sun if `None`),
+/// with the radius of the orbit reached
+fn climb(anchor: &Anchor, chain: &[&CelestialBody], common: Option<&CelestialBody>) -> (f64, f64, f64) {
+    let (mut delta_v, mut days) = (0.0, 0.0);
+    let radius = match anchor {
+        Anchor::DeepSpace(position) => position.x.hypot(position.y),
+        Anchor::Body { body, hex } => {
+            if hex.is_some() {
+                delta_v += orbital_mechanics::ascent_delta_v(body.orbital_velocity());
+                days += SURFACE_LEG_DAYS;
+            }
+            if common.is_some_and(|common| common.id == body.id) {
+                // Already around the shared body, in a low parking orbit
+                return (delta_v, body.diameter / 2.0, days);
+            }
+            let mut radius = 0.0;
+            for body in chain.iter().take_while(|body| common.is_none_or(|common| common.id != body.id)) {
+                delta_v += orbital_mechanics::escape_delta_v(body.orbital_velocity(), body.escape_velocity());
+                radius = body.orbital_state.as_ref().map_or(0.0, |state| state.parameters.semi_major_axis);
+            }
+            radius
+        }
+    };
+    (delta_v, radius, days)
+}
@@ -0,0 +1,73 @@
+// src/universe/orbital_mechanics.rs
+// Back-of-the-envelope orbital mechanics for travel planning.  Orbits are
+// treated as circular and coplanar, and burns as instantaneous, which is
+// close enough to rank routes and budget propellant.
+
+use std::f64::consts::PI;
+
+/// Gravitational parameter of the Sun in km³/s²
+pub const SUN_GRAVITATIONAL_PARAMETER: f64 = 1.327_124_400_18e11;
+
+/// Gravity and drag losses on top of orbital speed when climbing off a surface
+pub const ASCENT_LOSS_FACTOR: f64 = 1.15;
+
+const SECONDS_PER_DAY: f64 = 86_400.0;
+
+/// A single transfer orbit between two circular orbits
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Transfer {
+    /// Total of the departure and arrival burns, in km/s
+    pub delta_v: f64,
+    /// Coast time from departure to arrival
+    pub days: f64,
+}
+
+/// Speed of a circular orbit of `radius_km` around a body with parameter `mu`, in km/s
+pub fn circular_velocity(mu: f64, radius_km: f64) -> f64 {
+    if radius_km <= 0.0 {
+        return 0.0;
+    }
+    (mu / radius_km).sqrt()
+}
+
+/// Hohmann transfer between circular orbits of radius `from_km` and `to_km`
+pub fn hohmann(mu: f64, from_km: f64, to_km: f64) -> Transfer {
+    if from_km <= 0.0 || to_km <= 0.0 || mu <= 0.0 {
+        return Transfer { delta_v: 0.0, days: 0.0 };
+    }
+    let semi_major_axis = (from_km + to_km) / 2.0;
+    let departure = circular_velocity(mu, from_km) * ((to_km / semi_major_axis).sqrt() - 1.0);
+    let arrival = circular_velocity(mu, to_km) * (1.0 - (from_km / semi_major_axis).sqrt());
+    let seconds = PI * (semi_major_axis.powi(3) / mu).sqrt();
+    Transfer { delta_v: departure.abs() + arrival.abs(), days: seconds / SECONDS_PER_DAY }
+}
+
+/// Delta-v to climb from a surface into low orbit, in km/s
+pub fn ascent_delta_v(orbital_velocity: f64) -> f64 {
+    orbital_velocity * ASCENT_LOSS_FACTOR
+}
+
+/// Delta-v to leave low orbit for the space around a body, in km/s
+pub fn escape_delta_v(orbital_velocity: f64, escape_velocity: f64) -> f64 {
+    (escape_velocity - orbital_velocity).max(0.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EARTH_ORBIT_KM: f64 = 149_598_023.0;
+    const MARS_ORBIT_KM: f64 = 227_939_200.0;
+
+    #[test]
+    fn test_earth_to_mars_hohmann() {
+        let transfer = hohmann(SUN_GRAVITATIONAL_PARAMETER, EARTH_ORBIT_KM, MARS_ORBIT_KM);
+        // About 5.6 km/s heliocentric and 259 days
+        assert!((transfer.delta_v - 5.59).abs() < 0.05, "{}", transfer.delta_v);
+        assert!((transfer.days - 259.0).abs() < 2.0, "{}", transfer.days);
+
+        let back = hohmann(SUN_GRAVITATIONAL_PARAMETER, MARS_ORBIT_KM, EARTH_ORBIT_KM);
+        assert!((back.delta_v - transfer.delta_v).abs() < 1e-9);
+        assert_eq!(hohmann(SUN_GRAVITATIONAL_PARAMETER, EARTH_ORBIT_KM, EARTH_ORBIT_KM).delta_v, 0.0);
+    }
+}
@@ -85,7 +85,7 @@ impl OrbitalState {
         };
         
         // Update date
-        self.current_date = self.current_date + chrono::Duration::days(days_elapsed as i64);
+        self.current_date += chrono::Duration::days(days_elapsed as i64);
     }
     
     /// Converts mean anomaly to true anomaly using Kepler's equation
@@ -124,6 +124,17 @@ impl OrbitalState {
         }
     }
     
+    /// Position this orbit will have (or had) on `date`, leaving the state untouched
+    pub fn position_at(&self, date: NaiveDate) -> CartesianPosition {
+        let days = (date - self.current_date).num_days();
+        if days == 0 {
+            return self.to_cartesian();
+        }
+        let mut state = self.clone();
+        state.update_position(days as f64);
+        state.to_cartesian()
+    }
+
     /// Checks if the position change is significant enough to warrant a screen update
     pub fn is_significant_change(&self, previous_angle: f64, threshold_degrees: f64) -> bool {
         let angle_diff = (self.current_position.angle - previous_angle).abs();
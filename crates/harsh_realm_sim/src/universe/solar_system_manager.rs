@@ -5,9 +5,16 @@ use log::{info, warn};
 use std::fs::File;
 use std::io::BufReader;
 use std::path::Path;
-
+use uuid::Uuid;
+
+use crate::maps::location::Location;
+use crate::maps::sphere_grid::SphereGrid;
+use crate::procedural_generation::deposit_generator;
+use crate::procedural_generation::terrain_generator::{self, TerrainProfile};
+use crate::maps::surface_map::SurfaceMap;
+use crate::maps::tile::Tile;
 use crate::universe::celestial_body::{CelestialBody, CelestialBodyType};
-use crate::universe::orbital_system::{OrbitalState, OrbitalParameters};
+use crate::universe::orbital_system::{CartesianPosition, OrbitalState, OrbitalParameters};
 
 /// Represents a row from the solar system CSV data
 #[derive(Debug, Deserialize)]
@@ -24,6 +31,10 @@ struct SolarSystemDataRow {
     #[serde(rename = "D")]
     diameter: Option<f64>,
     region: Option<String>,
+    #[serde(rename = "RP", default, deserialize_with = "deserialize_optional_f64")]
+    rotation_period: Option<f64>,
+    #[serde(rename = "TH", default, deserialize_with = "deserialize_optional_f64")]
+    total_hexes: Option<f64>,
 }
 
 /// Custom deserializer for f64 that handles non-numeric values
@@ -92,6 +103,12 @@ impl SolarSystemManager {
                 continue;
             }
 
+            // An eccentricity outside [0, 1) is not a closed orbit, and most likely a shifted column
+            let eccentricity = row.eccentricity.unwrap();
+            if !(0.0..1.0).contains(&eccentricity) {
+                return Err(format!("{} has eccentricity {}, which does not describe a closed orbit", row.body, eccentricity).into());
+            }
+
             // Use mean anomaly if available, otherwise default to 0
             let mean_anomaly = row.mean_anomaly.unwrap_or(0.0);
 
@@ -101,7 +118,7 @@ impl SolarSystemManager {
             // Create orbital parameters
             let orbital_params = OrbitalParameters {
                 semi_major_axis: row.semi_major_axis.unwrap(),
-                eccentricity: row.eccentricity.unwrap(),
+                eccentricity,
                 orbital_period: row.orbital_period.unwrap(),
                 mean_anomaly,
             };
@@ -115,8 +132,15 @@ impl SolarSystemManager {
 
             let region = row.region.clone().unwrap_or_default();
 
-            let celestial_body = CelestialBody::new(row.body.clone(), body_type, region, mass, diameter)
+            let mut celestial_body = CelestialBody::new(row.body.clone(), body_type, region, mass, diameter)
                 .with_orbital_state(orbital_state);
+            if let Some(rotation_period) = row.rotation_period {
+                celestial_body = celestial_body.with_rotation_period(rotation_period);
+            }
+            if let Some(total_hexes) = row.total_hexes.filter(|hexes| *hexes >= 1.0) {
+                celestial_body = celestial_body.with_hex_grid(SphereGrid::from_tile_count(total_hexes as u32));
+            }
+            celestial_body.deposits = deposit_generator::bulk_deposits(&celestial_body);
 
             // Store previous position for change detection
             if let Some(ref orbital_state) = celestial_body.orbital_state {
@@ -159,8 +183,14 @@ impl SolarSystemManager {
         }
     }
 
-    /// Updates all celestial body positions for a given time step
+    /// Updates all celestial body positions for a given time step and advances the game date
     pub fn update_all_positions(&mut self, days_elapsed: f64) {
+        self.update_positions(days_elapsed);
+        self.advance_date(days_elapsed as i64);
+    }
+
+    /// Moves every celestial body along its orbit without changing the game date
+    pub fn update_positions(&mut self, days_elapsed: f64) {
         info!("Updating positions of all celestial bodies for {} days", days_elapsed);
 
         let mut significant_changes = Vec::new();
@@ -195,9 +225,36 @@ impl SolarSystemManager {
                       body_name, date, degrees, distance);
             }
         }
+    }
 
-        // Update game date
-        self.game_date = self.game_date + chrono::Duration::days(days_elapsed as i64);
+    /// Pairs of planets within `max_separation_degrees` of each other as seen
+    /// from the Sun, with their separation; each pair is ordered by name
+    pub fn planetary_conjunctions(&self, max_separation_degrees: f64) -> Vec<(String, String, f64)> {
+        let mut planets: Vec<(&str, f64)> = self
+            .celestial_bodies
+            .values()
+            // Untyped moons fall back to Planet, but their region names the body they orbit
+            .filter(|body| body.body_type == CelestialBodyType::Planet && !body.region.ends_with("Orbit"))
+            .filter_map(|body| body.orbital_state.as_ref().map(|state| (body.name.as_str(), state.angle_degrees())))
+            .collect();
+        planets.sort_by(|a, b| a.0.cmp(b.0));
+
+        let mut conjunctions = Vec::new();
+        for (i, (first, first_angle)) in planets.iter().enumerate() {
+            for (second, second_angle) in &planets[i + 1..] {
+                let separation = (first_angle - second_angle).rem_euclid(360.0);
+                let separation = separation.min(360.0 - separation);
+                if separation <= max_separation_degrees {
+                    conjunctions.push((first.to_string(), second.to_string(), separation));
+                }
+            }
+        }
+        conjunctions
+    }
+
+    /// Advances the game date by whole days
+    pub fn advance_date(&mut self, days: i64) {
+        self.game_date += chrono::Duration::days(days);
     }
 
     /// Gets all celestial bodies
@@ -210,6 +267,85 @@ impl SolarSystemManager {
         self.celestial_bodies.get(name)
     }
 
+    /// Gets a celestial body by id
+    pub fn get_body_by_id(&self, body_id: Uuid) -> Option<&CelestialBody> {
+        self.celestial_bodies.values().find(|body| body.id == body_id)
+    }
+
+    /// Gets a celestial body by id for modification
+    pub fn get_body_by_id_mut(&mut self, body_id: Uuid) -> Option<&mut CelestialBody> {
+        self.celestial_bodies.values_mut().find(|body| body.id == body_id)
+    }
+
+    /// Gets the body a moon orbits
+    pub fn parent_of(&self, body: &CelestialBody) -> Option<&CelestialBody> {
+        body.parent_name().and_then(|name| self.get_body(name))
+    }
+
+    /// Mean distance of a body from the Sun in km; moons share their planet's
+    pub fn solar_distance(&self, body: &CelestialBody) -> Option<f64> {
+        let orbiting_sun = self.parent_of(body).unwrap_or(body);
+        orbiting_sun.orbital_state.as_ref().map(|state| state.parameters.semi_major_axis)
+    }
+
+    /// Heliocentric position of a body on `date`; moons are placed around their planet
+    pub fn body_position(&self, body: &CelestialBody, date: NaiveDate) -> CartesianPosition {
+        let own = body
+            .orbital_state
+            .as_ref()
+            .map_or(CartesianPosition { x: 0.0, y: 0.0 }, |state| state.position_at(date));
+        match self.parent_of(body).filter(|parent| parent.id != body.id) {
+            Some(parent) => {
+                let centre = self.body_position(parent, date);
+                CartesianPosition { x: centre.x + own.x, y: centre.y + own.y }
+            }
+            None => own,
+        }
+    }
+
+    /// Adds a body, replacing any existing body of the same name
+    pub fn add_body(&mut self, body: CelestialBody) {
+        self.celestial_bodies.insert(body.name.clone(), body);
+    }
+
+    /// Gets a body's surface map for reading, generating its terrain from `seed` on first use.
+    /// Unlike `surface_map_mut` this never copies a map shared with a turn snapshot.
+    pub fn surface_map(&mut self, body_id: Uuid, seed: u64) -> Option<&SurfaceMap> {
+        let name = self.get_body_by_id(body_id)?.name.clone();
+        let body = &self.celestial_bodies[&name];
+        if body.surface_map().is_none() {
+            let profile = TerrainProfile::for_body(body, self);
+            let surface_map = terrain_generator::generate(body, &profile, seed);
+            self.celestial_bodies.get_mut(&name)?.set_surface_map(surface_map);
+        }
+        self.celestial_bodies[&name].surface_map()
+    }
+
+    /// Gets a body's surface map for changing, generating its terrain from `seed` on first use
+    pub fn surface_map_mut(&mut self, body_id: Uuid, seed: u64) -> Option<&mut SurfaceMap> {
+        self.surface_map(body_id, seed)?;
+        self.get_body_by_id_mut(body_id)?.surface_map_mut()
+    }
+
+    /// Returns true if `location` is a hex that exists on its body's surface
+    pub fn is_valid_location(&self, location: &Location) -> bool {
+        match location {
+            Location::Surface { body_id, hex_coord } => self
+                .get_body_by_id(*body_id)
+                .is_some_and(|body| body.surface_grid().contains(*hex_coord)),
+            Location::Orbit { body_id, .. } => self.get_body_by_id(*body_id).is_some(),
+            Location::DeepSpace { .. } | Location::Docked { .. } => true,
+        }
+    }
+
+    /// Gets the tile a surface location points at, generating the body's map from `seed` if needed
+    pub fn tile_mut(&mut self, location: &Location, seed: u64) -> Option<&mut Tile> {
+        let Location::Surface { body_id, hex_coord } = location else {
+            return None;
+        };
+        self.surface_map_mut(*body_id, seed)?.tile_mut(*hex_coord)
+    }
+
     /// Gets the current game date
     pub fn get_game_date(&self) -> NaiveDate {
         self.game_date
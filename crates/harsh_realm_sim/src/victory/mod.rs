@@ -0,0 +1,2 @@
+pub mod victory_condition;
+pub mod victory_engine;
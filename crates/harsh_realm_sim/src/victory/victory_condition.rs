@@ -0,0 +1,18 @@
+use serde::{Deserialize, Serialize};
+
+/// A way for a faction to win the game, as configured by the scenario
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum VictoryCondition {
+    /// Hold at least `share` (0..1) of all wealth in the system
+    EconomicDominance { share: f64 },
+    /// Reach a total population of at least `population`
+    PopulationThreshold { population: u64 },
+    /// Hold most of the claimed territory on every named body
+    ControlBodies { bodies: Vec<String> },
+    /// Research the named technology
+    TechnologyMilestone { technology: String },
+    /// Be the last faction with any structures left
+    Elimination,
+    /// End the game after `turns` turns, ranking the factions by score
+    TurnLimit { turns: u64 },
+}
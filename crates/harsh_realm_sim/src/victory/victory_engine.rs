@@ -0,0 +1,194 @@
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use chrono::NaiveDate;
+use log::info;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::faction::faction_stats::FactionStats;
+use crate::faction::territory;
+use crate::game_state::GameState;
+use crate::victory::victory_condition::VictoryCondition;
+
+/// How far one faction is towards one victory condition
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ConditionProgress {
+    pub condition: VictoryCondition,
+    /// 0.0 (no progress) to 1.0 (met)
+    pub progress: f64,
+    pub met: bool,
+}
+
+/// A faction's position in the race for victory
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FactionStanding {
+    pub faction_id: Uuid,
+    pub name: String,
+    /// Mean progress over the competitive conditions, used for ranking
+    pub score: f64,
+    pub eliminated: bool,
+    pub progress: Vec<ConditionProgress>,
+}
+
+/// Final result of a game
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GameOutcome {
+    pub turn: u64,
+    pub date: NaiveDate,
+    /// The winning faction, if any faction is left to win
+    pub winner: Option<Uuid>,
+    /// The condition that ended the game
+    pub reason: VictoryCondition,
+    /// Every faction, best first
+    pub rankings: Vec<FactionStanding>,
+}
+
+/// Result of evaluating the victory conditions at the end of a turn
+#[derive(Debug, Clone, PartialEq)]
+pub struct VictoryReport {
+    /// Every faction, best first
+    pub standings: Vec<FactionStanding>,
+    pub outcome: Option<GameOutcome>,
+}
+
+/// Evaluates the game's victory conditions against the current state
+pub fn evaluate(state: &GameState) -> VictoryReport {
+    let conditions = &state.victory_conditions;
+    let turn = state.simulation.current_turn;
+    let stats = FactionStats::collect(&state.simulation);
+
+    let total_wealth: f64 = stats.values().map(FactionStats::wealth).sum();
+    let faction_count = stats.len();
+    let surviving = stats.values().filter(|s| s.structure_count() > 0).count();
+    let body_ids: HashMap<&str, Uuid> = state
+        .solar_system
+        .get_all_bodies()
+        .iter()
+        .map(|(name, body)| (name.as_str(), body.id))
+        .collect();
+
+    let mut standings: Vec<FactionStanding> = state
+        .simulation
+        .factions
+        .values()
+        .map(|faction| {
+            let faction_stats = &stats[&faction.id()];
+            let eliminated = faction_stats.structure_count() == 0;
+
+            let progress: Vec<ConditionProgress> = conditions
+                .iter()
+                .map(|condition| {
+                    let (progress, met) = match condition {
+                        // A share of nothing is held from the start
+                        VictoryCondition::EconomicDominance { share } if *share <= 0.0 => (1.0, true),
+                        VictoryCondition::EconomicDominance { share } => {
+                            let held = if total_wealth > 0.0 { faction_stats.wealth() / total_wealth } else { 0.0 };
+                            (held / share, held >= *share)
+                        }
+                        VictoryCondition::PopulationThreshold { population } => {
+                            let total = faction_stats.total_population();
+                            (total as f64 / (*population).max(1) as f64, total >= *population)
+                        }
+                        VictoryCondition::ControlBodies { bodies } => {
+                            let held = bodies
+                                .iter()
+                                .filter(|b| {
+                                    body_ids
+                                        .get(b.as_str())
+                                        .is_some_and(|&id| territory::holds_body(&state.solar_system, id, faction.id()))
+                                })
+                                .count();
+                            (held as f64 / bodies.len().max(1) as f64, !bodies.is_empty() && held == bodies.len())
+                        }
+                        VictoryCondition::TechnologyMilestone { technology } => {
+                            let has = faction.has_technology(technology);
+                            (if has { 1.0 } else { 0.0 }, has)
+                        }
+                        VictoryCondition::Elimination => {
+                            let rivals = faction_count.saturating_sub(1);
+                            let rivals_left = surviving.saturating_sub(if eliminated { 0 } else { 1 });
+                            let progress = if rivals == 0 { 0.0 } else { (rivals - rivals_left) as f64 / rivals as f64 };
+                            (progress, rivals > 0 && !eliminated && rivals_left == 0)
+                        }
+                        VictoryCondition::TurnLimit { turns } => {
+                            (turn as f64 / (*turns).max(1) as f64, turn >= *turns)
+                        }
+                    };
+                    ConditionProgress {
+                        condition: condition.clone(),
+                        progress: progress.clamp(0.0, 1.0),
+                        met: met && !eliminated,
+                    }
+                })
+                .collect();
+
+            let competitive: Vec<f64> = progress
+                .iter()
+                .filter(|p| !matches!(p.condition, VictoryCondition::TurnLimit { .. }))
+                .map(|p| p.progress)
+                .collect();
+            let score = if competitive.is_empty() {
+                0.0
+            } else {
+                competitive.iter().sum::<f64>() / competitive.len() as f64
+            };
+
+            FactionStanding {
+                faction_id: faction.id(),
+                name: faction.name().to_string(),
+                score,
+                eliminated,
+                progress,
+            }
+        })
+        .collect();
+
+    // Survivors first, then by score, with wealth and name as tie-breakers
+    standings.sort_by(|a, b| {
+        a.eliminated
+            .cmp(&b.eliminated)
+            .then(b.score.partial_cmp(&a.score).unwrap_or(Ordering::Equal))
+            .then(
+                stats[&b.faction_id]
+                    .wealth()
+                    .partial_cmp(&stats[&a.faction_id].wealth())
+                    .unwrap_or(Ordering::Equal),
+            )
+            .then(a.name.cmp(&b.name))
+    });
+
+    let outcome = decide_outcome(&standings).map(|(winner, reason)| GameOutcome {
+        turn,
+        date: state.get_game_date(),
+        winner,
+        reason,
+        rankings: standings.clone(),
+    });
+    if let Some(outcome) = &outcome {
+        info!("Game over on turn {}: {:?} (winner: {:?})", turn, outcome.reason, outcome.winner);
+    }
+
+    VictoryReport { standings, outcome }
+}
+
+/// Picks the winner and deciding condition from ranked standings, if the game is over
+fn decide_outcome(standings: &[FactionStanding]) -> Option<(Option<Uuid>, VictoryCondition)> {
+    // The best-ranked faction meeting a competitive condition wins outright
+    for standing in standings {
+        let met = standing
+            .progress
+            .iter()
+            .find(|p| p.met && !matches!(p.condition, VictoryCondition::TurnLimit { .. }));
+        if let Some(met) = met {
+            return Some((Some(standing.faction_id), met.condition.clone()));
+        }
+    }
+
+    // Otherwise a reached turn limit ends the game in favour of the leader
+    let turn_limit = standings
+        .iter()
+        .flat_map(|s| &s.progress)
+        .find(|p| matches!(p.condition, VictoryCondition::TurnLimit { .. }) && p.progress >= 1.0)?;
+    let leader = standings.first().filter(|s| !s.eliminated).map(|s| s.faction_id);
+    Some((leader, turn_limit.condition.clone()))
+}
@@ -0,0 +1,124 @@
+use std::collections::HashMap;
+use std::path::PathBuf;
+use harsh_realm_sim::events::event_bus::EventFilter;
+use harsh_realm_sim::events::game_event::GameEventKind;
+use harsh_realm_sim::faction::faction::Faction;
+use harsh_realm_sim::game_state::GameState;
+use harsh_realm_sim::maps::location::Location;
+use harsh_realm_sim::resources::deposit::DepositStatus;
+use harsh_realm_sim::resources::resource_type::ResourceType;
+use harsh_realm_sim::structures::installation::{Installation, InstallationPurpose};
+use harsh_realm_sim::structures::settlement::Settlement;
+use harsh_realm_sim::structures::spacecraft::Spacecraft;
+use harsh_realm_sim::structures::structure_type::StructureType;
+use harsh_realm_sim::units::unit_type::UnitType;
+use uuid::Uuid;
+
+fn game() -> (GameState, Uuid) {
+    let mut state = GameState::new();
+    let csv = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("../../data/solar_system_data.csv");
+    state.solar_system.load_from_csv(&csv).unwrap();
+    let miners = state.simulation.add_faction(Faction::new("Miners".to_string()));
+    (state, miners)
+}
+
+fn mine(state: &mut GameState, owner: Uuid, location: Location) -> Uuid {
+    let installation = Installation::new("Mine".to_string(), InstallationPurpose::Mine, location).with_owner(owner);
+    state.simulation.add_structure(StructureType::Installation(installation))
+}
+
+fn stock(state: &GameState, id: Uuid, resource: ResourceType) -> u64 {
+    match &state.simulation.structures[&id] {
+        StructureType::Installation(installation) => installation.resources().get(&resource).copied().unwrap_or(0),
+        _ => 0,
+    }
+}
+
+#[test]
+fn orbital_mines_draw_down_small_bodies() {
+    let (mut state, miners) = game();
+    let psyche = state.solar_system.get_body("16 Psyche").unwrap();
+    let (psyche_id, before) = (psyche.id, psyche.deposits.clone());
+    assert_eq!(before.iter().max_by_key(|d| d.quantity).unwrap().resource, ResourceType::Minerals);
+
+    let id = mine(&mut state, miners, Location::Orbit { body_id: psyche_id, orbital_slot_id: Uuid::new_v4() });
+    state.process_turn();
+
+    let minerals = stock(&state, id, ResourceType::Minerals);
+    assert!(minerals > 0);
+    let after = &state.solar_system.get_body("16 Psyche").unwrap().deposits;
+    let remaining = after.iter().find(|d| d.resource == ResourceType::Minerals).unwrap();
+    let original = before.iter().find(|d| d.resource == ResourceType::Minerals).unwrap();
+    assert_eq!(remaining.quantity + minerals, original.quantity);
+    assert_eq!(remaining.status(miners), DepositStatus::Surveyed);
+}
+
+#[test]
+fn surface_mines_exhaust_their_hex() {
+    let (mut state, miners) = game();
+    let mars = state.solar_system.get_body("Mars").unwrap().id;
+    let map = state.surface_map_mut(mars).unwrap();
+    let tile = map.tiles_mut().find(|tile| !tile.deposits.is_empty()).expect("Mars should have deposits");
+    // Leave little enough that a month of mining empties it
+    tile.deposits.truncate(1);
+    tile.deposits[0].quantity = 50;
+    let (coord, resource) = (tile.coord, tile.deposits[0].resource.clone());
+    assert_eq!(tile.deposits[0].status(miners), DepositStatus::Hidden);
+
+    let id = mine(&mut state, miners, Location::Surface { body_id: mars, hex_coord: coord });
+    state.process_turn();
+
+    assert_eq!(stock(&state, id, resource.clone()), 50);
+    let deposit = &state.surface_map_mut(mars).unwrap().tile(coord).unwrap().deposits[0];
+    assert!(deposit.is_depleted());
+    let theirs = EventFilter::all().for_faction(miners);
+    let events: Vec<_> = state.simulation.events.events(&theirs).map(|e| e.kind.clone()).collect();
+    assert!(events.contains(&GameEventKind::DepositDepleted { structure_id: id, resource }));
+}
+
+#[test]
+fn mines_without_energy_dig_less() {
+    let (mut state, miners) = game();
+    let psyche = state.solar_system.get_body("16 Psyche").unwrap().id;
+    let orbit = Location::Orbit { body_id: psyche, orbital_slot_id: Uuid::new_v4() };
+    let crewed = |resources| {
+        Installation::new("Mine".to_string(), InstallationPurpose::Mine, orbit.clone())
+            .with_owner(miners)
+            .with_crew(UnitType::Crew)
+            .with_resources(resources)
+    };
+    let powered = crewed(HashMap::from([(ResourceType::Energy, 100_000)]));
+    let powered = state.simulation.add_structure(StructureType::Installation(powered));
+    let unpowered = state.simulation.add_structure(StructureType::Installation(crewed(HashMap::new())));
+    state.process_turn();
+
+    let dug = stock(&state, powered, ResourceType::Minerals);
+    let starved = stock(&state, unpowered, ResourceType::Minerals);
+    assert!(starved > 0, "its own plant still runs it at half power");
+    assert!(starved < dug);
+}
+
+#[test]
+fn surveying_a_body_reveals_its_deposits() {
+    let (mut state, miners) = game();
+    let psyche = state.solar_system.get_body("16 Psyche").unwrap().id;
+    let mars = state.solar_system.get_body("Mars").unwrap().id;
+    let map = state.surface_map_mut(mars).unwrap();
+    let (site, elsewhere) = {
+        let mut rich = map.tiles().filter(|tile| !tile.deposits.is_empty()).map(|tile| tile.coord);
+        (rich.next().unwrap(), rich.next().unwrap())
+    };
+
+    let probe = Spacecraft::new("Probe".to_string(), Location::Orbit { body_id: psyche, orbital_slot_id: Uuid::new_v4() })
+        .with_owner(miners);
+    state.simulation.add_structure(StructureType::Spacecraft(probe));
+    let camp = Settlement::new("Camp".to_string(), Location::Surface { body_id: mars, hex_coord: site }).with_owner(miners);
+    state.simulation.add_structure(StructureType::Settlement(camp));
+    state.process_turn();
+
+    let psyche = state.solar_system.get_body("16 Psyche").unwrap();
+    assert!(psyche.deposits.iter().all(|d| d.status(miners) == DepositStatus::Surveyed));
+    let map = state.surface_map_mut(mars).unwrap();
+    assert!(map.tile(site).unwrap().deposits.iter().all(|d| d.status(miners) == DepositStatus::Surveyed));
+    assert!(map.tile(elsewhere).unwrap().deposits.iter().all(|d| d.status(miners) == DepositStatus::Hidden));
+}
@@ -0,0 +1,154 @@
+use std::path::PathBuf;
+use harsh_realm_sim::events::event_bus::EventFilter;
+use harsh_realm_sim::events::game_event::{EventCategory, GameEventKind};
+use harsh_realm_sim::faction::faction::Faction;
+use harsh_realm_sim::game_state::GameState;
+use harsh_realm_sim::maps::environment::{self, Atmosphere, Climate, Environment, Weather};
+use harsh_realm_sim::maps::location::Location;
+use harsh_realm_sim::population::person_type::PersonType;
+use harsh_realm_sim::resources::resource_type::ResourceType;
+use harsh_realm_sim::structures::settlement::{Settlement, HABITAT_SHIELDING_G_CM2};
+use harsh_realm_sim::structures::structure_type::StructureType;
+
+fn data_path() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("..").join("..").join("data").join("solar_system_data.csv")
+}
+
+fn loaded_game() -> GameState {
+    let mut state = GameState::new();
+    state.load_solar_system_data(data_path().to_str().unwrap()).unwrap();
+    state
+}
+
+fn equator(state: &GameState, body: &str) -> Environment {
+    let body = state.solar_system.get_body(body).unwrap();
+    Climate::for_body(body, &state.solar_system).at(0.0, None)
+}
+
+#[test]
+fn bodies_get_their_real_world_conditions() {
+    let state = loaded_game();
+    let earth = equator(&state, "Earth");
+    assert!(earth.is_habitable());
+    assert!(earth.radiation_msv_per_day < 0.02);
+    assert!(earth.day_night_swing_k < 20.0);
+
+    let venus = equator(&state, "Venus");
+    assert_eq!(venus.atmosphere, Atmosphere::CarbonDioxide);
+    assert!(venus.pressure_kpa > 5000.0 && venus.temperature_k > 650.0, "{:?}", venus);
+
+    let mars = equator(&state, "Mars");
+    assert!(mars.pressure_kpa < 1.0);
+    assert!((0.3..1.5).contains(&mars.radiation_msv_per_day), "{}", mars.radiation_msv_per_day);
+
+    let luna = equator(&state, "Luna (E I)");
+    assert_eq!(luna.atmosphere, Atmosphere::Vacuum);
+    assert!(luna.day_night_swing_k > 100.0, "{}", luna.day_night_swing_k);
+    assert!(!luna.is_habitable());
+
+    let titan = equator(&state, "TItan (S VI)");
+    assert_eq!(titan.atmosphere, Atmosphere::Nitrogen);
+    assert!(titan.pressure_kpa > EARTH_KPA);
+
+    // Jupiter's radiation belts: lethal at Io and Europa, tolerable at Callisto
+    let dose = |body: &str| equator(&state, body).radiation_msv_per_day;
+    assert!(dose("Io (J I)") > 10_000.0, "{}", dose("Io (J I)"));
+    assert!(dose("Europa (J II)") > 1_000.0, "{}", dose("Europa (J II)"));
+    assert!(dose("Ganymede (J III)") < dose("Europa (J II)") / 10.0);
+    assert!(dose("Callisto (J IV)") < 5.0);
+    assert!(equator(&state, "Io (J I)").effects().shielding_g_cm2 > equator(&state, "Callisto (J IV)").effects().shielding_g_cm2);
+}
+
+const EARTH_KPA: f64 = environment::EARTH_PRESSURE_KPA;
+
+#[test]
+fn dust_storms_come_and_go_on_mars_only() {
+    let mut state = loaded_game();
+    let mars = state.solar_system.get_body("Mars").unwrap().id;
+    let luna = state.solar_system.get_body("Luna (E I)").unwrap().id;
+    state.surface_map_mut(mars);
+    state.surface_map_mut(luna);
+
+    // Regional storms blow up and die down; the airless Moon never has weather
+    let mut stormy = Vec::new();
+    for turn in 1..=20 {
+        stormy.push(environment::update_weather(&mut state.solar_system, 11, turn));
+        let calm_moon = state.solar_system.get_body_by_id(luna).unwrap().surface_map().unwrap();
+        assert!(calm_moon.tiles().all(|tile| tile.weather == Weather::Calm));
+    }
+    let martian = state.solar_system.get_body_by_id(mars).unwrap().surface_map().unwrap();
+    assert!(stormy.iter().all(|tiles| *tiles < martian.len() / 10), "{:?}", stormy);
+    assert!(stormy.iter().any(|tiles| *tiles != stormy[0]), "{:?}", stormy);
+    assert!(martian.tiles().all(|tile| tile.weather != Weather::Storm));
+}
+
+#[test]
+fn harsh_surfaces_strain_life_support_and_wear_equipment() {
+    let mut state = loaded_game();
+    let settlers = state.simulation.add_faction(Faction::new("Settlers".to_string()));
+    // Strain is reported to the settlement's owner
+    let strained = state.simulation.events.subscribe(EventFilter::categories([EventCategory::Population]).for_faction(settlers));
+    let settle = |state: &mut GameState, name: &str, body: &str, energy: u64| {
+        // Hex (0, 0) is the north pole; settle on the equator instead
+        let body = state.solar_system.get_body(body).unwrap();
+        let location = Location::Surface { body_id: body.id, hex_coord: body.surface_grid().tile_at(0.0, 0.0) };
+        let settlement = Settlement::new(name.to_string(), location)
+            .with_owner(settlers)
+            .with_population([(PersonType::Colonist, 1000)].into())
+            .with_resources([(ResourceType::Energy, energy)].into());
+        state.simulation.add_structure(StructureType::Settlement(settlement))
+    };
+    // A little Energy sees Houston through a storm but falls far short on the Moon
+    let houston = settle(&mut state, "Houston", "Earth", 100);
+    let base = settle(&mut state, "Shackleton Base", "Luna (E I)", 100);
+    state.process_turn();
+
+    let wear = |state: &GameState, id| state.simulation.structures[&id].wear();
+    assert!(wear(&state, base) > wear(&state, houston));
+    let events = state.simulation.events.poll(strained);
+    assert!(events.iter().any(|e| matches!(e.kind, GameEventKind::LifeSupportStrained { structure_id, .. } if structure_id == base)));
+    assert!(!events.iter().any(|e| matches!(e.kind, GameEventKind::LifeSupportStrained { structure_id, .. } if structure_id == houston)));
+}
+
+#[test]
+fn thin_shielding_lets_radiation_kill() {
+    let mut state = loaded_game();
+    let settlers = state.simulation.add_faction(Faction::new("Settlers".to_string()));
+    let sick = state.simulation.events.subscribe(EventFilter::categories([EventCategory::Population]).for_faction(settlers));
+    let settle = |state: &mut GameState, body: &str, shielding: f64| {
+        let body = state.solar_system.get_body(body).unwrap();
+        let location = Location::Surface { body_id: body.id, hex_coord: body.surface_grid().tile_at(0.0, 0.0) };
+        let settlement = Settlement::new(body.name.clone(), location)
+            .with_owner(settlers)
+            .with_shielding(shielding)
+            .with_population([(PersonType::Colonist, 1000)].into());
+        state.simulation.add_structure(StructureType::Settlement(settlement))
+    };
+    // Standard habitats are enough on Mars but not under Jupiter's belts
+    let jezero = settle(&mut state, "Mars", HABITAT_SHIELDING_G_CM2);
+    let exposed = settle(&mut state, "Io (J I)", HABITAT_SHIELDING_G_CM2);
+    let bunker = settle(&mut state, "Io (J I)", 3000.0);
+    state.process_turn();
+
+    let population = |state: &GameState, id| match &state.simulation.structures[&id] {
+        StructureType::Settlement(settlement) => settlement.total_population(),
+        _ => unreachable!(),
+    };
+    assert_eq!(population(&state, jezero), 1000);
+    assert_eq!(population(&state, bunker), 1000);
+    assert!(population(&state, exposed) < 100, "{}", population(&state, exposed));
+
+    let sickened: Vec<_> = state
+        .simulation
+        .events
+        .poll(sick)
+        .into_iter()
+        .filter_map(|e| match e.kind {
+            GameEventKind::RadiationSickness { structure_id, deaths, .. } => Some((structure_id, deaths)),
+            _ => None,
+        })
+        .collect();
+    assert!(!sickened.is_empty());
+    assert!(sickened.iter().all(|(id, _)| *id == exposed));
+    assert_eq!(sickened.iter().map(|(_, deaths)| deaths).sum::<u32>(), 1000 - population(&state, exposed));
+}
@@ -0,0 +1,122 @@
+use std::path::PathBuf;
+use harsh_realm_sim::events::event_bus::EventFilter;
+use harsh_realm_sim::events::game_event::{EventCategory, GameEventKind};
+use harsh_realm_sim::faction::faction::Faction;
+use harsh_realm_sim::game_state::GameState;
+use harsh_realm_sim::maps::location::Location;
+use harsh_realm_sim::population::person_type::PersonType;
+use harsh_realm_sim::resources::resource_type::ResourceType;
+use harsh_realm_sim::simulation::real_time::{PauseTrigger, SimulationSpeed};
+use harsh_realm_sim::structures::settlement::Settlement;
+use harsh_realm_sim::structures::structure_type::StructureType;
+
+fn data_path() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("..").join("..").join("data").join("solar_system_data.csv")
+}
+
+fn loaded_game() -> GameState {
+    let mut state = GameState::new();
+    state.load_solar_system_data(data_path().to_str().unwrap()).unwrap();
+    state
+}
+
+#[test]
+fn each_turn_ends_with_a_turn_ended_event() {
+    let mut state = GameState::new();
+    let subscription = state.simulation.events.subscribe(EventFilter::categories([EventCategory::Game]));
+
+    state.process_turn();
+    state.process_turn();
+
+    let last_turn = state.simulation.events.previous_turn();
+    assert_eq!(last_turn.len(), 1);
+    assert_eq!(last_turn[0].kind, GameEventKind::TurnEnded { turn: 2 });
+    assert_eq!(last_turn[0].turn, 2);
+    assert_eq!(last_turn[0].date, state.get_game_date());
+
+    // The subscriber still sees turn 1's event, which has rolled out of the buffer
+    let polled: Vec<_> = state.simulation.events.poll(subscription).into_iter().map(|e| e.kind).collect();
+    assert_eq!(polled, vec![GameEventKind::TurnEnded { turn: 1 }, GameEventKind::TurnEnded { turn: 2 }]);
+}
+
+#[test]
+fn planetary_conjunctions_are_reported() {
+    let mut state = loaded_game();
+    let subscription = state.simulation.events.subscribe(EventFilter::categories([EventCategory::Astronomy]));
+
+    let mut conjunctions = Vec::new();
+    for _ in 0..120 {
+        state.process_turn();
+        conjunctions.extend(state.simulation.events.poll(subscription));
+    }
+
+    assert!(!conjunctions.is_empty(), "ten years should contain a conjunction");
+    for event in &conjunctions {
+        let GameEventKind::Conjunction { first, second, separation_degrees } = &event.kind else {
+            panic!("unexpected event {:?}", event.kind);
+        };
+        assert!(first < second);
+        assert!(*separation_degrees <= 1.0);
+    }
+}
+
+#[test]
+fn hungry_settlements_report_starvation_to_their_owner() {
+    let mut state = GameState::new();
+    let owner = state.simulation.add_faction(Faction::new("Settlers".to_string()));
+    let subscription =
+        state.simulation.events.subscribe(EventFilter::categories([EventCategory::Population]).for_faction(owner));
+    let town = Settlement::new("Lean Times".to_string(), Location::DeepSpace { x: 0.0, y: 0.0 })
+        .with_owner(owner)
+        .with_population([(PersonType::Colonist, 1000)].into())
+        .with_resources([(ResourceType::Food, 100)].into());
+    let town = state.simulation.add_structure(StructureType::Settlement(town));
+    let granary = Settlement::new("Granary".to_string(), Location::DeepSpace { x: 0.0, y: 0.0 })
+        .with_owner(owner)
+        .with_population([(PersonType::Colonist, 1000)].into())
+        .with_resources([(ResourceType::Food, 5000)].into());
+    let granary = state.simulation.add_structure(StructureType::Settlement(granary));
+
+    // Stores are checked when the month turns; a month feeding 1000 people takes about
+    // 300 Food, so over two thirds would go hungry
+    state.process_turn();
+    state.process_turn();
+    let polled = state.simulation.events.poll(subscription);
+    let starving = polled.iter().find_map(|e| match e.kind {
+        GameEventKind::PopulationStarving { structure_id, shortfall } if structure_id == town => Some(shortfall),
+        _ => None,
+    });
+    let shortfall = starving.expect("the town should report starvation");
+    assert!((667..1000).contains(&shortfall), "{} went hungry", shortfall);
+    let fed = |kind: &GameEventKind| !matches!(kind, GameEventKind::PopulationStarving { structure_id, .. } if *structure_id == granary);
+    assert!(polled.iter().all(|e| fed(&e.kind)));
+}
+
+#[test]
+fn real_time_pauses_on_event_category() {
+    let mut state = GameState::new();
+    state.clock.speed = SimulationSpeed::Custom(1.0);
+    state.clock.set_pause_on(PauseTrigger::Event(EventCategory::Game), true);
+    state.clock.resume();
+
+    // The default 30-day turn ends with a Game event on day 30
+    let tick = state.tick_real_time(100.0);
+    assert_eq!(tick.days_advanced, 30);
+    assert_eq!(tick.paused_by, Some(PauseTrigger::Event(EventCategory::Game)));
+}
+
+#[test]
+fn rewinding_clears_buffered_events() {
+    let mut state = GameState::new();
+    let subscription = state.simulation.events.subscribe(EventFilter::all());
+    state.process_turn();
+    state.process_turn();
+
+    state.undo_turn().unwrap();
+    assert!(state.simulation.events.previous_turn().is_empty());
+    assert!(state.simulation.events.poll(subscription).is_empty());
+
+    // The subscription survives the rewind
+    state.process_turn();
+    assert_eq!(state.simulation.events.poll(subscription).len(), 1);
+}
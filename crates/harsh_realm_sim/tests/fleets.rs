@@ -0,0 +1,79 @@
+use std::collections::HashMap;
+use std::path::PathBuf;
+use harsh_realm_sim::events::event_bus::EventFilter;
+use harsh_realm_sim::events::game_event::{EventCategory, GameEventKind};
+use harsh_realm_sim::game_state::GameState;
+use harsh_realm_sim::maps::location::Location;
+use harsh_realm_sim::save::save_file::{load_game, save_game, SaveOptions};
+use harsh_realm_sim::structures::fleet::{self, FleetOrder, Formation, OrderStatus};
+use harsh_realm_sim::structures::movement::PROPELLANT;
+use harsh_realm_sim::structures::spacecraft::{Spacecraft, SpacecraftModuleType};
+use harsh_realm_sim::structures::structure_type::StructureType;
+use uuid::Uuid;
+
+fn data_path() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("..").join("..").join("data").join("solar_system_data.csv")
+}
+
+fn loaded_game() -> GameState {
+    let mut state = GameState::new();
+    state.load_solar_system_data(data_path().to_str().unwrap()).unwrap();
+    state
+}
+
+fn orbit(state: &GameState, body: &str) -> Location {
+    Location::Orbit { body_id: state.solar_system.get_body(body).unwrap().id, orbital_slot_id: Uuid::new_v4() }
+}
+
+fn launch(state: &mut GameState, faction: Uuid, modules: HashMap<SpacecraftModuleType, u32>) -> Uuid {
+    let mut modules = modules;
+    modules.insert(SpacecraftModuleType::LiquidTank, 1);
+    let ship = Spacecraft::new("Frigate".to_string(), orbit(state, "Earth"))
+        .with_owner(faction)
+        .with_modules(modules)
+        .with_cargo(HashMap::from([(PROPELLANT, 5000)]));
+    state.simulation.add_structure(StructureType::Spacecraft(ship))
+}
+
+#[test]
+fn convoys_cross_to_the_moon_together() {
+    let mut state = loaded_game();
+    let faction = Uuid::new_v4();
+    let logistics = state.simulation.events.subscribe(EventFilter::categories([EventCategory::Logistics]));
+    let escort = launch(&mut state, faction, HashMap::from([(SpacecraftModuleType::Military, 2)]));
+    let freighter = launch(&mut state, faction, HashMap::from([(SpacecraftModuleType::CargoHold, 6)]));
+    let convoy = fleet::create(&mut state.simulation, "Lunar Convoy".to_string(), vec![escort, freighter]).unwrap();
+    fleet::set_formation(&mut state.simulation, convoy, Formation::Screen).unwrap();
+    let luna = orbit(&state, "Luna (E I)");
+    fleet::set_order(&mut state.simulation, convoy, FleetOrder::Move { destination: luna.clone() }).unwrap();
+
+    let mut events = Vec::new();
+    for _ in 0..60 {
+        state.process_turn();
+        events.extend(state.simulation.events.poll(logistics));
+        let [a, b] = [escort, freighter].map(|ship| state.simulation.structures[&ship].location().clone());
+        assert_eq!(a, b, "the escort left the freighter behind");
+        if state.simulation.fleets[&convoy].status() == OrderStatus::OnStation {
+            break;
+        }
+    }
+
+    assert!(events.iter().any(|event| event.faction == Some(faction)
+        && event.kind == GameEventKind::FleetArrived { fleet_id: convoy, location: luna.clone() }));
+    assert_eq!(state.simulation.structures[&escort].location(), &luna);
+    assert_eq!(state.simulation.fleets[&convoy].order(), &FleetOrder::Hold);
+    let stats = fleet::stats(&state.simulation, convoy).unwrap();
+    assert_eq!((stats.ships, stats.strength), (2, 2));
+    assert!(stats.propellant < 10_000);
+
+    // Fleets survive a save
+    let path = std::env::temp_dir().join(format!("harsh_realm_fleets_{}.json", Uuid::new_v4()));
+    save_game(&state, &path, &SaveOptions::default()).unwrap();
+    let loaded = load_game(&path).unwrap();
+    std::fs::remove_file(&path).ok();
+    assert_eq!(loaded.simulation.fleets[&convoy].members(), &[escort, freighter]);
+    let StructureType::Spacecraft(ship) = &loaded.simulation.structures[&freighter] else {
+        panic!("the freighter is not a spacecraft");
+    };
+    assert_eq!(ship.fleet_id(), Some(convoy));
+}
@@ -0,0 +1,91 @@
+use harsh_realm_sim::game_state::GameState;
+use harsh_realm_sim::headless::runner::{build_game, run, run_game};
+use harsh_realm_sim::headless::runner_options::{RunnerOptions, UNTIL_VICTORY_TURN_CAP};
+use harsh_realm_sim::victory::victory_condition::VictoryCondition;
+use harsh_realm_sim::save::save_file::load_game;
+use uuid::Uuid;
+
+#[test]
+fn runs_turns_and_writes_saves() {
+    let root = std::path::PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("..").join("..");
+    let out_dir = std::env::temp_dir().join(format!("harsh_realm_headless_{}", Uuid::new_v4()));
+
+    let options = RunnerOptions {
+        scenario_path: None,
+        data_path: root.join("data").join("solar_system_data.csv"),
+        turns: 4,
+        until_victory: false,
+        seed: Some(42),
+        save_every: Some(2),
+        save_dir: out_dir.clone(),
+        dump_json: Some(out_dir.join("final.json")),
+        metrics_path: None,
+    };
+
+    let mut output = Vec::new();
+    let report = run(&options, &mut output).expect("headless run should succeed");
+
+    let output = String::from_utf8(output).unwrap();
+    assert_eq!(output.lines().count(), 4);
+    assert!(output.lines().last().unwrap().starts_with("Turn    4 | 2070 May 01"));
+
+    assert_eq!(report.turns_run, 4);
+    assert_eq!(report.saves_written.len(), 2);
+    let last_save = load_game(&report.saves_written[1]).expect("save should load");
+    assert_eq!(last_save.simulation.current_turn, 4);
+    assert_eq!(last_save.simulation.seed, 42);
+
+    let dump: serde_json::Value =
+        serde_json::from_slice(&std::fs::read(out_dir.join("final.json")).unwrap()).unwrap();
+    assert_eq!(dump["simulation"]["current_turn"], 4);
+
+    std::fs::remove_dir_all(&out_dir).ok();
+}
+
+#[test]
+fn seed_option_shapes_scenario_terrain() {
+    let root = std::path::PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("..").join("..");
+    let mars_terrain = |seed| {
+        let options = RunnerOptions {
+            scenario_path: Some(root.join("data").join("scenarios").join("sol_2070.json")),
+            data_path: root.join("data").join("solar_system_data.csv"),
+            turns: 0,
+            until_victory: false,
+            seed: Some(seed),
+            save_every: None,
+            save_dir: std::env::temp_dir(),
+            dump_json: None,
+            metrics_path: None,
+        };
+        let state = build_game(&options).expect("scenario should load");
+        assert_eq!(state.simulation.seed, seed);
+        let map = state.solar_system.get_body("Mars").unwrap().surface_map().expect("Mars map should exist");
+        let mut tiles: Vec<_> = map.tiles().map(|tile| (tile.coord, tile.elevation)).collect();
+        tiles.sort_by_key(|(coord, _)| *coord);
+        tiles
+    };
+    assert_ne!(mars_terrain(7), mars_terrain(8));
+}
+
+#[test]
+fn until_victory_runs_scenarios_to_their_end() {
+    let root = std::path::PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("..").join("..");
+    let options = RunnerOptions {
+        scenario_path: Some(root.join("data").join("scenarios").join("sol_2070.json")),
+        turns: 1,
+        until_victory: true,
+        ..Default::default()
+    };
+    let report = run(&options, &mut Vec::new()).expect("headless run should succeed");
+
+    // Nobody wins outright, so the scenario's 240 turn limit ends it
+    let outcome = report.outcome.expect("the game should have ended");
+    assert_eq!(outcome.reason, VictoryCondition::TurnLimit { turns: 240 });
+    assert_eq!(report.turns_run, 240);
+
+    // A game that can never end stops at the cap
+    let mut endless = GameState::new();
+    let report = run_game(&mut endless, &options, &mut Vec::new()).unwrap();
+    assert!(report.outcome.is_none());
+    assert_eq!(report.turns_run, UNTIL_VICTORY_TURN_CAP);
+}
@@ -0,0 +1,91 @@
+use std::path::PathBuf;
+use harsh_realm_sim::events::event_bus::EventFilter;
+use harsh_realm_sim::events::game_event::{EventCategory, GameEventKind};
+use harsh_realm_sim::faction::faction::Faction;
+use harsh_realm_sim::game_state::GameState;
+use harsh_realm_sim::maps::location::Location;
+use harsh_realm_sim::structures::settlement::Settlement;
+use harsh_realm_sim::structures::spacecraft::Spacecraft;
+use harsh_realm_sim::structures::structure_type::StructureType;
+use hex2d::Coordinate as HexCoord;
+use uuid::Uuid;
+
+fn data_path() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("..").join("..").join("data").join("solar_system_data.csv")
+}
+
+fn surface(state: &GameState, body: &str) -> Location {
+    Location::Surface { body_id: state.solar_system.get_body(body).unwrap().id, hex_coord: HexCoord::new(0, 0) }
+}
+
+fn settle(state: &mut GameState, name: &str, owner: Uuid, body: &str) -> Uuid {
+    let settlement = Settlement::new(name.to_string(), surface(state, body)).with_owner(owner);
+    state.simulation.add_structure(StructureType::Settlement(settlement))
+}
+
+fn two_faction_game() -> (GameState, Uuid, Uuid) {
+    let mut state = GameState::new();
+    state.load_solar_system_data(data_path().to_str().unwrap()).unwrap();
+    let terrans = state.simulation.add_faction(Faction::new("Terrans".to_string()));
+    let martians = state.simulation.add_faction(Faction::new("Martians".to_string()));
+    settle(&mut state, "Houston", terrans, "Earth");
+    settle(&mut state, "Jezero", martians, "Mars");
+    (state, terrans, martians)
+}
+
+#[test]
+fn factions_only_see_what_shares_a_site_with_them() {
+    let (mut state, terrans, martians) = two_faction_game();
+    state.process_turn();
+
+    let terran_view = state.knowledge_of(terrans).unwrap();
+    assert_eq!(terran_view.known_structures().count(), 1);
+    assert_eq!(terran_view.contacts().count(), 0);
+    let earth = state.solar_system.get_body("Earth").unwrap().id;
+    let mars = state.solar_system.get_body("Mars").unwrap().id;
+    assert!(terran_view.is_surveyed(earth));
+    assert!(!terran_view.is_surveyed(mars));
+    assert_eq!(state.knowledge_of(martians).unwrap().contacts().count(), 0);
+}
+
+#[test]
+fn remote_sightings_arrive_late_and_go_stale() {
+    let (mut state, terrans, martians) = two_faction_game();
+    let contacts = state
+        .simulation
+        .events
+        .subscribe(EventFilter::categories([EventCategory::Intelligence]).for_faction(terrans));
+
+    // A Terran probe lands beside Jezero; its report needs light time to reach Earth
+    let probe = Spacecraft::new("Probe".to_string(), surface(&state, "Mars")).with_owner(terrans);
+    let probe_id = state.simulation.add_structure(StructureType::Spacecraft(probe));
+    state.process_turn();
+
+    let jezero_id = state.simulation.structures.values().find(|s| s.name() == "Jezero").unwrap().id();
+    let terran_view = state.knowledge_of(terrans).unwrap();
+    assert!(terran_view.get_structure(jezero_id).is_none());
+    assert_eq!(terran_view.in_transit().len(), 1);
+    assert!(state.simulation.events.poll(contacts).is_empty());
+
+    // By the next sweep the report has arrived
+    state.process_turn();
+    let terran_view = state.knowledge_of(terrans).unwrap();
+    let jezero = terran_view.get_structure(jezero_id).expect("Jezero should have been reported");
+    assert_eq!(jezero.owner, Some(martians));
+    assert_eq!((jezero.received_date - jezero.observed_date).num_days(), 1);
+
+    let events = state.simulation.events.poll(contacts);
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0].kind, GameEventKind::ContactReported { structure_id: jezero_id });
+
+    // The Martians see the probe too, and the lost probe leaves Jezero's intel to age
+    assert!(state.knowledge_of(martians).unwrap().get_structure(probe_id).is_some());
+    state.simulation.structures.remove(&probe_id);
+    state.process_turn();
+    state.process_turn();
+
+    let terran_view = state.knowledge_of(terrans).unwrap();
+    assert!(terran_view.get_structure(probe_id).is_none());
+    assert!(terran_view.in_transit().is_empty());
+    assert!(terran_view.get_structure(jezero_id).unwrap().age_days(state.get_game_date()) >= 58);
+}
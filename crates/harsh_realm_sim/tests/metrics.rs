@@ -0,0 +1,84 @@
+use std::collections::HashMap;
+use std::path::PathBuf;
+use harsh_realm_sim::faction::faction::Faction;
+use harsh_realm_sim::game_state::GameState;
+use harsh_realm_sim::headless::runner::run;
+use harsh_realm_sim::headless::runner_options::RunnerOptions;
+use harsh_realm_sim::maps::location::Location;
+use harsh_realm_sim::population::person_type::PersonType;
+use harsh_realm_sim::resources::resource_type::ResourceType;
+use harsh_realm_sim::structures::settlement::Settlement;
+use harsh_realm_sim::structures::structure_type::StructureType;
+use uuid::Uuid;
+
+fn root() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("..").join("..")
+}
+
+#[test]
+fn records_stocks_and_flows_every_turn() {
+    let mut state = GameState::new();
+    let faction = state.simulation.add_faction(Faction::new("Alpha".to_string()).with_treasury(50.0));
+    let settlement = Settlement::new("Base".to_string(), Location::DeepSpace { x: 0.0, y: 0.0 })
+        .with_owner(faction)
+        .with_population(HashMap::from([(PersonType::Worker, 10)]))
+        .with_resources(HashMap::from([(ResourceType::Water, 100)]));
+    let settlement_id = state.simulation.add_structure(StructureType::Settlement(settlement));
+
+    state.process_turn();
+
+    // Swap the settlement for one with less water to produce a negative flow
+    state.simulation.structures.remove(&settlement_id);
+    let settlement = Settlement::new("Base".to_string(), Location::DeepSpace { x: 0.0, y: 0.0 })
+        .with_owner(faction)
+        .with_resources(HashMap::from([(ResourceType::Water, 60), (ResourceType::Metal, 5)]));
+    state.simulation.add_structure(StructureType::Settlement(settlement));
+    state.process_turn();
+
+    let series: Vec<_> = state.metrics.faction_series(faction).collect();
+    assert_eq!(series.len(), 2);
+    let (turn, first) = series[0];
+    assert_eq!(turn, 1);
+    assert_eq!(first.treasury, 50.0);
+    assert_eq!(first.population[&PersonType::Worker], 10);
+    assert!(first.flows.is_empty(), "turn 1 has nothing to compare with");
+
+    let (_, second) = series[1];
+    assert_eq!(second.stocks[&ResourceType::Water], 60);
+    assert_eq!(second.flows[&ResourceType::Water], -40);
+    assert_eq!(second.flows[&ResourceType::Metal], 5);
+    assert!(second.population.is_empty());
+
+    // Undoing a turn drops its metrics
+    state.undo_turn().unwrap();
+    assert_eq!(state.metrics.series().len(), 1);
+}
+
+#[test]
+fn headless_run_exports_csv_and_json() {
+    let out_dir = std::env::temp_dir().join(format!("harsh_realm_metrics_{}", Uuid::new_v4()));
+    std::fs::create_dir_all(&out_dir).unwrap();
+
+    for file in ["metrics.csv", "metrics.json"] {
+        let options = RunnerOptions {
+            scenario_path: Some(root().join("data").join("scenarios").join("sol_2070.json")),
+            turns: 3,
+            metrics_path: Some(out_dir.join(file)),
+            ..Default::default()
+        };
+        run(&options, &mut Vec::new()).expect("headless run should succeed");
+    }
+
+    let csv = std::fs::read_to_string(out_dir.join("metrics.csv")).unwrap();
+    let mut lines = csv.lines();
+    assert_eq!(lines.next(), Some("turn,date,faction_id,faction,metric,value"));
+    assert!(csv.lines().any(|line| line.starts_with("3,2070-04-01,") && line.contains(",Mars Directorate,population.Soldier,50")));
+
+    let json: serde_json::Value = serde_json::from_slice(&std::fs::read(out_dir.join("metrics.json")).unwrap()).unwrap();
+    let turns = json.as_array().unwrap();
+    assert_eq!(turns.len(), 3);
+    assert_eq!(turns[0]["factions"].as_array().unwrap().len(), 3);
+    assert_eq!(turns[0]["factions"][0]["name"], "Belt Union");
+
+    std::fs::remove_dir_all(&out_dir).ok();
+}
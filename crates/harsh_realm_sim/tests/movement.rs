@@ -0,0 +1,100 @@
+use std::collections::HashMap;
+use std::path::PathBuf;
+use harsh_realm_sim::events::event_bus::EventFilter;
+use harsh_realm_sim::events::game_event::{EventCategory, GameEventKind};
+use harsh_realm_sim::game_state::GameState;
+use harsh_realm_sim::maps::location::Location;
+use harsh_realm_sim::structures::movement::{self, MovementError, PROPELLANT};
+use harsh_realm_sim::structures::settlement::Settlement;
+use harsh_realm_sim::structures::spacecraft::{Spacecraft, SpacecraftModuleType};
+use harsh_realm_sim::structures::structure_type::StructureType;
+use uuid::Uuid;
+
+fn data_path() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("..").join("..").join("data").join("solar_system_data.csv")
+}
+
+fn loaded_game() -> GameState {
+    let mut state = GameState::new();
+    state.load_solar_system_data(data_path().to_str().unwrap()).unwrap();
+    state
+}
+
+fn orbit(state: &GameState, body: &str) -> Location {
+    Location::Orbit { body_id: state.solar_system.get_body(body).unwrap().id, orbital_slot_id: Uuid::new_v4() }
+}
+
+fn spacecraft(state: &GameState, id: Uuid) -> &Spacecraft {
+    let Some(StructureType::Spacecraft(spacecraft)) = state.simulation.structures.get(&id) else {
+        panic!("no spacecraft {}", id);
+    };
+    spacecraft
+}
+
+#[test]
+fn ships_fly_to_the_moon_and_dock() {
+    let mut state = loaded_game();
+    let faction = Uuid::new_v4();
+    let logistics = state.simulation.events.subscribe(EventFilter::categories([EventCategory::Logistics]));
+    let luna = state.solar_system.get_body("Luna (E I)").unwrap();
+    let base = Location::Surface { body_id: luna.id, hex_coord: luna.surface_grid().tile_at(0.0, 0.0) };
+    let base = state.simulation.add_structure(StructureType::Settlement(Settlement::new("Tranquility".to_string(), base)));
+    let ship = Spacecraft::new("Shuttle".to_string(), orbit(&state, "Earth"))
+        .with_owner(faction)
+        // A bare hull only has room for 500 propellant
+        .with_modules(HashMap::from([(SpacecraftModuleType::LiquidTank, 1)]))
+        .with_cargo(HashMap::from([(PROPELLANT, 5000)]));
+    let ship = state.simulation.add_structure(StructureType::Spacecraft(ship));
+
+    let trajectory = movement::set_course(&mut state.simulation, &state.solar_system, ship, Location::Docked { structure_id: base }).unwrap();
+    assert!(trajectory.eta() > state.get_game_date());
+    assert!(trajectory.propellant() > 0 && trajectory.propellant() <= 5000);
+
+    let mut events = Vec::new();
+    let mut progress = Vec::new();
+    for _ in 0..30 {
+        state.process_turn();
+        events.extend(state.simulation.events.poll(logistics));
+        match spacecraft(&state, ship).trajectory() {
+            Some(flying) => {
+                assert!(matches!(spacecraft(&state, ship).location(), Location::DeepSpace { .. }));
+                progress.push(flying.progress());
+            }
+            None => break,
+        }
+    }
+
+    // Progress only ever goes up until the ship gets there
+    assert!(progress.windows(2).all(|pair| pair[0] < pair[1]));
+    assert!(events.iter().any(|event| event.faction == Some(faction)
+        && event.kind == GameEventKind::ShipArrived { spacecraft_id: ship, location: Location::Docked { structure_id: base } }));
+    let ship = spacecraft(&state, ship);
+    assert_eq!(ship.location(), &Location::Docked { structure_id: base });
+    assert_eq!(ship.propellant(), 5000 - trajectory.propellant());
+    assert!(state.get_game_date() >= trajectory.eta());
+}
+
+#[test]
+fn ships_without_propellant_have_their_orders_cancelled() {
+    let mut state = loaded_game();
+    let logistics = state.simulation.events.subscribe(EventFilter::categories([EventCategory::Logistics]));
+    let ship = Spacecraft::new("Hulk".to_string(), orbit(&state, "Earth"));
+    let ship = state.simulation.add_structure(StructureType::Spacecraft(ship));
+
+    let mars = orbit(&state, "Mars");
+    assert!(matches!(
+        movement::set_course(&mut state.simulation, &state.solar_system, ship, mars.clone()),
+        Err(MovementError::InsufficientPropellant { available: 0, .. })
+    ));
+
+    // Orders given directly are checked in the movement phase instead
+    let Some(StructureType::Spacecraft(hulk)) = state.simulation.structures.get_mut(&ship) else {
+        unreachable!();
+    };
+    hulk.set_destination(Some(mars));
+    state.process_turn();
+    let events = state.simulation.events.poll(logistics);
+    assert!(events.iter().any(|event| event.kind == GameEventKind::CourseBlocked { spacecraft_id: ship }));
+    assert!(spacecraft(&state, ship).destination().is_none());
+    assert!(matches!(spacecraft(&state, ship).location(), Location::Orbit { .. }));
+}
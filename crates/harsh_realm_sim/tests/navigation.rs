@@ -0,0 +1,121 @@
+use std::path::PathBuf;
+use harsh_realm_sim::game_state::GameState;
+use harsh_realm_sim::maps::location::Location;
+use harsh_realm_sim::structures::settlement::Settlement;
+use harsh_realm_sim::structures::spacecraft::Spacecraft;
+use harsh_realm_sim::structures::structure_type::StructureType;
+use harsh_realm_sim::universe::navigation::{self, NavigationError};
+use hex2d::Coordinate as HexCoord;
+use uuid::Uuid;
+
+fn data_path() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("..").join("..").join("data").join("solar_system_data.csv")
+}
+
+fn loaded_game() -> GameState {
+    let mut state = GameState::new();
+    state.load_solar_system_data(data_path().to_str().unwrap()).unwrap();
+    state
+}
+
+fn surface(state: &GameState, body: &str) -> Location {
+    Location::Surface { body_id: state.solar_system.get_body(body).unwrap().id, hex_coord: HexCoord::new(0, 0) }
+}
+
+fn orbit(state: &GameState, body: &str) -> Location {
+    Location::Orbit { body_id: state.solar_system.get_body(body).unwrap().id, orbital_slot_id: Uuid::new_v4() }
+}
+
+#[test]
+fn docked_structures_share_their_hosts_position() {
+    let mut state = loaded_game();
+    let date = state.get_game_date();
+    let station = Spacecraft::new("Gateway".to_string(), orbit(&state, "Luna (E I)"));
+    let station_id = state.simulation.add_structure(StructureType::Spacecraft(station));
+    let tug = Spacecraft::new("Tug".to_string(), Location::Docked { structure_id: station_id });
+    let tug_id = state.simulation.add_structure(StructureType::Spacecraft(tug));
+    let shuttle = Location::Docked { structure_id: tug_id };
+
+    let (solar_system, simulation) = (&state.solar_system, &state.simulation);
+    assert_eq!(navigation::resolve(simulation, &shuttle).unwrap(), &orbit_of(&state, station_id));
+    let luna = navigation::position(solar_system, simulation, &orbit(&state, "Luna (E I)"), date).unwrap();
+    let docked = navigation::position(solar_system, simulation, &shuttle, date).unwrap();
+    assert_eq!((luna.x, luna.y), (docked.x, docked.y));
+
+    // Luna is placed around Earth, not around the Sun
+    let earth_to_luna = navigation::distance_km(solar_system, simulation, &surface(&state, "Earth"), &shuttle, date).unwrap();
+    assert!((300_000.0..420_000.0).contains(&earth_to_luna), "{}", earth_to_luna);
+
+    let missing = Location::Docked { structure_id: Uuid::new_v4() };
+    assert!(matches!(
+        navigation::position(solar_system, simulation, &missing, date),
+        Err(NavigationError::UnknownStructure(_))
+    ));
+}
+
+fn orbit_of(state: &GameState, structure_id: Uuid) -> Location {
+    state.simulation.structures[&structure_id].location().clone()
+}
+
+#[test]
+fn docking_loops_are_reported() {
+    let mut state = loaded_game();
+    // Two craft each docked to the other, filed under the ids they point at
+    let (first_id, second_id) = (Uuid::new_v4(), Uuid::new_v4());
+    for (id, host) in [(first_id, second_id), (second_id, first_id)] {
+        let craft = Spacecraft::new("Looped".to_string(), Location::Docked { structure_id: host });
+        state.simulation.structures.insert(id, StructureType::Spacecraft(craft));
+    }
+    let location = Location::Docked { structure_id: first_id };
+    assert!(matches!(navigation::resolve(&state.simulation, &location), Err(NavigationError::DockingLoop(_))));
+}
+
+#[test]
+fn surface_launches_cost_more_than_orbital_departures() {
+    let state = loaded_game();
+    let date = state.get_game_date();
+    let (solar_system, simulation) = (&state.solar_system, &state.simulation);
+    let estimate = |from: &Location, to: &Location| navigation::estimate(solar_system, simulation, from, to, date).unwrap();
+
+    // Reaching low Earth orbit takes around 9 km/s
+    let to_orbit = estimate(&surface(&state, "Earth"), &orbit(&state, "Earth"));
+    assert!((8.5..10.0).contains(&to_orbit.delta_v), "{}", to_orbit.delta_v);
+
+    let from_orbit = estimate(&orbit(&state, "Earth"), &orbit(&state, "Mars"));
+    let from_ground = estimate(&surface(&state, "Earth"), &orbit(&state, "Mars"));
+    assert!((from_ground.delta_v - from_orbit.delta_v - to_orbit.delta_v).abs() < 1e-9);
+    assert!((240.0..280.0).contains(&from_orbit.travel_days), "{}", from_orbit.travel_days);
+    assert!(from_orbit.distance_km > 50_000_000.0);
+
+    // Landing on Mars adds a smaller descent than Earth's ascent
+    let to_jezero = estimate(&orbit(&state, "Earth"), &surface(&state, "Mars"));
+    assert!(to_jezero.delta_v > from_orbit.delta_v);
+    assert!(to_jezero.delta_v - from_orbit.delta_v < to_orbit.delta_v);
+
+    // Earth to Luna stays inside Earth's well and takes days, not months
+    let to_luna = estimate(&orbit(&state, "Earth"), &orbit(&state, "Luna (E I)"));
+    assert!(to_luna.travel_days < 10.0, "{}", to_luna.travel_days);
+    assert!(to_luna.delta_v < from_orbit.delta_v);
+
+    // Hexes on one body are a suborbital hop apart
+    let mars = state.solar_system.get_body("Mars").unwrap();
+    let far_hex = Location::Surface { body_id: mars.id, hex_coord: mars.surface_grid().tile_at(-30.0, 90.0) };
+    let hop = estimate(&surface(&state, "Mars"), &far_hex);
+    assert!(hop.distance_km > 1000.0);
+    assert!(hop.delta_v > 0.0 && hop.delta_v <= 2.0 * estimate(&surface(&state, "Mars"), &orbit(&state, "Mars")).delta_v);
+}
+
+#[test]
+fn settlements_off_the_grid_cannot_be_placed() {
+    let mut state = loaded_game();
+    let luna = state.solar_system.get_body("Luna (E I)").unwrap().id;
+    let stray = Location::Surface { body_id: luna, hex_coord: HexCoord::new(-5, 900) };
+    let camp = Settlement::new("Stray".to_string(), stray);
+    let camp_id = state.simulation.add_structure(StructureType::Settlement(camp));
+    let rover = Location::Docked { structure_id: camp_id };
+    let date = state.get_game_date();
+    assert!(matches!(
+        navigation::estimate(&state.solar_system, &state.simulation, &rover, &orbit(&state, "Luna (E I)"), date),
+        Err(NavigationError::OffSurface { .. })
+    ));
+}
@@ -0,0 +1,109 @@
+use std::collections::HashMap;
+use std::path::PathBuf;
+use harsh_realm_sim::buildings::building_type::BuildingType;
+use harsh_realm_sim::events::event_bus::EventFilter;
+use harsh_realm_sim::events::game_event::{EventCategory, GameEventKind};
+use harsh_realm_sim::game_state::GameState;
+use harsh_realm_sim::maps::location::Location;
+use harsh_realm_sim::population::person_type::PersonType;
+use harsh_realm_sim::production::production_queue::{self, BuildItem, ProductionError, Workshop};
+use harsh_realm_sim::resources::resource_type::ResourceType;
+use harsh_realm_sim::structures::installation::{Installation, InstallationPurpose};
+use harsh_realm_sim::structures::settlement::Settlement;
+use harsh_realm_sim::structures::spacecraft::SpacecraftModuleType;
+use harsh_realm_sim::structures::structure_type::StructureType;
+use harsh_realm_sim::units::unit_type::UnitType;
+use uuid::Uuid;
+
+fn data_path() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("..").join("..").join("data").join("solar_system_data.csv")
+}
+
+fn loaded_game() -> GameState {
+    let mut state = GameState::new();
+    state.load_solar_system_data(data_path().to_str().unwrap()).unwrap();
+    state
+}
+
+fn equator(state: &GameState, body: &str) -> Location {
+    let body = state.solar_system.get_body(body).unwrap();
+    Location::Surface { body_id: body.id, hex_coord: body.surface_grid().tile_at(0.0, 0.0) }
+}
+
+#[test]
+fn settlements_build_and_launch_over_turns() {
+    let mut state = loaded_game();
+    let faction = Uuid::new_v4();
+    let construction = state.simulation.events.subscribe(EventFilter::categories([EventCategory::Construction]));
+    let settlement = Settlement::new("Port".to_string(), equator(&state, "Earth"))
+        .with_owner(faction)
+        .with_population(HashMap::from([(PersonType::Worker, 300), (PersonType::Child, 500)]))
+        .with_buildings(HashMap::from([(BuildingType::Factory, 1), (BuildingType::Warehouse, 1)]))
+        .with_resources(HashMap::from([
+            (ResourceType::Metal, 5000),
+            (ResourceType::NonMetal, 5000),
+            (ResourceType::Food, 5000),
+            (ResourceType::Energy, 100_000),
+        ]));
+    let port = state.simulation.add_structure(StructureType::Settlement(settlement));
+
+    production_queue::enqueue(&mut state.simulation, port, BuildItem::Building(BuildingType::Mine)).unwrap();
+    production_queue::enqueue(&mut state.simulation, port, BuildItem::Unit(UnitType::Crew)).unwrap();
+    let modules = HashMap::from([(SpacecraftModuleType::Factory, 1)]);
+    production_queue::enqueue(&mut state.simulation, port, BuildItem::Spacecraft { name: "Tender".to_string(), modules }).unwrap();
+
+    // Events are only buffered for a turn, so collect them as they come
+    let mut events = Vec::new();
+    for _ in 0..8 {
+        state.process_turn();
+        events.extend(state.simulation.events.poll(construction));
+    }
+
+    assert!(events.iter().all(|event| event.faction == Some(faction)));
+    assert!(events.iter().any(|e| e.kind == GameEventKind::BuildingCompleted { structure_id: port, building: BuildingType::Mine }));
+    assert!(events.iter().any(|e| e.kind == GameEventKind::UnitTrained { structure_id: port, unit: UnitType::Crew }));
+    let ship = events
+        .iter()
+        .find_map(|e| match e.kind {
+            GameEventKind::SpacecraftLaunched { structure_id, spacecraft_id } if structure_id == port => Some(spacecraft_id),
+            _ => None,
+        })
+        .expect("the tender was never launched");
+
+    let StructureType::Settlement(settlement) = &state.simulation.structures[&port] else {
+        panic!("port is not a settlement");
+    };
+    assert_eq!(settlement.buildings()[&BuildingType::Mine], 1);
+    assert_eq!(settlement.garrison()[&UnitType::Crew], 1);
+    assert!(settlement.production().is_empty());
+    assert_eq!(settlement.resources()[&ResourceType::Metal], 5000 - 200 - 20 - 700);
+
+    let tender = &state.simulation.structures[&ship];
+    assert_eq!(tender.location(), &Location::Docked { structure_id: port });
+    assert_eq!(tender.owner(), Some(faction));
+    assert!(tender.workshop().can_produce(&BuildItem::Spacecraft { name: "Next".to_string(), modules: HashMap::new() }));
+}
+
+#[test]
+fn installations_only_make_what_they_are_equipped_for() {
+    let mut state = loaded_game();
+    let here = equator(&state, "Mars");
+    let mine = Installation::new("Pit".to_string(), InstallationPurpose::Mine, here.clone());
+    let mine = state.simulation.add_structure(StructureType::Installation(mine));
+    let barracks = Installation::new("Barracks".to_string(), InstallationPurpose::Military, here);
+    let barracks = state.simulation.add_structure(StructureType::Installation(barracks));
+
+    let infantry = BuildItem::Unit(UnitType::Infantry);
+    assert_eq!(
+        production_queue::enqueue(&mut state.simulation, mine, infantry.clone()),
+        Err(ProductionError::CannotProduce { structure_id: mine, item: infantry.clone() })
+    );
+    assert!(production_queue::enqueue(&mut state.simulation, barracks, BuildItem::Building(BuildingType::Mine)).is_err());
+    let order = production_queue::enqueue(&mut state.simulation, barracks, infantry.clone()).unwrap();
+
+    // Nothing in the stockpile: the order waits, then is cancelled with nothing to refund
+    production_queue::advance(&mut state.simulation, 60);
+    assert_eq!(state.simulation.structures[&barracks].workshop().production().items()[0].progress(), 0.0);
+    assert_eq!(production_queue::cancel(&mut state.simulation, barracks, order), Ok(infantry));
+    assert_eq!(production_queue::cancel(&mut state.simulation, barracks, order), Err(ProductionError::UnknownItem(order)));
+}
@@ -0,0 +1,59 @@
+use chrono::NaiveDate;
+use harsh_realm_sim::game_state::GameState;
+use harsh_realm_sim::simulation::real_time::{PauseTrigger, SimulationSpeed};
+
+#[test]
+fn paused_clock_does_not_advance() {
+    let mut state = GameState::new();
+    let start = state.get_game_date();
+
+    let tick = state.tick_real_time(10.0);
+    assert_eq!(tick.days_advanced, 0);
+    assert_eq!(state.get_game_date(), start);
+}
+
+#[test]
+fn advances_at_selected_speed() {
+    let mut state = GameState::new();
+    state.clock.speed = SimulationSpeed::WeekPerSecond;
+    state.clock.resume();
+
+    let tick = state.tick_real_time(0.5);
+    assert_eq!(tick.days_advanced, 3);
+    let tick = state.tick_real_time(0.5);
+    assert_eq!(tick.days_advanced, 4);
+    assert_eq!(state.get_game_date(), NaiveDate::from_ymd_opt(2070, 1, 8).unwrap());
+    assert_eq!(state.simulation.current_turn, 0);
+}
+
+#[test]
+fn auto_pauses_at_turn_end() {
+    let mut state = GameState::new();
+    state.clock.speed = SimulationSpeed::MonthPerSecond;
+    state.clock.set_pause_on(PauseTrigger::TurnEnded, true);
+    state.clock.resume();
+
+    // 90 days of real time requested, but the 30-day turn ends first
+    let tick = state.tick_real_time(3.0);
+    assert_eq!(tick.turns_completed, 1);
+    assert_eq!(tick.days_advanced, 30);
+    assert_eq!(tick.paused_by, Some(PauseTrigger::TurnEnded));
+    assert!(state.clock.paused);
+    assert_eq!(state.simulation.current_turn, 1);
+}
+
+#[test]
+fn end_turn_finishes_a_partial_real_time_turn() {
+    let mut real_time = GameState::new();
+    real_time.clock.speed = SimulationSpeed::Custom(10.0);
+    real_time.clock.resume();
+    real_time.tick_real_time(1.0);
+    real_time.clock.pause();
+    real_time.process_turn();
+
+    let mut discrete = GameState::new();
+    discrete.process_turn();
+
+    assert_eq!(real_time.simulation.current_turn, 1);
+    assert_eq!(real_time.get_game_date(), discrete.get_game_date());
+}
@@ -0,0 +1,99 @@
+use harsh_realm_sim::faction::faction::Faction;
+use harsh_realm_sim::game_state::GameState;
+use harsh_realm_sim::simulation::turn_history::RewindError;
+use std::ptr;
+
+fn loaded_game() -> GameState {
+    let csv_path = std::path::PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("..")
+        .join("..")
+        .join("data")
+        .join("solar_system_data.csv");
+
+    let mut state = GameState::new();
+    state
+        .load_solar_system_data(csv_path.to_str().unwrap())
+        .expect("should load CSV");
+    state
+}
+
+fn earth_angle(state: &GameState) -> f64 {
+    state
+        .solar_system
+        .get_body("Earth")
+        .and_then(|b| b.orbital_state.as_ref())
+        .map(|o| o.current_position.angle)
+        .expect("Earth should have an orbit")
+}
+
+#[test]
+fn undo_restores_previous_turn() {
+    let mut state = loaded_game();
+    let start_date = state.get_game_date();
+    let start_angle = earth_angle(&state);
+
+    state.process_turn();
+    let turn_one_date = state.get_game_date();
+    let turn_one_angle = earth_angle(&state);
+    state.process_turn();
+
+    assert!(state.can_undo());
+    state.undo_turn().expect("should undo turn 2");
+    assert_eq!(state.simulation.current_turn, 1);
+    assert_eq!(state.get_game_date(), turn_one_date);
+    assert_eq!(earth_angle(&state), turn_one_angle);
+
+    state.rewind_to(0).expect("should rewind to the start");
+    assert_eq!(state.simulation.current_turn, 0);
+    assert_eq!(state.get_game_date(), start_date);
+    assert_eq!(earth_angle(&state), start_angle);
+    assert!(!state.can_undo());
+}
+
+#[test]
+fn rewind_discards_changes_made_after_the_snapshot() {
+    let mut state = GameState::new();
+    state.process_turn();
+    state.simulation.add_faction(Faction::new("Late Arrival".to_string()));
+    state.process_turn();
+
+    state.rewind_to(1).expect("should rewind to turn 1");
+    assert!(state.simulation.factions.is_empty());
+
+    // Replaying after a rewind records a fresh branch
+    state.process_turn();
+    assert_eq!(state.history.turns(), vec![0, 1, 2]);
+}
+
+#[test]
+fn snapshots_share_unchanged_maps_and_leave_out_metrics() {
+    // Mercury has no weather, so nothing changes its map between turns
+    let mut state = loaded_game();
+    let mercury = state.solar_system.get_body("Mercury").unwrap().id;
+    let seed = state.simulation.seed;
+    state.solar_system.surface_map(mercury, seed).expect("Mercury has a surface");
+    state.process_turn();
+
+    let live = state.solar_system.get_body_by_id(mercury).unwrap().surface_map().unwrap();
+    let snapshot = state.history.get(1).expect("turn 1 is in the history");
+    let kept = snapshot.solar_system.get_body_by_id(mercury).unwrap().surface_map().unwrap();
+    assert!(ptr::eq(live, kept));
+    assert!(snapshot.metrics.series().is_empty());
+    assert!(!state.metrics.series().is_empty());
+}
+
+#[test]
+fn rewind_beyond_capacity_fails() {
+    let mut state = GameState::new();
+    state.history.set_capacity(2);
+    for _ in 0..5 {
+        state.process_turn();
+    }
+
+    assert_eq!(state.history.turns(), vec![4, 5]);
+    assert!(matches!(
+        state.rewind_to(1),
+        Err(RewindError::TurnNotInHistory { requested: 1, .. })
+    ));
+    assert_eq!(state.simulation.current_turn, 5);
+}
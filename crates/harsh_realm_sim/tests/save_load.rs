@@ -0,0 +1,156 @@
+use std::path::PathBuf;
+use hex2d::Coordinate as HexCoord;
+use harsh_realm_sim::faction::faction::Faction;
+use harsh_realm_sim::game_state::GameState;
+use harsh_realm_sim::maps::location::Location;
+use harsh_realm_sim::save::migration::{Migration, MigrationRegistry};
+use harsh_realm_sim::save::save_error::SaveError;
+use harsh_realm_sim::save::save_file::{load_game, save_game, SaveOptions, SAVE_FORMAT_VERSION};
+use harsh_realm_sim::structures::installation::{Installation, InstallationPurpose};
+use harsh_realm_sim::structures::settlement::Settlement;
+use harsh_realm_sim::structures::spacecraft::Spacecraft;
+use harsh_realm_sim::structures::structure_type::StructureType;
+use serde_json::Value;
+use uuid::Uuid;
+
+fn csv_path() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("..")
+        .join("..")
+        .join("data")
+        .join("solar_system_data.csv")
+}
+
+fn temp_save_path() -> PathBuf {
+    std::env::temp_dir().join(format!("harsh_realm_save_{}.json", Uuid::new_v4()))
+}
+
+fn populated_game() -> GameState {
+    let mut state = GameState::new();
+    state
+        .load_solar_system_data(csv_path().to_str().unwrap())
+        .expect("should load CSV");
+
+    state.simulation.add_faction(Faction::new("Mars Directorate".to_string()));
+    state.simulation.add_faction(Faction::new("Belt Union".to_string()));
+    let mars = state.solar_system.get_body("Mars").unwrap().id;
+    let ceres = state.solar_system.get_body("1 Ceres").unwrap().id;
+    state.simulation.add_structure(StructureType::Settlement(Settlement::new(
+        "Jezero".to_string(),
+        Location::Surface { body_id: mars, hex_coord: HexCoord::new(2, 1) },
+    )));
+    state.simulation.add_structure(StructureType::Installation(Installation::new(
+        "Ceres Ice Mine".to_string(),
+        InstallationPurpose::Mine,
+        Location::Surface { body_id: ceres, hex_coord: HexCoord::new(0, 0) },
+    )));
+    state.simulation.add_structure(StructureType::Spacecraft(Spacecraft::new(
+        "Wanderer".to_string(),
+        Location::DeepSpace { x: 1.0, y: 2.0 },
+    )));
+
+    for _ in 0..3 {
+        state.process_turn();
+    }
+    state
+}
+
+fn round_trip(options: &SaveOptions) {
+    let state = populated_game();
+    let path = temp_save_path();
+
+    save_game(&state, &path, options).expect("should save");
+    let loaded = load_game(&path).expect("should load");
+    std::fs::remove_file(&path).ok();
+
+    assert_eq!(loaded.simulation.current_turn, 3);
+    assert_eq!(loaded.get_game_date(), state.get_game_date());
+    assert_eq!(loaded.simulation.factions.len(), 2);
+    assert_eq!(loaded.simulation.structures.len(), 3);
+    assert_eq!(
+        serde_json::to_value(&loaded).unwrap(),
+        serde_json::to_value(&state).unwrap()
+    );
+}
+
+#[test]
+fn saves_replace_the_old_file_only_once_written() {
+    let mut state = populated_game();
+    let path = temp_save_path();
+    let temp = path.with_file_name(format!("{}.tmp", path.file_name().unwrap().to_str().unwrap()));
+
+    save_game(&state, &path, &SaveOptions::default()).expect("should save");
+    state.process_turn();
+    save_game(&state, &path, &SaveOptions::default()).expect("should save over the old save");
+    assert!(!temp.exists());
+    assert_eq!(load_game(&path).expect("should load").simulation.current_turn, 4);
+    std::fs::remove_file(&path).ok();
+
+    // A save that cannot be put in place leaves nothing behind
+    let directory = temp_save_path();
+    std::fs::create_dir_all(directory.join("occupied")).unwrap();
+    assert!(save_game(&state, &directory, &SaveOptions::default()).is_err());
+    assert!(directory.join("occupied").exists());
+    assert!(!directory.with_file_name(format!("{}.tmp", directory.file_name().unwrap().to_str().unwrap())).exists());
+    std::fs::remove_dir_all(&directory).ok();
+}
+
+#[test]
+fn round_trip_compressed() {
+    round_trip(&SaveOptions { compress: true });
+}
+
+#[test]
+fn round_trip_uncompressed() {
+    round_trip(&SaveOptions { compress: false });
+}
+
+#[test]
+fn registered_migrations_upgrade_old_documents() {
+    let mut registry = MigrationRegistry::empty();
+    registry.register(Migration {
+        from_version: 1,
+        description: "rename turn to current_turn",
+        apply: |game| {
+            let simulation = game.get_mut("simulation").and_then(Value::as_object_mut).ok_or("missing simulation")?;
+            let turn = simulation.remove("turn").ok_or("missing turn")?;
+            simulation.insert("current_turn".to_string(), turn);
+            Ok(())
+        },
+    });
+
+    let mut game = serde_json::json!({ "simulation": { "turn": 7 } });
+    registry.migrate(&mut game, 1, 2).unwrap();
+    assert_eq!(game, serde_json::json!({ "simulation": { "current_turn": 7 } }));
+
+    // A failing step and a gap in the chain both stop the upgrade
+    assert!(matches!(
+        registry.migrate(&mut game, 1, 2),
+        Err(SaveError::Migration { from_version: 1, .. })
+    ));
+    let mut fresh = serde_json::json!({ "simulation": { "turn": 7 } });
+    assert!(matches!(
+        registry.migrate(&mut fresh, 1, 3),
+        Err(SaveError::MissingMigration { from_version: 2 })
+    ));
+    assert!(MigrationRegistry::default().migrate(&mut fresh, SAVE_FORMAT_VERSION, SAVE_FORMAT_VERSION).is_ok());
+}
+
+#[test]
+fn rejects_newer_and_foreign_files() {
+    let path = temp_save_path();
+    let future = serde_json::json!({
+        "format": "harsh_realm_save",
+        "format_version": SAVE_FORMAT_VERSION + 1,
+        "game": {}
+    });
+    std::fs::write(&path, serde_json::to_vec(&future).unwrap()).unwrap();
+    assert!(matches!(
+        load_game(&path),
+        Err(SaveError::UnsupportedVersion { .. })
+    ));
+
+    std::fs::write(&path, b"region,body,type").unwrap();
+    assert!(matches!(load_game(&path), Err(SaveError::NotASaveFile)));
+    std::fs::remove_file(&path).ok();
+}
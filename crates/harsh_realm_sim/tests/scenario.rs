@@ -0,0 +1,116 @@
+use std::path::PathBuf;
+use chrono::NaiveDate;
+use harsh_realm_sim::maps::location::Location;
+use harsh_realm_sim::scenario::scenario_definition::ScenarioDefinition;
+use harsh_realm_sim::scenario::scenario_error::ScenarioError;
+use harsh_realm_sim::scenario::scenario_loader::{build_game, load_scenario, load_scenario_with_seed};
+use harsh_realm_sim::structures::structure_type::StructureType;
+
+fn data_dir() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("..").join("..").join("data")
+}
+
+#[test]
+fn loads_bundled_scenario() {
+    let state = load_scenario(&data_dir().join("scenarios").join("sol_2070.json")).expect("scenario should load");
+
+    assert_eq!(state.get_game_date(), NaiveDate::from_ymd_opt(2070, 1, 1).unwrap());
+    assert_eq!(state.simulation.seed, 2070);
+    assert_eq!(state.simulation.factions.len(), 3);
+    assert_eq!(state.victory_conditions.len(), 4);
+
+    let mars = state.solar_system.get_body("Mars").unwrap();
+    let (mars_id, jezero_hex) = (mars.id, mars.surface_grid().tile_at(18.4, 77.5));
+    let jezero = state
+        .simulation
+        .structures
+        .values()
+        .find(|s| s.name() == "Jezero")
+        .expect("Jezero should exist");
+    assert_eq!(jezero.location(), &Location::Surface { body_id: mars_id, hex_coord: jezero_hex });
+    let owner = &state.simulation.factions[&jezero.owner().unwrap()];
+    assert_eq!(owner.name(), "Mars Directorate");
+
+    // Jezero's hex on the Mars surface map is claimed
+    let mars_map = state.solar_system.get_body("Mars").unwrap().surface_map().expect("Mars map should exist");
+    let tile = mars_map.tile(jezero_hex).unwrap();
+    let (latitude, longitude) = mars_map.lat_lon(jezero_hex).unwrap();
+    assert!((latitude - 18.4).abs() < 3.0 && (longitude - 77.5).abs() < 3.0);
+    assert_eq!(tile.structure_id, Some(jezero.id()));
+    assert_eq!(tile.owner, jezero.owner());
+
+    // Docked spacecraft point at the structure they are docked to
+    let gateway = state.simulation.structures.values().find(|s| s.name() == "Gateway Station").unwrap();
+    let resolute = state.simulation.structures.values().find(|s| s.name() == "TCS Resolute").unwrap();
+    assert!(matches!(resolute, StructureType::Spacecraft(_)));
+    assert_eq!(resolute.location(), &Location::Docked { structure_id: gateway.id() });
+
+    // The scenario's monthly turns are in effect
+    let mut state = state;
+    state.process_turn();
+    assert_eq!(state.get_game_date(), NaiveDate::from_ymd_opt(2070, 2, 1).unwrap());
+}
+
+#[test]
+fn reports_every_validation_error() {
+    let definition: ScenarioDefinition = serde_json::from_value(serde_json::json!({
+        "name": "Broken",
+        "solar_system_data": "solar_system_data.csv",
+        "start_date": "2070-01-01",
+        "turn_length": { "Days": 30 },
+        "factions": [
+            {
+                "name": "A",
+                "settlements": [
+                    { "name": "Twin", "location": { "Surface": { "body": "Vulcan", "q": 0, "r": 0 } } },
+                    { "name": "Twin", "location": { "Surface": { "body": "Vulcan", "q": 0, "r": 0 } } },
+                    { "name": "Polar", "location": { "Surface": { "body": "Mars", "q": 0, "r": 40 } } },
+                    { "name": "Beyond", "location": { "SurfaceAt": { "body": "Mars", "latitude": 95.0, "longitude": 0.0 } } },
+                    { "name": "Cloud City", "location": { "SurfaceAt": { "body": "Jupiter", "latitude": 0.0, "longitude": 0.0 } } }
+                ],
+                "installations": [
+                    { "name": "Fort", "purpose": "Military", "location": { "Orbit": { "body": "Mars" } }, "crew": "Crew" }
+                ],
+                "spacecraft": [
+                    { "name": "Lost", "location": { "Docked": { "structure": "Nowhere" } } }
+                ]
+            },
+            { "name": "A" }
+        ],
+        "victory_conditions": [ { "ControlBodies": { "bodies": ["Atlantis"] } } ]
+    }))
+    .unwrap();
+
+    match build_game(&definition, &data_dir()) {
+        Err(ScenarioError::Invalid(issues)) => {
+            let all = issues.join("\n");
+            assert!(all.contains("duplicate faction name 'A'"), "{}", all);
+            assert!(all.contains("duplicate structure name 'Twin'"), "{}", all);
+            assert!(all.contains("already holds a settlement"), "{}", all);
+            assert!(all.contains("unknown body 'Vulcan'"), "{}", all);
+            assert!(all.contains("'Polar' is on hex (0, 40), which is off the surface of Mars"), "{}", all);
+            assert!(all.contains("'Beyond' is at latitude 95, longitude 0, which is not a point on Mars"), "{}", all);
+            assert!(all.contains("'Cloud City' is on the surface of Jupiter, which has no surface to build on"), "{}", all);
+            assert!(all.contains("docked to 'Nowhere'"), "{}", all);
+            assert!(all.contains("'Fort' cannot be crewed by Crew"), "{}", all);
+            assert!(all.contains("unknown body 'Atlantis'"), "{}", all);
+        }
+        other => panic!("expected validation errors, got {:?}", other.map(|_| ())),
+    }
+}
+
+#[test]
+fn seed_override_reshapes_claimed_terrain() {
+    let path = data_dir().join("scenarios").join("sol_2070.json");
+    let terrain = |seed| {
+        let state = load_scenario_with_seed(&path, seed).expect("scenario should load");
+        assert_eq!(state.simulation.seed, seed);
+        // Mars is claimed while the scenario loads, so its map is generated then
+        let map = state.solar_system.get_body("Mars").unwrap().surface_map().expect("Mars map should exist");
+        let mut tiles: Vec<_> = map.tiles().map(|tile| (tile.coord, tile.terrain, tile.elevation)).collect();
+        tiles.sort_by_key(|(coord, _, _)| *coord);
+        tiles
+    };
+    assert_eq!(terrain(1), terrain(1));
+    assert_ne!(terrain(1), terrain(2));
+}
@@ -1,4 +1,6 @@
 use chrono::NaiveDate;
+use harsh_realm_sim::game_state::GameState;
+use harsh_realm_sim::simulation::calendar::{CalendarDisplay, GameCalendar, TurnLength};
 use harsh_realm_sim::universe::solar_system_manager::SolarSystemManager;
 
 #[test]
@@ -18,6 +20,7 @@ fn load_csv_and_step() {
 
     // basic sanity: at least one body loaded
     assert!(!solar.get_all_bodies().is_empty());
+    assert!(solar.get_body("Tethys (S III)").is_some());
 
     // advance 30 days (one turn) and confirm date progression
     solar.update_all_positions(30.0);
@@ -25,4 +28,44 @@ fn load_csv_and_step() {
         solar.get_game_date(),
         start_date + chrono::Duration::days(30)
     );
-}
\ No newline at end of file
+}
+
+#[test]
+fn configurable_turn_length() {
+    let start_date = NaiveDate::from_ymd_opt(2075, 2, 1).unwrap();
+    let mut state = GameState::with_calendar(GameCalendar::new(
+        start_date,
+        TurnLength::CalendarMonths(1),
+        CalendarDisplay::Gregorian,
+    ));
+
+    // February 2075 has 28 days
+    state.process_turn();
+    assert_eq!(state.get_game_date(), NaiveDate::from_ymd_opt(2075, 3, 1).unwrap());
+
+    // switch to short turns mid-game
+    state.set_turn_length(TurnLength::Days(5));
+    state.process_turn();
+    assert_eq!(state.get_game_date(), NaiveDate::from_ymd_opt(2075, 3, 6).unwrap());
+    assert_eq!(state.get_formatted_date(), "2075 March 06");
+}
+
+#[test]
+fn open_orbits_fail_the_load() {
+    let header = std::fs::read_to_string(
+        std::path::PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("../../data/solar_system_data.csv"),
+    )
+    .unwrap()
+    .lines()
+    .next()
+    .unwrap()
+    .to_string();
+    let csv = format!("{}\nSaturn Orbit,Comet,,,,294619.0,1.1,1.9,,1.1,,,1,1,1,,,,,,,,,,,\n", header);
+    let path = std::env::temp_dir().join(format!("open_orbit_{}.csv", std::process::id()));
+    std::fs::write(&path, csv).unwrap();
+
+    let mut solar = SolarSystemManager::new(NaiveDate::from_ymd_opt(2070, 1, 1).unwrap());
+    let error = solar.load_from_csv(&path).expect_err("an open orbit should not load");
+    std::fs::remove_file(&path).unwrap();
+    assert!(error.to_string().contains("Comet"), "{}", error);
+}
@@ -0,0 +1,88 @@
+use std::collections::HashMap;
+use std::path::PathBuf;
+use harsh_realm_sim::buildings::building_type::BuildingType;
+use harsh_realm_sim::events::event_bus::EventFilter;
+use harsh_realm_sim::events::game_event::{EventCategory, GameEventKind};
+use harsh_realm_sim::game_state::GameState;
+use harsh_realm_sim::faction::faction::Faction;
+use harsh_realm_sim::maps::location::Location;
+use harsh_realm_sim::resources::deposit::Deposit;
+use harsh_realm_sim::resources::resource_type::ResourceType;
+use harsh_realm_sim::resources::storage::{self, StorageType};
+use harsh_realm_sim::structures::installation::{Installation, InstallationPurpose};
+use harsh_realm_sim::structures::settlement::Settlement;
+use harsh_realm_sim::structures::spacecraft::{Spacecraft, SpacecraftModuleType};
+use harsh_realm_sim::structures::structure_type::StructureType;
+use harsh_realm_sim::units::unit_type::UnitType;
+
+fn data_path() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("..").join("..").join("data").join("solar_system_data.csv")
+}
+
+#[test]
+fn storage_buildings_and_cargo_modules_add_capacity() {
+    let here = Location::DeepSpace { x: 0.0, y: 0.0 };
+    let depot = Settlement::new("Depot".to_string(), here.clone())
+        .with_buildings(HashMap::from([(BuildingType::Warehouse, 2), (BuildingType::Mine, 1)]))
+        .with_resources(HashMap::from([(ResourceType::Metal, 1000), (ResourceType::Water, 300), (ResourceType::Energy, 10_000_000)]));
+    let depot = StructureType::Settlement(depot);
+    let capacity = storage::capacity(&depot);
+    assert_eq!(capacity[&StorageType::Bulk], storage::SETTLEMENT_BASE_CAPACITY + 2 * storage::STORAGE_BUILDING_CAPACITY);
+    assert_eq!(capacity[&StorageType::Liquid], storage::SETTLEMENT_BASE_CAPACITY);
+    assert_eq!(storage::free_space(&depot, &ResourceType::Minerals), capacity[&StorageType::Bulk] - 1000);
+    assert_eq!(storage::free_space(&depot, &ResourceType::Energy), u64::MAX);
+
+    let tanker = Spacecraft::new("Tanker".to_string(), here).with_modules(HashMap::from([(SpacecraftModuleType::LiquidTank, 3)]));
+    let tanker = StructureType::Spacecraft(tanker);
+    assert_eq!(storage::capacity(&tanker)[&StorageType::Liquid], storage::SPACECRAFT_BASE_CAPACITY + 3 * storage::CARGO_MODULE_CAPACITY);
+    assert_eq!(storage::capacity(&tanker)[&StorageType::Gas], storage::SPACECRAFT_BASE_CAPACITY);
+}
+
+#[test]
+fn overfull_holds_are_dumped_and_reported() {
+    let mut state = GameState::new();
+    state.load_solar_system_data(data_path().to_str().unwrap()).unwrap();
+    let dumped = state.simulation.events.subscribe(EventFilter::categories([EventCategory::Logistics]));
+    let luna = state.solar_system.get_body("Luna (E I)").unwrap().id;
+    let freighter = Spacecraft::new("Freighter".to_string(), Location::Orbit { body_id: luna, orbital_slot_id: luna })
+        .with_cargo(HashMap::from([(ResourceType::Minerals, 800), (ResourceType::Food, 400), (ResourceType::Air, 100)]));
+    let freighter = state.simulation.add_structure(StructureType::Spacecraft(freighter));
+    state.process_turn();
+
+    let cargo = state.simulation.structures[&freighter].stockpile().contents();
+    let bulk: u64 = cargo.iter().filter(|(r, _)| r.storage_type() == Some(StorageType::Bulk)).map(|(_, a)| a).sum();
+    assert_eq!(bulk, storage::SPACECRAFT_BASE_CAPACITY);
+    assert!(cargo.contains(&(ResourceType::Air, 100)));
+    let events = state.simulation.events.poll(dumped);
+    let reported: u64 = events
+        .iter()
+        .filter_map(|e| match e.kind {
+            GameEventKind::StockpileOverflowed { structure_id, amount, .. } if structure_id == freighter => Some(amount),
+            _ => None,
+        })
+        .sum();
+    assert_eq!(reported, 800 + 400 - storage::SPACECRAFT_BASE_CAPACITY);
+}
+
+#[test]
+fn full_mines_leave_their_deposits_in_the_ground() {
+    let mut state = GameState::new();
+    state.load_solar_system_data(data_path().to_str().unwrap()).unwrap();
+    let mars = state.solar_system.get_body("Mars").unwrap().id;
+    let hex = state.solar_system.get_body("Mars").unwrap().surface_grid().tile_at(0.0, 0.0);
+    state.surface_map_mut(mars).unwrap().tile_mut(hex).unwrap().deposits = vec![Deposit::new(ResourceType::Minerals, 10_000, 1.0)];
+
+    let miners = state.simulation.add_faction(Faction::new("Miners".to_string()));
+    let full = HashMap::from([(ResourceType::Metal, storage::INSTALLATION_CAPACITY), (ResourceType::Energy, 1_000_000)]);
+    let mine = Installation::new("Full Mine".to_string(), InstallationPurpose::Mine, Location::Surface { body_id: mars, hex_coord: hex })
+        .with_owner(miners)
+        .with_crew(UnitType::Crew)
+        .with_resources(full);
+    let mine = state.simulation.add_structure(StructureType::Installation(mine));
+    state.process_turn();
+
+    let mined = state.simulation.structures[&mine].stockpile().available(&ResourceType::Minerals);
+    assert_eq!(mined, 0);
+    assert_eq!(state.surface_map_mut(mars).unwrap().tile(hex).unwrap().deposits[0].quantity, 10_000);
+    assert_eq!(state.simulation.structures[&mine].stockpile().available(&ResourceType::Metal), storage::INSTALLATION_CAPACITY);
+}
@@ -0,0 +1,47 @@
+use std::path::PathBuf;
+use harsh_realm_sim::game_state::GameState;
+use harsh_realm_sim::maps::tile::Terrain;
+use harsh_realm_sim::procedural_generation::terrain_generator::{SurfaceClass, TerrainProfile};
+
+fn game(seed: u64) -> GameState {
+    let mut state = GameState::new();
+    state.simulation.seed = seed;
+    let csv = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("../../data/solar_system_data.csv");
+    state.solar_system.load_from_csv(&csv).unwrap();
+    state
+}
+
+#[test]
+fn classifies_bodies_from_their_data() {
+    let state = game(0);
+    let class = |name: &str| {
+        let body = state.solar_system.get_body(name).unwrap();
+        TerrainProfile::for_body(body, &state.solar_system).class
+    };
+    assert_eq!(class("Earth"), SurfaceClass::Temperate);
+    assert_eq!(class("Mars"), SurfaceClass::Arid);
+    assert_eq!(class("Venus"), SurfaceClass::Arid);
+    assert_eq!(class("Mercury"), SurfaceClass::Barren);
+    assert_eq!(class("Luna (E I)"), SurfaceClass::Barren);
+    assert_eq!(class("Io (J I)"), SurfaceClass::Volcanic);
+    assert_eq!(class("Europa (J II)"), SurfaceClass::Frozen);
+    assert_eq!(class("Jupiter"), SurfaceClass::Giant);
+}
+
+#[test]
+fn surface_maps_regenerate_identically() {
+    let mut first = game(42);
+    let mut second = game(42);
+    let earth = first.solar_system.get_body("Earth").unwrap().id;
+    let earth_again = second.solar_system.get_body("Earth").unwrap().id;
+
+    let map = first.surface_map_mut(earth).unwrap().clone();
+    let mut again = second.surface_map_mut(earth_again).unwrap().clone();
+    again.body_id = map.body_id;
+    assert_eq!(map, again);
+    assert!(map.tiles().any(|tile| tile.terrain == Terrain::Ocean));
+
+    // Generated maps are kept, so later lookups see the same tiles
+    let first_tile = map.tiles().next().unwrap().clone();
+    assert_eq!(first.surface_map_mut(earth).unwrap().tile(first_tile.coord), Some(&first_tile));
+}
@@ -0,0 +1,116 @@
+use std::path::PathBuf;
+use harsh_realm_sim::faction::faction::Faction;
+use harsh_realm_sim::game_state::GameState;
+use harsh_realm_sim::maps::location::Location;
+use harsh_realm_sim::population::person_type::PersonType;
+use harsh_realm_sim::resources::deposit::Deposit;
+use harsh_realm_sim::resources::resource_type::ResourceType;
+use harsh_realm_sim::structures::installation::{Installation, InstallationPurpose};
+use harsh_realm_sim::structures::settlement::Settlement;
+use harsh_realm_sim::structures::structure_type::StructureType;
+use hex2d::Coordinate as HexCoord;
+use uuid::Uuid;
+
+struct World {
+    state: GameState,
+    mars: Uuid,
+    reds: Uuid,
+    blues: Uuid,
+}
+
+fn world() -> World {
+    let mut state = GameState::new();
+    let csv = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("../../data/solar_system_data.csv");
+    state.solar_system.load_from_csv(&csv).unwrap();
+    let mars = state.solar_system.get_body("Mars").unwrap().id;
+    let reds = state.simulation.add_faction(Faction::new("Reds".to_string()));
+    let blues = state.simulation.add_faction(Faction::new("Blues".to_string()));
+    World { state, mars, reds, blues }
+}
+
+impl World {
+    fn at(&self, hex_coord: HexCoord) -> Location {
+        Location::Surface { body_id: self.mars, hex_coord }
+    }
+
+    fn settle(&mut self, owner: Uuid, hex: HexCoord, population: u32) -> Uuid {
+        let settlement = Settlement::new("Town".to_string(), self.at(hex))
+            .with_owner(owner)
+            .with_population([(PersonType::Colonist, population)].into());
+        self.state.simulation.add_structure(StructureType::Settlement(settlement))
+    }
+
+    fn tile(&mut self, hex: HexCoord) -> harsh_realm_sim::maps::tile::Tile {
+        self.state.surface_map_mut(self.mars).unwrap().tile(hex).unwrap().clone()
+    }
+
+    fn neighbors(&mut self, hex: HexCoord) -> Vec<HexCoord> {
+        self.state.surface_map_mut(self.mars).unwrap().neighbors(hex)
+    }
+}
+
+#[test]
+fn large_settlements_claim_their_surroundings() {
+    let mut world = world();
+    let centre = world.state.solar_system.get_body("Mars").unwrap().surface_grid().tile_at(0.0, 0.0);
+    world.settle(world.reds, centre, 10_000);
+    world.state.process_turn();
+
+    // Population 10,000 reaches five hexes out
+    let reds = world.reds;
+    let map = world.state.surface_map_mut(world.mars).unwrap();
+    assert_eq!(map.tile(centre).unwrap().owner, Some(reds));
+    assert!(map.owned_by(reds).count() > 60);
+    assert!(map.tiles().all(|tile| !tile.contested));
+}
+
+#[test]
+fn evenly_matched_neighbours_contest_the_hexes_between_them() {
+    let mut world = world();
+    let west = world.state.solar_system.get_body("Mars").unwrap().surface_grid().tile_at(0.0, 0.0);
+    let middle = world.neighbors(west)[0];
+    let west_neighbors = world.neighbors(west);
+    let east = world
+        .neighbors(middle)
+        .into_iter()
+        .find(|hex| *hex != west && !west_neighbors.contains(hex))
+        .unwrap();
+    world.settle(world.reds, west, 0);
+    let blue_town = world.settle(world.blues, east, 0);
+    world.state.process_turn();
+
+    assert_eq!(world.tile(west).owner, Some(world.reds));
+    assert_eq!(world.tile(east).owner, Some(world.blues));
+    let middle_tile = world.tile(middle);
+    assert!(middle_tile.contested);
+    assert_eq!(middle_tile.owner, None);
+
+    // Once the Blues leave, the Reds take the middle and the Blues' hexes lapse
+    world.state.simulation.structures.remove(&blue_town);
+    world.state.process_turn();
+    assert_eq!(world.tile(middle).owner, Some(world.reds));
+    assert!(!world.tile(middle).contested);
+    assert_eq!(world.tile(east).owner, None);
+}
+
+#[test]
+fn mines_in_enemy_territory_cannot_extract() {
+    let mut world = world();
+    let city = world.state.solar_system.get_body("Mars").unwrap().surface_grid().tile_at(0.0, 0.0);
+    let mine_hex = world.neighbors(city)[0];
+    world.state.surface_map_mut(world.mars).unwrap().tile_mut(mine_hex).unwrap().deposits =
+        vec![Deposit::new(ResourceType::Minerals, 10_000, 1.0)];
+
+    world.settle(world.reds, city, 10_000);
+    let mine = Installation::new("Claim Jumper".to_string(), InstallationPurpose::Mine, world.at(mine_hex))
+        .with_owner(world.blues);
+    let mine_id = world.state.simulation.add_structure(StructureType::Installation(mine));
+    world.state.process_turn();
+
+    assert_eq!(world.tile(mine_hex).owner, Some(world.reds));
+    let StructureType::Installation(mine) = &world.state.simulation.structures[&mine_id] else {
+        panic!("mine should be an installation");
+    };
+    assert!(mine.resources().is_empty());
+    assert_eq!(world.tile(mine_hex).deposits[0].quantity, 10_000);
+}
@@ -0,0 +1,163 @@
+use std::collections::HashMap;
+use std::path::PathBuf;
+use harsh_realm_sim::faction::faction::Faction;
+use harsh_realm_sim::game_state::GameState;
+use harsh_realm_sim::headless::runner::run_game;
+use harsh_realm_sim::headless::runner_options::RunnerOptions;
+use harsh_realm_sim::maps::location::Location;
+use harsh_realm_sim::population::person_type::PersonType;
+use harsh_realm_sim::save::save_file::{load_game, save_game, SaveOptions};
+use harsh_realm_sim::structures::settlement::Settlement;
+use harsh_realm_sim::structures::spacecraft::Spacecraft;
+use harsh_realm_sim::structures::structure_type::StructureType;
+use harsh_realm_sim::victory::victory_condition::VictoryCondition;
+use harsh_realm_sim::victory::victory_engine::evaluate;
+use uuid::Uuid;
+
+fn data_path() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("..").join("..").join("data").join("solar_system_data.csv")
+}
+
+fn settle(state: &mut GameState, name: &str, owner: Uuid, colonists: u32) -> Uuid {
+    let settlement = Settlement::new(name.to_string(), Location::DeepSpace { x: 0.0, y: 0.0 })
+        .with_owner(owner)
+        .with_population(HashMap::from([(PersonType::Colonist, colonists)]));
+    state.simulation.add_structure(StructureType::Settlement(settlement))
+}
+
+/// Two factions, one settlement each
+fn two_faction_game() -> (GameState, Uuid, Uuid) {
+    let mut state = GameState::new();
+    let alpha = state.simulation.add_faction(Faction::new("Alpha".to_string()).with_treasury(100.0));
+    let beta = state.simulation.add_faction(Faction::new("Beta".to_string()).with_treasury(300.0));
+    settle(&mut state, "Alpha Prime", alpha, 100);
+    settle(&mut state, "Beta Prime", beta, 400);
+    (state, alpha, beta)
+}
+
+#[test]
+fn population_threshold_ends_the_game() {
+    let (mut state, alpha, beta) = two_faction_game();
+    state.victory_conditions = vec![VictoryCondition::PopulationThreshold { population: 500 }];
+
+    state.process_turn();
+    assert!(!state.is_game_over());
+    let standings = state.victory_standings();
+    assert_eq!(standings[0].faction_id, beta);
+    assert!((standings[0].score - 0.8).abs() < 1e-9);
+
+    settle(&mut state, "Alpha Secundus", alpha, 450);
+    state.process_turn();
+    let outcome = state.game_over.as_ref().expect("alpha should have won");
+    assert_eq!(outcome.winner, Some(alpha));
+    assert_eq!(outcome.turn, 2);
+    assert_eq!(outcome.reason, VictoryCondition::PopulationThreshold { population: 500 });
+    assert_eq!(outcome.rankings[0].faction_id, alpha);
+}
+
+#[test]
+fn economic_dominance_and_elimination() {
+    let (mut state, alpha, beta) = two_faction_game();
+    state.victory_conditions = vec![VictoryCondition::EconomicDominance { share: 0.75 }, VictoryCondition::Elimination];
+
+    // Beta holds 300 of 400 wealth, which is exactly the share it needs
+    let report = evaluate(&state);
+    let outcome = report.outcome.expect("beta should dominate the economy");
+    assert_eq!(outcome.winner, Some(beta));
+    assert_eq!(outcome.reason, VictoryCondition::EconomicDominance { share: 0.75 });
+
+    // With beta's settlement gone, alpha is the last faction standing
+    let beta_settlement = state
+        .simulation
+        .structures
+        .values()
+        .find(|s| s.owner() == Some(beta))
+        .map(|s| s.id())
+        .unwrap();
+    state.simulation.structures.remove(&beta_settlement);
+    let report = evaluate(&state);
+    assert!(report.standings.last().unwrap().eliminated);
+    let outcome = report.outcome.expect("alpha should win by elimination");
+    assert_eq!(outcome.winner, Some(alpha));
+    assert_eq!(outcome.reason, VictoryCondition::Elimination);
+}
+
+#[test]
+fn a_zero_share_of_the_economy_is_already_held() {
+    let (mut state, _, beta) = two_faction_game();
+    state.victory_conditions = vec![VictoryCondition::EconomicDominance { share: 0.0 }];
+
+    let report = evaluate(&state);
+    assert!(report.standings.iter().all(|s| s.score == 1.0 && s.progress[0].met));
+    // Wealth breaks the tie
+    assert_eq!(report.outcome.expect("the condition is met").winner, Some(beta));
+}
+
+#[test]
+fn control_bodies_and_technology() {
+    let mut state = GameState::new();
+    state.load_solar_system_data(data_path().to_str().unwrap()).unwrap();
+    let alpha = state.simulation.add_faction(Faction::new("Alpha".to_string()));
+    let mars = state.solar_system.get_body("Mars").unwrap().id;
+    let settlement = Settlement::new(
+        "Jezero".to_string(),
+        Location::Surface { body_id: mars, hex_coord: hex2d::Coordinate::new(0, 0) },
+    )
+    .with_owner(alpha);
+    state.simulation.add_structure(StructureType::Settlement(settlement));
+    state.victory_conditions = vec![
+        VictoryCondition::ControlBodies { bodies: vec!["Earth".to_string(), "Mars".to_string()] },
+        VictoryCondition::TechnologyMilestone { technology: "Fusion Drive".to_string() },
+    ];
+
+    // Jezero's hexes are claimed when territory is resolved
+    state.process_turn();
+    let report = evaluate(&state);
+    assert!(report.outcome.is_none());
+    assert!((report.standings[0].progress[0].progress - 0.5).abs() < 1e-9);
+    assert_eq!(report.standings[0].progress[1].progress, 0.0);
+
+    state.simulation.factions.get_mut(&alpha).unwrap().research("Fusion Drive".to_string());
+    let outcome = evaluate(&state).outcome.expect("alpha reached the milestone");
+    assert_eq!(outcome.reason, VictoryCondition::TechnologyMilestone { technology: "Fusion Drive".to_string() });
+}
+
+#[test]
+fn a_lone_ship_in_orbit_does_not_control_a_body() {
+    let mut state = GameState::new();
+    state.load_solar_system_data(data_path().to_str().unwrap()).unwrap();
+    let alpha = state.simulation.add_faction(Faction::new("Alpha".to_string()));
+    let mars = state.solar_system.get_body("Mars").unwrap().id;
+    let scout = Spacecraft::new("Scout".to_string(), Location::Orbit { body_id: mars, orbital_slot_id: Uuid::new_v4() })
+        .with_owner(alpha);
+    state.simulation.add_structure(StructureType::Spacecraft(scout));
+    state.victory_conditions = vec![VictoryCondition::ControlBodies { bodies: vec!["Mars".to_string()] }];
+
+    state.process_turn();
+    assert!(!state.is_game_over());
+    assert_eq!(state.victory_standings()[0].progress[0].progress, 0.0);
+}
+
+#[test]
+fn turn_limit_stops_headless_run_and_survives_save() {
+    let (mut state, _, beta) = two_faction_game();
+    state.victory_conditions = vec![
+        VictoryCondition::PopulationThreshold { population: 10_000 },
+        VictoryCondition::TurnLimit { turns: 3 },
+    ];
+
+    let options = RunnerOptions { turns: 10, ..Default::default() };
+    let mut out = Vec::new();
+    let report = run_game(&mut state, &options, &mut out).unwrap();
+    assert_eq!(report.turns_run, 3);
+    let outcome = report.outcome.expect("turn limit should end the game");
+    assert_eq!(outcome.winner, Some(beta));
+    let output = String::from_utf8(out).unwrap();
+    assert!(output.contains("Game over on turn 3: Beta wins"), "{}", output);
+
+    let path = std::env::temp_dir().join(format!("victory_{}.hrsave", Uuid::new_v4()));
+    save_game(&state, &path, &SaveOptions::default()).unwrap();
+    let loaded = load_game(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(loaded.game_over, state.game_over);
+}